@@ -0,0 +1,105 @@
+//! Checking throughput on synthetic prose and code, and suggestion latency
+//! against a large dictionary. Run with:
+//!
+//!     cargo bench --no-default-features --bench throughput
+//!
+//! Everything is built in memory, so results don't depend on the
+//! dictionaries installed on the machine.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use spellchecker::dictionary::Dictionary;
+use spellchecker::suggest::{EditDistanceProvider, SuggestionProvider};
+use spellchecker::{Language, SpellChecker};
+
+const SYLLABLES: &[&str] = &[
+    "ba", "con", "de", "fi", "gra", "hel", "in", "jo", "ka", "lo", "men", "nor",
+    "pe", "qui", "ra", "sto", "ter", "un", "ver", "wo", "xa", "yo", "zen", "tion",
+];
+
+/// `count` distinct made-up words of two to four syllables
+fn synthetic_words(count: usize) -> Vec<String> {
+    let n = SYLLABLES.len();
+    (0..count)
+        .map(|i| {
+            let mut word = String::new();
+            let mut rest = i;
+            loop {
+                word.push_str(SYLLABLES[rest % n]);
+                rest /= n;
+                if rest == 0 && word.len() > 3 {
+                    break word;
+                }
+            }
+        })
+        .collect()
+}
+
+/// Prose of `words` dictionary words, one in fifty misspelled
+fn synthetic_prose(dictionary: &[String], words: usize) -> String {
+    let mut text = String::new();
+    for i in 0..words {
+        let word = &dictionary[(i * 7919) % dictionary.len()];
+        if i % 50 == 49 {
+            text.push_str(&word.chars().rev().collect::<String>());
+        } else {
+            text.push_str(word);
+        }
+        text.push(if i % 12 == 11 { '\n' } else { ' ' });
+    }
+    text
+}
+
+/// A Rust-looking file whose comments and identifiers use dictionary words
+fn synthetic_code(dictionary: &[String], functions: usize) -> String {
+    let mut text = String::new();
+    for i in 0..functions {
+        let a = &dictionary[(i * 31) % dictionary.len()];
+        let b = &dictionary[(i * 101) % dictionary.len()];
+        text.push_str(&format!(
+            "/// Returns the {a} for a {b}\nfn {a}_{b}(value: usize) -> usize {{\n    let {b} = value * 2; // {a} {b}\n    {b} + 1\n}}\n\n"
+        ));
+    }
+    text
+}
+
+fn checker_with(words: &[String]) -> SpellChecker {
+    let mut checker = SpellChecker::new(Language::English).expect("checker");
+    checker.use_dictionary(Dictionary::from_words(Language::English, words.iter().cloned()));
+    checker
+}
+
+fn bench_check_document(c: &mut Criterion) {
+    let words = synthetic_words(20_000);
+    let checker = checker_with(&words);
+    
+    let mut group = c.benchmark_group("check_document");
+    for size in [1_000, 10_000] {
+        let prose = synthetic_prose(&words, size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("prose_words", size), &prose, |b, text| {
+            b.iter(|| checker.check_document_with_context(black_box(text), Some("notes.txt")))
+        });
+    }
+    
+    let code = synthetic_code(&words, 500);
+    let code_words = checker.check_document_with_context(&code, Some("lib.rs")).total_words;
+    group.throughput(Throughput::Elements(code_words as u64));
+    group.bench_function("rust_file", |b| {
+        b.iter(|| checker.check_document_with_context(black_box(&code), Some("lib.rs")))
+    });
+    group.finish();
+}
+
+fn bench_suggestions(c: &mut Criterion) {
+    let words = synthetic_words(100_000);
+    let dictionary = Dictionary::from_words(Language::English, words.iter().cloned());
+    let provider = EditDistanceProvider::new();
+    let typo = words[4242].chars().rev().collect::<String>();
+    
+    c.bench_function("suggest/100k_words", |b| {
+        b.iter(|| provider.suggest(black_box(&typo), Language::English, &dictionary))
+    });
+}
+
+criterion_group!(benches, bench_check_document, bench_suggestions);
+criterion_main!(benches);
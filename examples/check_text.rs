@@ -0,0 +1,22 @@
+//! Spell check text from the command line using only the library, with no
+//! GUI dependencies. This must keep compiling with:
+//!
+//!     cargo build --example check_text --no-default-features
+
+use spellchecker::{Language, SpellChecker};
+
+fn main() -> anyhow::Result<()> {
+    let text = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    let text = if text.is_empty() { "I recieve the letter".to_string() } else { text };
+    
+    let mut checker = SpellChecker::new(Language::English)?;
+    checker.enable_suggestions(true);
+    let analysis = checker.check_document(&text);
+    
+    for word in analysis.words.iter().filter(|w| !w.is_correct) {
+        println!("{}:{} {} -> {}", word.line, word.column, word.original, word.suggestions.join(", "));
+    }
+    println!("{} words, {} misspelled", analysis.total_words, analysis.misspelled_words);
+    
+    Ok(())
+}
@@ -1,29 +1,106 @@
-use crate::dictionary::{Dictionary, DictionaryManager};
-use crate::language::Language;
-use crate::util::{sanitize_word, is_valid_word, is_code_file, is_likely_code};
+use crate::dictionary::{Dictionary, DictionaryManager, DictionarySource, ImportMode, LoadState};
+use crate::language::{Language, LanguageManager};
+use crate::rules::IgnoreRule;
+use crate::settings::CheckerSettings;
+use crate::suggest::{EditDistanceProvider, KeyboardLayout, SuggestionProvider};
+use crate::util::{byte_to_char_column, html_text_spans, is_cjk_text, sanitize_word, is_valid_word, is_code_file, is_likely_code, expand_to_word_boundaries, line_start_offsets, markdown_section, match_case, metaphone, programming_language_from_filename, split_identifier};
 use dashmap::DashMap;
-use rayon::prelude::*;
-use serde::Serialize;
-use std::collections::HashSet;
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WordCheck {
     pub word: String,
     pub original: String,
+    /// Byte offsets of the word in the whole checked text
     pub start: usize,
     pub end: usize,
     pub is_correct: bool,
+    /// Whether the dictionary rejected the word, even if its confidence was
+    /// below the threshold and `is_correct` was forced to true
+    pub raw_misspelled: bool,
     pub suggestions: Vec<String>,
     pub line: usize,
+    /// 1-based character (not byte) column within the line
     pub column: usize,
     pub confidence: f32,
     pub word_type: WordType,
+    /// For split code identifiers, the part that was misspelled
+    pub subword: Option<String>,
+    #[serde(default)]
+    pub kind: IssueKind,
+    /// The word with some of its line around it, for flagged words when
+    /// `SpellChecker::set_capture_context` is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<WordContext>,
+    /// The name of the `IgnoreRule` that matched, for text it let through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_rule: Option<String>,
+    /// How prominently to show the word if it's flagged
+    #[serde(default)]
+    pub severity: Severity,
+    /// A secondary language the word is spelled correctly in, which makes
+    /// it a hint rather than an error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_in: Option<Language>,
+}
+
+impl WordCheck {
+    /// The word as written; `word` is lowercased for lookups
+    pub fn display_word(&self) -> &str {
+        &self.original
+    }
+    
+    /// The flagged text as it should be listed, with whitespace made visible
+    pub fn display_text(&self) -> String {
+        match self.kind {
+            IssueKind::Whitespace => self.original.replace(' ', "·").replace('\t', "→"),
+            _ => self.display_word().to_string(),
+        }
+    }
+    
+    /// `suggestion` cased to match the word as written, for inserting in
+    /// its place
+    pub fn replacement(&self, suggestion: &str) -> String {
+        match_case(suggestion, &self.original)
+    }
+    
+    /// A short remark to show with the word, e.g. "valid in Spanish"
+    pub fn note(&self) -> Option<String> {
+        self.valid_in.map(|language| format!("valid in {}", language.name()))
+    }
+    
+    /// What applying `suggestion` does, for a button or menu entry
+    pub fn suggestion_label(&self, suggestion: &str) -> String {
+        match (self.kind, suggestion) {
+            (IssueKind::Whitespace, "") => "Remove trailing whitespace".to_string(),
+            (IssueKind::Whitespace, _) => "Use a single space".to_string(),
+            _ => self.replacement(suggestion),
+        }
+    }
+}
+
+/// A snippet of the line a word is on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordContext {
+    pub text: String,
+    /// Byte offset of the word within `text`
+    pub offset: usize,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+/// Characters of the line kept on each side of a word in its `WordContext`
+const CONTEXT_CHARS: usize = 20;
+
+/// Serialized by variant name ("CodeIdentifier"); renaming a variant
+/// changes the JSON output
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
 pub enum WordType {
     Normal,
     CodeIdentifier,
@@ -33,9 +110,163 @@ pub enum WordType {
     Number,
     Symbol,
     ShortWord,
+    Url,
+    Email,
+    /// The same word twice in a row ("the the"); not a spelling error
+    RepeatedWord,
+}
+
+/// What kind of problem a flagged `WordCheck` reports
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum IssueKind {
+    /// Misspelled or repeated words, and words that aren't flagged at all
+    #[default]
+    Spelling,
+    /// A lowercase sentence start or a lowercase "i"; the suggestion is the
+    /// capitalized form
+    Capitalization,
+    /// Letters from more than one of Latin, Cyrillic and Greek in one word,
+    /// usually a pasted lookalike; the suggestion uses the dominant script
+    MixedScript,
+    /// Spaces in a row between words, suggesting one space, or whitespace
+    /// at the end of a line, suggesting nothing
+    Whitespace,
+}
+
+/// How prominently a flagged word should be shown, separate from how sure
+/// the checker is that it's wrong. Words that aren't flagged are hints.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Hint,
+}
+
+/// Misspelled plain words at least this confident are errors; below it
+/// they are warnings
+const ERROR_CONFIDENCE: f32 = 0.7;
+
+impl Severity {
+    /// The severity of a spelling issue on a `word_type` word flagged with
+    /// `confidence`. Unknown names and acronyms are often fine, and code
+    /// identifiers are often abbreviated on purpose.
+    pub fn for_spelling(word_type: &WordType, confidence: f32) -> Self {
+        match word_type {
+            WordType::ProperNoun | WordType::Acronym => Severity::Hint,
+            WordType::CodeIdentifier | WordType::RepeatedWord => Severity::Warning,
+            WordType::Normal | WordType::TechnicalTerm if confidence >= ERROR_CONFIDENCE => Severity::Error,
+            WordType::Normal | WordType::TechnicalTerm => Severity::Warning,
+            _ => Severity::Hint,
+        }
+    }
+}
+
+/// How tokens that mix letters and digits ("covid19", "h1b", "mp3") are
+/// checked. Tokens of two characters or fewer are short words and are never
+/// checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum AlphanumericPolicy {
+    /// Never flag them
+    AlwaysSkip,
+    /// Skip them unless letters outnumber digits, and look the rest up
+    /// whole, so "b2b" and "covid19" must be dictionary words but "x264"
+    /// is skipped. The default.
+    #[default]
+    SkipIfMostlyDigits,
+    /// Check each run of letters on its own: "covid19" is checked as
+    /// "covid"; runs shorter than the minimum word length pass
+    CheckLettersOnly,
+}
+
+/// Multipliers applied to a dictionary miss's base confidence of 0.5 to
+/// decide how sure the checker is that it's a typo. The product is clamped
+/// to `0.0..=1.0` and compared with the confidence threshold, so lowering a
+/// weight makes that kind of word less likely to be flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfidenceWeights {
+    pub normal: f32,
+    /// Identifiers in source code
+    pub code_identifier: f32,
+    /// Identifier-like words in prose
+    pub code_identifier_in_prose: f32,
+    pub acronym: f32,
+    pub proper_noun: f32,
+    pub technical_term: f32,
+    /// Every other word type
+    pub other: f32,
+    /// Words shorter than three bytes
+    pub short_word: f32,
+    /// Words longer than twenty bytes
+    pub long_word: f32,
+    /// Words containing `_` or `-`
+    pub joined_word: f32,
+    /// Words containing a part that's often misspelled, such as "ie" or "tion"
+    pub typo_pattern: f32,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        Self {
+            normal: 1.2,
+            code_identifier: 0.3,
+            code_identifier_in_prose: 0.8,
+            acronym: 0.4,
+            proper_noun: 0.6,
+            technical_term: 0.8,
+            other: 0.2,
+            short_word: 0.3,
+            long_word: 0.7,
+            joined_word: 1.1,
+            typo_pattern: 1.3,
+        }
+    }
 }
 
+/// How the checker decided on a single word, from `SpellChecker::explain`
 #[derive(Debug, Clone, Serialize)]
+pub struct WordExplanation {
+    pub word: String,
+    pub word_type: WordType,
+    /// Why the word was skipped without a dictionary lookup, if it was
+    pub skip_reason: Option<&'static str>,
+    pub in_dictionary: bool,
+    pub in_user_dictionary: bool,
+    pub in_project_dictionary: bool,
+    pub ignored: bool,
+    pub is_correct: bool,
+    pub confidence: f32,
+    /// Whether the word would be reported as an error
+    pub flagged: bool,
+}
+
+impl WordExplanation {
+    pub fn skipped(&self) -> bool {
+        self.skip_reason.is_some()
+    }
+    
+    /// One fact per line, for tooltips and terminal output
+    pub fn summary(&self) -> String {
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+        let mut lines = vec![format!("Type: {:?}", self.word_type)];
+        match self.skip_reason {
+            Some(reason) => lines.push(format!("Skipped: {}", reason)),
+            None => lines.push("Skipped: no".to_string()),
+        }
+        lines.push(format!("In dictionary: {}", yes_no(self.in_dictionary)));
+        lines.push(format!("In user dictionary: {}", yes_no(self.in_user_dictionary)));
+        lines.push(format!("In project dictionary: {}", yes_no(self.in_project_dictionary)));
+        lines.push(format!("Ignored: {}", yes_no(self.ignored)));
+        lines.push(format!("Confidence: {:.0}%", self.confidence * 100.0));
+        lines.push(format!("Flagged: {}", yes_no(self.flagged)));
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentAnalysis {
     pub total_words: usize,
     pub misspelled_words: usize,
@@ -48,25 +279,303 @@ pub struct DocumentAnalysis {
     pub likely_code: bool,
     pub file_type: Option<String>,
     pub unique_words: usize,
+    /// Set when `SpellChecker::set_max_errors` cut off the flagged words;
+    /// `misspelled_words` still counts them all
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl DocumentAnalysis {
+    /// Read back an analysis serialized to JSON, e.g. by the CLI's `--json`
+    /// output
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+    
+    /// Each distinct misspelled word, lowercased, mapped to its top
+    /// suggestion. When a word occurs more than once, the first occurrence
+    /// with a suggestion wins. Repeated words and capitalization issues are
+    /// left out.
+    pub fn correction_map(&self) -> BTreeMap<String, Option<String>> {
+        let mut map: BTreeMap<String, Option<String>> = BTreeMap::new();
+        for word in self.words.iter().filter(|w| !w.is_correct && w.kind == IssueKind::Spelling && w.word_type != WordType::RepeatedWord) {
+            let entry = map.entry(word.original.to_lowercase()).or_default();
+            if entry.is_none() {
+                *entry = word.suggestions.first().cloned();
+            }
+        }
+        map
+    }
+    
+    /// Build the edits needed to replace every misspelled word that has at
+    /// least `min_confidence` with its top suggestion. Edits are
+    /// `(start, end, replacement)` byte ranges into `text`, sorted by start
+    /// and never overlapping; apply them from the end backwards so earlier
+    /// offsets stay valid. Words whose text no longer matches (stale analysis)
+    /// or that have no suggestions are left untouched.
+    pub fn fix_all_edits(&self, text: &str, min_confidence: f32) -> Vec<(usize, usize, String)> {
+        let mut edits: Vec<(usize, usize, String)> = self.words
+            .iter()
+            .filter(|w| !w.is_correct && w.confidence >= min_confidence)
+            .filter_map(|w| {
+                let replacement = w.suggestions.first()?;
+                (text.get(w.start..w.end)? == w.original).then(|| (w.start, w.end, w.replacement(replacement)))
+            })
+            .collect();
+        
+        edits.sort_by_key(|(start, _, _)| *start);
+        
+        let mut last_end = 0;
+        edits.retain(|(start, end, _)| {
+            if *start < last_end {
+                return false;
+            }
+            last_end = *end;
+            true
+        });
+        
+        edits
+    }
+    
+    /// Misspelled words grouped by their 1-based line, in line order.
+    /// Low-confidence words the checker suppressed are not included.
+    pub fn errors_by_line(&self) -> BTreeMap<usize, Vec<&WordCheck>> {
+        let mut lines: BTreeMap<usize, Vec<&WordCheck>> = BTreeMap::new();
+        for word in self.words.iter().filter(|w| !w.is_correct) {
+            lines.entry(word.line).or_default().push(word);
+        }
+        lines
+    }
+    
+    pub fn error_count_on_line(&self, line: usize) -> usize {
+        self.words.iter().filter(|w| !w.is_correct && w.line == line).count()
+    }
+    
+    /// Replace the results for bytes `start..end` of `text` with `range`, the
+    /// result of `SpellChecker::check_range` over the same bytes. The range
+    /// is widened to word boundaries the same way. Totals are adjusted by the
+    /// difference; words the checker skips outright (numbers, known
+    /// acronyms and the like) can't be told apart afterwards, so
+    /// `total_words` is approximate once merged.
+    pub fn merge_range(&mut self, text: &str, start: usize, end: usize, range: DocumentAnalysis) {
+        let (start, end) = expand_to_word_boundaries(text, start, end);
+        let in_range = |w: &WordCheck| w.start < end && w.end > start;
+        
+        let (removed, kept): (Vec<WordCheck>, Vec<WordCheck>) = std::mem::take(&mut self.words)
+            .into_iter()
+            .partition(|w| in_range(w));
+        
+        let counted = |w: &&WordCheck| !matches!(
+            w.word_type,
+            WordType::Url | WordType::Email | WordType::RepeatedWord | WordType::Number | WordType::Symbol | WordType::ShortWord
+        );
+        
+        self.total_words = (self.total_words + range.total_words).saturating_sub(removed.iter().filter(counted).count());
+        self.misspelled_words = (self.misspelled_words + range.misspelled_words)
+            .saturating_sub(removed.iter().filter(|w| !w.is_correct).count());
+        self.suggestions_count = (self.suggestions_count + range.suggestions_count)
+            .saturating_sub(removed.iter().map(|w| w.suggestions.len()).sum());
+        
+        self.truncated |= range.truncated;
+        self.words = kept;
+        self.words.extend(range.words);
+        self.words.sort_by_key(|w| (w.line, w.start));
+        self.unique_words = self.words.iter().filter(counted).map(|w| &w.word).collect::<HashSet<_>>().len();
+        
+        // The merged totals are approximate, so keep the errors in range
+        let uncounted_issues = self.words
+            .iter()
+            .filter(|w| matches!(w.kind, IssueKind::Whitespace | IssueKind::Capitalization) || w.word_type == WordType::RepeatedWord)
+            .count();
+        let word_errors = self.misspelled_words.saturating_sub(uncounted_issues).min(self.total_words);
+        self.accuracy = word_accuracy(self.total_words, word_errors);
+    }
+}
+
+/// Percentage of `total_words` that aren't among `word_errors`, rounded
+fn word_accuracy(total_words: usize, word_errors: usize) -> f32 {
+    if total_words > 0 {
+        ((total_words - word_errors) as f32 / total_words as f32 * 100.0).round()
+    } else {
+        100.0
+    }
+}
+
+/// Name of the project word list looked up next to checked documents
+pub const PROJECT_DICTIONARY_FILE: &str = ".atomspell-dict.txt";
+
+/// Acronyms written to a language's `acronyms_<code>.csv` when it is first
+/// created
+const DEFAULT_ACRONYMS: &[&str] = &[
+    "api", "http", "https", "url", "uri", "html", "css", "js", "ts",
+    "json", "xml", "sql", "nosql", "cpu", "gpu", "ram", "rom", "usb",
+    "ssd", "hdd", "lan", "wan", "vpn", "dns", "ip", "tcp", "udp",
+    "ftp", "ssh", "ssl", "tls", "csv", "pdf", "doc", "jpg", "png",
+    "gif", "mp3", "mp4", "avi", "mkv", "zip", "rar", "tar", "gz",
+    "exe", "dll", "so", "dylib", "bin", "iso", "img", "vm", "aws",
+    "gcp", "azure", "ui", "ux", "cli", "gui", "ide", "sdk",
+];
+
+/// Per-document settings shared by every line of a check
+struct LineContext<'a> {
+    dictionary: &'a Dictionary,
+    word_pattern: &'a Regex,
+    checked_spans: Option<&'a [(usize, usize)]>,
+    is_code: bool,
+    /// Two trailing spaces are a line break in Markdown, not a mistake
+    is_markdown: bool,
+    retain_correct: bool,
+}
+
+/// Running totals while a document is checked line by line
+#[derive(Default)]
+struct CheckTally {
+    words: Vec<WordCheck>,
+    total_words: usize,
+    misspelled_words: usize,
+    /// Whitespace issues, counted in `misspelled_words` but not accuracy
+    whitespace_issues: usize,
+    /// Doubled words, counted in `misspelled_words` but not accuracy: the
+    /// pair isn't a word of its own in `total_words`
+    repeated_words: usize,
+    /// Capitalization issues, counted in `misspelled_words` but not
+    /// accuracy: short words such as "i" aren't in `total_words`
+    capitalization_issues: usize,
+    suggestions_count: usize,
+    unique_words: HashSet<String>,
+    /// Whether the last line checked ended partway through a sentence
+    mid_sentence: bool,
+    suppressions: Suppressions,
+    /// Flagged words kept in `words`, for the error cap
+    errors_kept: usize,
+    truncated: bool,
+}
+
+/// Marks the inline directives authors use to switch checking off:
+/// `atomspell:disable` ... `atomspell:enable` around a region,
+/// `atomspell:ignore` for its own line, and `atomspell:ignore-word foo bar`
+/// to accept words throughout the document
+const DIRECTIVE_PREFIX: &str = "atomspell:";
+
+/// Regions and words switched off by directives
+#[derive(Default)]
+struct Suppressions {
+    /// Absolute byte ranges that aren't checked
+    spans: Vec<(usize, usize)>,
+    /// Start of an `atomspell:disable` region with no `enable` yet
+    disabled_from: Option<usize>,
+    words: HashSet<String>,
+    /// Set when built from the whole text up front, so lines aren't
+    /// scanned again as they are checked
+    prescanned: bool,
+}
+
+impl Suppressions {
+    /// Scan a whole document, so `ignore-word` applies to words before the
+    /// directive too. An unclosed `disable` runs to the end.
+    fn scan(text: &str) -> Self {
+        let mut suppressions = Self::default();
+        for (line, line_base) in text.lines().zip(line_start_offsets(text)) {
+            suppressions.scan_line(line, line_base);
+        }
+        if let Some(from) = suppressions.disabled_from.take() {
+            suppressions.spans.push((from, text.len()));
+        }
+        suppressions.prescanned = true;
+        suppressions
+    }
+    
+    fn scan_line(&mut self, line: &str, line_base: usize) {
+        let line_end = line_base + line.len();
+        for (pos, _) in line.match_indices(DIRECTIVE_PREFIX) {
+            let directive = &line[pos + DIRECTIVE_PREFIX.len()..];
+            if directive.starts_with("disable") {
+                self.disabled_from.get_or_insert(line_base + pos);
+            } else if directive.starts_with("enable") {
+                if let Some(from) = self.disabled_from.take() {
+                    self.spans.push((from, line_end));
+                }
+            } else if let Some(words) = directive.strip_prefix("ignore-word") {
+                // Stop at the end of an HTML or block comment
+                let words = words.split("-->").next().unwrap_or_default();
+                let words = words.split("*/").next().unwrap_or_default();
+                self.words.extend(words.split_whitespace().map(str::to_lowercase));
+                self.spans.push((line_base + pos, line_end));
+            } else if directive.starts_with("ignore") {
+                self.spans.push((line_base, line_end));
+            }
+        }
+    }
+    
+    fn is_suppressed(&self, start: usize, end: usize) -> bool {
+        self.disabled_from.is_some_and(|from| end > from)
+            || self.spans.iter().any(|&(span_start, span_end)| start < span_end && end > span_start)
+    }
 }
 
+/// Clones share the loaded dictionaries and the caches, so a clone can
+/// check in the background while the original takes edits
+#[derive(Clone)]
 pub struct SpellChecker {
     dictionary_manager: DictionaryManager,
     current_language: Language,
     suggestions_enabled: bool,
     case_sensitive: bool,
+    /// Set by `set_case_sensitive`; otherwise `case_sensitive` follows the
+    /// language's default
+    explicit_case_override: bool,
     max_suggestions: usize,
+    /// Flagged words kept per analysis; `None` keeps them all
+    max_errors: Option<usize>,
     cache: Arc<DashMap<String, bool>>,
+    suggestion_cache: Arc<DashMap<String, Vec<String>>>,
+    max_cache_entries: usize,
+    suggestion_provider: EditDistanceProvider,
+    /// Layout used by `suggestion_provider` while keyboard-aware
+    keyboard_layout: KeyboardLayout,
+    /// Replaces `suggestion_provider` when set
+    custom_suggestion_provider: Option<Arc<dyn SuggestionProvider>>,
+    phonetic_suggestions: bool,
+    /// Dictionary words by metaphone key, built on first phonetic lookup
+    phonetic_index: OnceCell<Arc<HashMap<String, Vec<String>>>>,
     ignore_list: HashSet<String>,
+    /// Words ignored only in the open document, until `reset_document_scope`
+    document_ignore_list: HashSet<String>,
     user_dictionary: HashSet<String>,
+    project_dictionary: HashSet<String>,
+    project_dictionary_path: Option<PathBuf>,
     proper_nouns: HashSet<String>,
     acronyms: HashSet<String>,
     confidence_threshold: f32,
+    confidence_weights: ConfidenceWeights,
+    comments_only: bool,
+    html_mode: bool,
+    cjk_allow_mixed: bool,
+    split_identifiers: bool,
+    advanced_typo_detection: bool,
+    alphanumeric_policy: AlphanumericPolicy,
+    capture_context: bool,
+    skip_constants: bool,
+    detect_mixed_script: bool,
+    check_whitespace: bool,
+    check_hyphen_parts: bool,
+    /// Languages a misspelled word is looked up in before it's reported
+    secondary_languages: Vec<Language>,
+    /// Custom word regexes by language, from `set_word_pattern`
+    word_patterns: HashMap<Language, Regex>,
+    ignore_rules: Vec<IgnoreRule>,
 }
 
 impl SpellChecker {
     pub fn new(language: Language) -> anyhow::Result<Self> {
-        let dictionary_manager = DictionaryManager::new();
+        Self::with_data_dir(language, LanguageManager::system_dict_dir())
+    }
+    
+    /// Like `new`, but with dictionaries and user data (added and ignored
+    /// words, acronyms, rules and settings) kept under `data_dir` instead
+    /// of `LanguageManager::system_dict_dir`
+    pub fn with_data_dir(language: Language, data_dir: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let dictionary_manager = DictionaryManager::with_data_dir(data_dir);
         
         // Try to load dictionary
         let dict_result = dictionary_manager.get_dictionary(&language);
@@ -75,42 +584,75 @@ impl SpellChecker {
             // Continue with empty dictionary
         }
         
+        Ok(Self::with_dictionary_manager(dictionary_manager, language))
+    }
+    
+    /// Create a checker without waiting for its dictionary, which loads on a
+    /// background thread. Until `dictionary_state` is `Loaded`, checks find
+    /// no words; then call `dictionary_reloaded` to pick up user data.
+    pub fn new_in_background(language: Language) -> Self {
+        let dictionary_manager = DictionaryManager::new();
+        dictionary_manager.load_in_background(language);
+        Self::with_dictionary_manager(dictionary_manager, language)
+    }
+    
+    fn with_dictionary_manager(dictionary_manager: DictionaryManager, language: Language) -> Self {
+        let ignore_rules = IgnoreRule::load_all(dictionary_manager.data_dir());
         let mut checker = Self {
             dictionary_manager,
             current_language: language,
             suggestions_enabled: true,
-            case_sensitive: false,
+            case_sensitive: language.default_case_sensitive(),
+            explicit_case_override: false,
             max_suggestions: 5,
+            max_errors: None,
             cache: Arc::new(DashMap::new()),
+            suggestion_cache: Arc::new(DashMap::new()),
+            max_cache_entries: 10_000,
+            suggestion_provider: EditDistanceProvider::new(),
+            keyboard_layout: KeyboardLayout::default(),
+            custom_suggestion_provider: None,
+            phonetic_suggestions: false,
+            phonetic_index: OnceCell::new(),
             ignore_list: HashSet::new(),
+            document_ignore_list: HashSet::new(),
             user_dictionary: HashSet::new(),
+            project_dictionary: HashSet::new(),
+            project_dictionary_path: None,
             proper_nouns: HashSet::new(),
             acronyms: HashSet::new(),
             confidence_threshold: 0.7,
+            confidence_weights: ConfidenceWeights::default(),
+            comments_only: false,
+            html_mode: true,
+            cjk_allow_mixed: true,
+            split_identifiers: false,
+            advanced_typo_detection: crate::Config::default().enable_advanced_typo_detection,
+            alphanumeric_policy: AlphanumericPolicy::default(),
+            capture_context: false,
+            skip_constants: true,
+            detect_mixed_script: false,
+            check_whitespace: false,
+            check_hyphen_parts: true,
+            secondary_languages: Vec::new(),
+            word_patterns: HashMap::new(),
+            ignore_rules,
         };
         
         // Load user data
         checker.load_user_data();
+        let settings = CheckerSettings::load(checker.data_dir());
+        checker.apply_settings(&settings);
         
-        Ok(checker)
+        checker
     }
     
     fn load_user_data(&mut self) {
-        // Load user dictionary
-        let user_dict_path = crate::language::LanguageManager::user_dict_dir()
-            .join(format!("user_{}.txt", self.current_language.code()));
-        
-        if let Ok(content) = fs::read_to_string(&user_dict_path) {
-            for line in content.lines() {
-                let word = line.trim().to_lowercase();
-                if !word.is_empty() {
-                    self.user_dictionary.insert(word);
-                }
-            }
-        }
+        // Load user dictionary and ignored words
+        self.refresh_user_data();
         
         // Load proper nouns
-        let proper_nouns_path = crate::language::LanguageManager::user_dict_dir()
+        let proper_nouns_path = LanguageManager::user_dict_dir(self.data_dir())
             .join(format!("proper_{}.txt", self.current_language.code()));
         
         if let Ok(content) = fs::read_to_string(&proper_nouns_path) {
@@ -122,166 +664,904 @@ impl SpellChecker {
             }
         }
         
-        // Load common acronyms
-        self.acronyms.extend(vec![
-            "api", "http", "https", "url", "uri", "html", "css", "js", "ts",
-            "json", "xml", "sql", "nosql", "cpu", "gpu", "ram", "rom", "usb",
-            "ssd", "hdd", "lan", "wan", "vpn", "dns", "ip", "tcp", "udp",
-            "ftp", "ssh", "ssl", "tls", "csv", "pdf", "doc", "jpg", "png",
-            "gif", "mp3", "mp4", "avi", "mkv", "zip", "rar", "tar", "gz",
-            "exe", "dll", "so", "dylib", "bin", "iso", "img", "vm", "aws",
-            "gcp", "azure", "api", "ui", "ux", "cli", "gui", "ide", "sdk",
-        ].into_iter().map(String::from));
+        self.load_acronyms();
+    }
+    
+    fn acronyms_path(&self) -> PathBuf {
+        LanguageManager::user_dict_dir(self.data_dir())
+            .join(format!("acronyms_{}.csv", self.current_language.code()))
+    }
+    
+    /// Load the current language's acronyms, one per line, seeding the file
+    /// with `DEFAULT_ACRONYMS` the first time
+    fn load_acronyms(&mut self) {
+        let path = self.acronyms_path();
+        self.acronyms.clear();
+        
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                for line in content.lines() {
+                    let acronym = line.split(',').next().unwrap_or("").trim().to_lowercase();
+                    if !acronym.is_empty() {
+                        self.acronyms.insert(acronym);
+                    }
+                }
+            }
+            Err(_) => {
+                self.acronyms.extend(DEFAULT_ACRONYMS.iter().map(|a| a.to_string()));
+                if let Err(e) = self.save_acronyms() {
+                    eprintln!("Warning: Could not write {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+    
+    fn save_acronyms(&self) -> anyhow::Result<()> {
+        let content: String = self.acronyms()
+            .into_iter()
+            .map(|acronym| format!("{}\n", acronym))
+            .collect();
+        fs::write(self.acronyms_path(), content)?;
+        Ok(())
+    }
+    
+    /// Known acronyms for the current language, uppercased and sorted
+    pub fn acronyms(&self) -> Vec<String> {
+        let mut acronyms: Vec<String> = self.acronyms.iter().map(|a| a.to_uppercase()).collect();
+        acronyms.sort();
+        acronyms
+    }
+    
+    /// Text matching these rules is skipped; see `IgnoreRule`
+    pub fn ignore_rules(&self) -> &[IgnoreRule] {
+        &self.ignore_rules
+    }
+    
+    /// Add `rule`, replacing any rule with the same name, and save the
+    /// rules to `rules.json`
+    pub fn add_ignore_rule(&mut self, rule: IgnoreRule) -> anyhow::Result<()> {
+        self.ignore_rules.retain(|existing| existing.name != rule.name);
+        self.ignore_rules.push(rule);
+        IgnoreRule::save_all(self.data_dir(), &self.ignore_rules)?;
+        Ok(())
+    }
+    
+    /// Returns false if there was no rule named `name`
+    pub fn remove_ignore_rule(&mut self, name: &str) -> anyhow::Result<bool> {
+        let count = self.ignore_rules.len();
+        self.ignore_rules.retain(|rule| rule.name != name);
+        if self.ignore_rules.len() == count {
+            return Ok(false);
+        }
+        IgnoreRule::save_all(self.data_dir(), &self.ignore_rules)?;
+        Ok(true)
+    }
+    
+    /// Accept `acronym` (matched case-insensitively) and save it to the
+    /// language's `acronyms_<code>.csv`. Returns false if it was known.
+    pub fn add_acronym(&mut self, acronym: &str) -> anyhow::Result<bool> {
+        let acronym = acronym.trim().to_lowercase();
+        if acronym.is_empty() || !self.acronyms.insert(acronym) {
+            return Ok(false);
+        }
+        self.save_acronyms()?;
+        Ok(true)
+    }
+    
+    /// Returns false if `acronym` wasn't known
+    pub fn remove_acronym(&mut self, acronym: &str) -> anyhow::Result<bool> {
+        if !self.acronyms.remove(&acronym.trim().to_lowercase()) {
+            return Ok(false);
+        }
+        self.save_acronyms()?;
+        Ok(true)
+    }
+    
+    /// Pull the current dictionary's user-added and ignored words into the
+    /// in-memory sets, invalidating cache entries only for words that changed.
+    pub fn refresh_user_data(&mut self) {
+        let (user_words, ignored_words) = match self.with_current_dictionary(|dictionary| {
+            (dictionary.get_user_words().clone(), dictionary.get_ignored_words().clone())
+        }) {
+            Ok(words) => words,
+            Err(_) => return,
+        };
+        
+        let changed: Vec<String> = self.user_dictionary
+            .symmetric_difference(&user_words)
+            .chain(self.ignore_list.symmetric_difference(&ignored_words))
+            .cloned()
+            .collect();
+        
+        // Any new or removed word can change suggestions for other words
+        if !changed.is_empty() {
+            self.suggestion_cache.clear();
+            self.phonetic_index = OnceCell::new();
+            
+            // Case-sensitive checks cache each spelling of a word apart
+            let stale: HashSet<String> = changed.iter().map(|word| self.cache_key(word).to_lowercase()).collect();
+            self.cache.retain(|key, _| !stale.contains(&key.to_lowercase()));
+        }
+        
+        self.user_dictionary = user_words;
+        self.ignore_list = ignored_words;
+    }
+    
+    /// Reload dictionary files when they change on disk. Reloaded languages
+    /// arrive on the returned channel; pass each to `dictionary_reloaded`
+    /// before re-checking.
+    pub fn watch_dictionaries(&self) -> anyhow::Result<std::sync::mpsc::Receiver<Language>> {
+        self.dictionary_manager.watch()
+    }
+    
+    /// See `DictionaryManager::language_availability`
+    pub fn language_availability(&self) -> Vec<(Language, bool)> {
+        self.dictionary_manager.language_availability()
+    }
+    
+    pub fn unwatch_dictionaries(&self) {
+        self.dictionary_manager.unwatch();
+    }
+    
+    /// Drop cached results that may be stale after `language`'s dictionary
+    /// was reloaded from disk
+    pub fn dictionary_reloaded(&mut self, language: Language) {
+        if language == self.current_language {
+            self.cache.clear();
+            self.suggestion_cache.clear();
+            self.phonetic_index = OnceCell::new();
+            self.refresh_user_data();
+        }
+    }
+    
+    fn cache_key(&self, word_lower: &str) -> String {
+        format!("{}_{}", self.current_language.code(), word_lower)
     }
     
     pub fn set_language(&mut self, language: Language) -> anyhow::Result<()> {
         if language != self.current_language {
             self.dictionary_manager.get_dictionary(&language)?;
-            self.current_language = language;
-            self.cache.clear();
-            self.load_user_data();
+            self.switch_language(language);
+        }
+        Ok(())
+    }
+    
+    /// Like `set_language`, but load the new dictionary on a background
+    /// thread; see `new_in_background`
+    pub fn set_language_in_background(&mut self, language: Language) {
+        if language != self.current_language {
+            self.dictionary_manager.load_in_background(language);
+            self.switch_language(language);
+        }
+    }
+    
+    fn switch_language(&mut self, language: Language) {
+        self.current_language = language;
+        if !self.explicit_case_override {
+            self.case_sensitive = language.default_case_sensitive();
         }
+        self.cache.clear();
+        self.phonetic_index = OnceCell::new();
+        self.load_user_data();
+    }
+    
+    /// Whether the current language's dictionary is loaded yet
+    pub fn dictionary_state(&self) -> LoadState {
+        self.dictionary_manager.loading_state(&self.current_language)
+    }
+    
+    /// Replace the current language's dictionary with one read from
+    /// `source`, e.g. bytes fetched by a WebAssembly host
+    pub fn load_dictionary_from_source(&mut self, source: &dyn DictionarySource) -> anyhow::Result<()> {
+        let dict = Dictionary::from_source(self.current_language, source)?;
+        self.use_dictionary(dict);
         Ok(())
     }
     
+    /// Use `dict` for its language, replacing the loaded one, e.g. a
+    /// dictionary built with `Dictionary::from_words`
+    pub fn use_dictionary(&mut self, dict: Dictionary) {
+        self.dictionary_manager.insert_dictionary(*dict.language(), dict);
+        self.cache.clear();
+        self.suggestion_cache.clear();
+        self.phonetic_index = OnceCell::new();
+        self.refresh_user_data();
+    }
+    
     pub fn current_language(&self) -> Language {
         self.current_language
     }
     
+    /// Where this checker's dictionaries and user data are kept
+    pub fn data_dir(&self) -> &Path {
+        self.dictionary_manager.data_dir()
+    }
+    
+    pub fn confidence_threshold(&self) -> f32 {
+        self.confidence_threshold
+    }
+    
     pub fn get_current_dictionary(&self) -> anyhow::Result<Dictionary> {
         self.dictionary_manager.get_dictionary(&self.current_language)
     }
     
-    pub fn check_document(&self, text: &str, filename: Option<&str>) -> DocumentAnalysis {
+    /// Run `f` on the current dictionary without copying it, for per-word
+    /// calls where `get_current_dictionary` would cost more than the check.
+    /// Other dictionaries a check may consult are loaded first, since
+    /// loading one while the current dictionary is borrowed would wait on
+    /// the borrow.
+    fn with_current_dictionary<R>(&self, f: impl FnOnce(&Dictionary) -> R) -> anyhow::Result<R> {
+        let mut others = self.secondary_languages.clone();
+        if self.cjk_allow_mixed && self.current_language.is_cjk() {
+            others.push(Language::English);
+        }
+        for language in others.iter().filter(|language| **language != self.current_language) {
+            // A missing dictionary just means no words are found in it
+            let _ = self.dictionary_manager.with_dictionary(language, |_| ());
+        }
+        
+        self.dictionary_manager.with_dictionary(&self.current_language, f)
+    }
+    
+    /// Check plain text with no file context. Use this when the text didn't
+    /// come from a file; otherwise prefer `check_document_with_context`.
+    pub fn check_document(&self, text: &str) -> DocumentAnalysis {
+        self.check_document_with_context(text, None)
+    }
+    
+    /// Check text that came from `filename`. The file extension decides
+    /// whether code-aware word extraction is used and is recorded in the
+    /// resulting `DocumentAnalysis::file_type`. Regions and words switched
+    /// off with `atomspell:` directives in the text are skipped.
+    pub fn check_document_with_context(&self, text: &str, filename: Option<&str>) -> DocumentAnalysis {
         let start_time = std::time::Instant::now();
         
-        let dictionary = match self.get_current_dictionary() {
-            Ok(dict) => dict,
-            Err(_) => {
-                return DocumentAnalysis {
-                    total_words: 0,
-                    misspelled_words: 0,
-                    accuracy: 100.0,
-                    words: Vec::new(),
-                    suggestions_count: 0,
-                    language: self.current_language,
-                    lines_checked: 0,
-                    check_duration_ms: 0,
-                    likely_code: false,
-                    file_type: filename.map(|f| f.to_string()),
-                    unique_words: 0,
-                };
+        // Borrowed rather than copied: the server checks a document per
+        // request, and a copy can cost more than the check
+        self.with_current_dictionary(|dictionary| {
+            let is_code = filename.map(is_code_file).unwrap_or(false) || is_likely_code(text);
+            let checked_spans = self.checked_spans(text, filename, is_code);
+            
+            let context = LineContext {
+                dictionary,
+                word_pattern: self.word_pattern(dictionary, is_code, checked_spans.is_some()),
+                checked_spans: checked_spans.as_deref(),
+                is_code,
+                is_markdown: filename.and_then(programming_language_from_filename) == Some("markdown"),
+                retain_correct: true,
+            };
+            
+            let lines: Vec<&str> = text.lines().collect();
+            let line_offsets = line_start_offsets(text);
+            let mut tally = CheckTally {
+                suppressions: Suppressions::scan(text),
+                ..Default::default()
+            };
+            
+            for (line_idx, line) in lines.iter().enumerate() {
+                self.check_line(&context, line, line_idx + 1, line_offsets[line_idx], &mut tally);
             }
-        };
-        
-        let is_cjk = matches!(self.current_language, Language::Chinese | Language::Japanese | Language::Korean);
-        let is_code = filename.map(|f| is_code_file(f)).unwrap_or(false) || is_likely_code(text);
-        
-        let lines: Vec<&str> = text.lines().collect();
-        let mut words = Vec::new();
-        let mut suggestions_count = 0;
-        let mut total_words = 0;
-        let mut misspelled_words = 0;
-        let mut unique_words = HashSet::new();
+            
+            self.finish_analysis(tally, lines.len(), start_time, is_code, filename)
+        })
+        .unwrap_or_else(|_| self.empty_analysis(filename))
+    }
+    
+    /// Check only the bytes `start..end` of `text`, widened so no word is cut
+    /// in half. Words keep the lines and columns they have in the whole of
+    /// `text`, so the result can be merged into a full analysis with
+    /// `DocumentAnalysis::merge_range`. `lines_checked` counts the lines the
+    /// range touches.
+    pub fn check_range(&self, text: &str, start: usize, end: usize, filename: Option<&str>) -> DocumentAnalysis {
+        let start_time = std::time::Instant::now();
+        let (start, end) = expand_to_word_boundaries(text, start, end);
         
-        for (line_idx, line) in lines.iter().enumerate() {
-            let line_num = line_idx + 1;
+        self.with_current_dictionary(|dictionary| {
+            // Code detection and comment spans look at the whole document so a
+            // range is treated the same as it would be in a full check
+            let is_code = filename.map(is_code_file).unwrap_or(false) || is_likely_code(text);
+            let checked_spans = self.checked_spans(text, filename, is_code);
             
-            let word_pattern = if is_cjk {
-                crate::util::CJK_WORD_REGEX.clone()
-            } else if is_code {
-                crate::util::CODE_WORD_REGEX.clone()
-            } else {
-                crate::util::WORD_REGEX.clone()
+            let context = LineContext {
+                dictionary,
+                word_pattern: self.word_pattern(dictionary, is_code, checked_spans.is_some()),
+                checked_spans: checked_spans.as_deref(),
+                is_code,
+                is_markdown: filename.and_then(programming_language_from_filename) == Some("markdown"),
+                retain_correct: true,
             };
             
-            for mat in word_pattern.find_iter(line) {
-                let original_word = mat.as_str();
-                let start = mat.start();
-                let end = mat.end();
-                
-                // Determine word type
-                let word_type = self.determine_word_type(original_word, is_code);
-                
-                // Skip based on word type
-                if self.should_skip_word(original_word, &word_type) {
-                    words.push(WordCheck {
-                        word: original_word.to_lowercase(),
-                        original: original_word.to_string(),
-                        start,
-                        end,
-                        is_correct: true,
-                        suggestions: Vec::new(),
-                        line: line_num,
-                        column: start + 1,
-                        confidence: 1.0,
-                        word_type,
-                    });
+            let line_offsets = line_start_offsets(text);
+            let mut tally = CheckTally {
+                mid_sentence: !ends_sentence(&text[..start]),
+                suppressions: Suppressions::scan(text),
+                ..Default::default()
+            };
+            let mut lines_checked = 0;
+            
+            for (line_idx, line) in text.lines().enumerate() {
+                let line_base = line_offsets[line_idx];
+                if line_base + line.len() <= start {
                     continue;
                 }
-                
-                let word_lower = original_word.to_lowercase();
-                unique_words.insert(word_lower.clone());
-                
-                // Check in various dictionaries and lists
-                let is_correct = self.check_word_correctness(&word_lower, original_word, &word_type, &dictionary, is_code);
-                let confidence = self.calculate_confidence(original_word, &word_type, is_correct, is_code);
-                
-                total_words += 1;
-                if !is_correct && confidence >= self.confidence_threshold {
-                    misspelled_words += 1;
+                if line_base >= end {
+                    break;
                 }
                 
-                let suggestions = if !is_correct && self.suggestions_enabled && confidence >= self.confidence_threshold {
-                    let sugg = self.get_suggestions(&word_lower, &dictionary);
-                    suggestions_count += sugg.len();
-                    sugg
-                } else {
-                    Vec::new()
-                };
+                let from = start.saturating_sub(line_base);
+                let to = (end - line_base).min(line.len());
+                let first_word = tally.words.len();
+                self.check_line(&context, &line[from..to], line_idx + 1, line_base + from, &mut tally);
                 
-                words.push(WordCheck {
-                    word: word_lower.clone(),
-                    original: original_word.to_string(),
-                    start,
-                    end,
-                    is_correct: is_correct || confidence < self.confidence_threshold,
-                    suggestions,
-                    line: line_num,
-                    column: start + 1,
-                    confidence,
-                    word_type,
-                });
+                // check_line saw only part of the line, so its columns start at `from`
+                let skipped_chars = line[..from].chars().count();
+                for word in &mut tally.words[first_word..] {
+                    word.column += skipped_chars;
+                }
+                lines_checked += 1;
             }
+            
+            self.finish_analysis(tally, lines_checked, start_time, is_code, filename)
+        })
+        .unwrap_or_else(|_| self.empty_analysis(filename))
+    }
+    
+    /// Check one section of a Markdown document: the first heading titled
+    /// `heading_title` (case-insensitively) and everything up to the next
+    /// heading of the same or a higher level. Lines and offsets are those
+    /// of the whole `text`. `None` if there's no such heading.
+    pub fn check_markdown_section(&self, text: &str, heading_title: &str) -> Option<DocumentAnalysis> {
+        let (start, end) = markdown_section(text, heading_title)?;
+        Some(self.check_range(text, start, end, None))
+    }
+    
+    /// In HTML mode HTML and XML files are checked only in their text
+    /// content; in comments-only mode a recognised source file is checked
+    /// only inside its comments and string literals
+    fn checked_spans(&self, text: &str, filename: Option<&str>, is_code: bool) -> Option<Vec<(usize, usize)>> {
+        let language = filename.and_then(programming_language_from_filename);
+        if self.html_mode && is_markup(language) {
+            return Some(html_text_spans(text));
         }
-        
-        let accuracy = if total_words > 0 {
-            ((total_words - misspelled_words) as f32 / total_words as f32 * 100.0).round()
-        } else {
-            100.0
-        };
-        
-        let check_duration = start_time.elapsed();
-        
-        DocumentAnalysis {
-            total_words,
-            misspelled_words,
-            accuracy,
-            words,
-            suggestions_count,
-            language: self.current_language,
-            lines_checked: lines.len(),
-            check_duration_ms: check_duration.as_millis(),
-            likely_code: is_code,
-            file_type: filename.map(|f| f.to_string()),
-            unique_words: unique_words.len(),
+        if !(self.comments_only && is_code) {
+            return None;
         }
+        
+        language
+            .and_then(comment_syntax_for)
+            .map(|syntax| comment_and_string_spans(text, &syntax))
     }
     
-    fn determine_word_type(&self, word: &str, is_code: bool) -> WordType {
-        // Check for numbers
-        if word.chars().all(|c| c.is_numeric()) {
-            return WordType::Number;
+    /// Check a document line by line without holding all of it in memory.
+    /// With `retain_correct` false only misspelled words are kept in
+    /// `DocumentAnalysis::words`, which bounds memory for very large files;
+    /// the totals and accuracy are the same either way. Reading stops at the
+    /// first I/O or UTF-8 error.
+    ///
+    /// Comments-only mode needs to see whole block comments and HTML mode
+    /// whole tags, so for files those modes apply to the reader is read
+    /// fully and checked in one go. Otherwise `atomspell:ignore-word` only
+    /// applies to the lines after it.
+    pub fn check_reader<R: BufRead>(&self, mut reader: R, filename: Option<&str>, retain_correct: bool) -> DocumentAnalysis {
+        let language = filename.and_then(programming_language_from_filename);
+        let needs_whole_text = (self.comments_only && language.and_then(comment_syntax_for).is_some())
+            || (self.html_mode && is_markup(language));
+        
+        if needs_whole_text {
+            let mut text = String::new();
+            if reader.read_to_string(&mut text).is_err() {
+                return self.empty_analysis(filename);
+            }
+            let mut analysis = self.check_document_with_context(&text, filename);
+            if !retain_correct {
+                analysis.words.retain(|w| !w.is_correct);
+            }
+            return analysis;
+        }
+        
+        let start_time = std::time::Instant::now();
+        
+        let dictionary = match self.get_current_dictionary() {
+            Ok(dict) => dict,
+            Err(_) => return self.empty_analysis(filename),
+        };
+        
+        // Content-based code detection only looks at the first few lines,
+        // so buffer those before deciding how to extract words
+        let mut head = Vec::new();
+        let mut buffer = String::new();
+        while head.len() < 10 && matches!(reader.read_line(&mut buffer), Ok(n) if n > 0) {
+            head.push(std::mem::take(&mut buffer));
+        }
+        
+        let is_code = filename.map(is_code_file).unwrap_or(false) || is_likely_code(&head.concat());
+        
+        let context = LineContext {
+            dictionary: &dictionary,
+            word_pattern: self.word_pattern(&dictionary, is_code, false),
+            checked_spans: None,
+            is_code,
+            is_markdown: filename.and_then(programming_language_from_filename) == Some("markdown"),
+            retain_correct,
+        };
+        
+        let mut tally = CheckTally::default();
+        let mut lines_checked = 0;
+        let mut line_base = 0;
+        
+        let mut check = |raw_line: &str| {
+            let line = raw_line.trim_end_matches('\n').trim_end_matches('\r');
+            lines_checked += 1;
+            self.check_line(&context, line, lines_checked, line_base, &mut tally);
+            line_base += raw_line.len();
+        };
+        
+        for raw_line in &head {
+            check(raw_line);
+        }
+        
+        if head.len() == 10 {
+            while matches!(reader.read_line(&mut buffer), Ok(n) if n > 0) {
+                check(&buffer);
+                buffer.clear();
+            }
+        }
+        
+        self.finish_analysis(tally, lines_checked, start_time, is_code, filename)
+    }
+    
+    /// The regex that splits lines into words. A custom pattern for the
+    /// language replaces the built-in one for prose and for comments and
+    /// strings in code, but not for whole source files.
+    fn word_pattern<'a>(&'a self, dictionary: &'a Dictionary, is_code: bool, has_checked_spans: bool) -> &'a Regex {
+        let is_cjk = matches!(self.current_language, Language::Chinese | Language::Japanese | Language::Korean);
+        let custom = self.word_patterns.get(&self.current_language).or_else(|| dictionary.custom_word_pattern());
+        
+        if let Some(pattern) = custom.filter(|_| !is_code || has_checked_spans) {
+            pattern
+        } else if is_cjk && self.cjk_allow_mixed {
+            &crate::util::CJK_MIXED_WORD_REGEX
+        } else if is_cjk {
+            &crate::util::CJK_WORD_REGEX
+        } else if self.current_language.is_rtl() && (!is_code || has_checked_spans) {
+            &crate::util::RTL_WORD_REGEX
+        } else if is_code && !has_checked_spans && self.split_identifiers {
+            &crate::util::IDENTIFIER_REGEX
+        } else if is_code && !has_checked_spans {
+            &crate::util::CODE_WORD_REGEX
+        } else {
+            &crate::util::WORD_REGEX
+        }
+    }
+    
+    fn check_line(&self, context: &LineContext, line: &str, line_num: usize, line_base: usize, tally: &mut CheckTally) {
+        let dictionary = context.dictionary;
+        let is_code = context.is_code;
+        let first_word = tally.words.len();
+        if !tally.suppressions.prescanned {
+            tally.suppressions.scan_line(line, line_base);
+        }
+        
+        // URLs and email addresses are recorded whole and masked so their
+        // pieces aren't checked as words
+        let mut masked = Vec::new();
+        for caps in crate::util::LINK_REGEX.captures_iter(line) {
+            let (mat, word_type) = match (caps.name("url"), caps.name("email")) {
+                (Some(url), _) => (url, WordType::Url),
+                (None, Some(email)) => (email, WordType::Email),
+                (None, None) => continue,
+            };
+            
+            if let Some(spans) = context.checked_spans {
+                if !span_contains(spans, line_base + mat.start(), line_base + mat.end()) {
+                    continue;
+                }
+            }
+            if tally.suppressions.is_suppressed(line_base + mat.start(), line_base + mat.end()) {
+                continue;
+            }
+            
+            masked.push((mat.start(), mat.end()));
+            if context.retain_correct {
+                tally.words.push(WordCheck {
+                    word: mat.as_str().to_lowercase(),
+                    original: mat.as_str().to_string(),
+                    start: mat.start(),
+                    end: mat.end(),
+                    is_correct: true,
+                    raw_misspelled: false,
+                    suggestions: Vec::new(),
+                    line: line_num,
+                    column: byte_to_char_column(line, mat.start()),
+                    confidence: 1.0,
+                    word_type,
+                    subword: None,
+                    kind: IssueKind::Spelling,
+                    context: None,
+                    ignore_rule: None,
+                    valid_in: None,
+                    severity: Severity::Hint,
+                });
+            }
+        }
+        
+        // So is text matching a user's ignore rule, when it's a whole token
+        for rule in &self.ignore_rules {
+            for mat in rule.pattern.find_iter(line) {
+                let (start, end) = (mat.start(), mat.end());
+                if start == end || !is_whole_token(line, start, end) {
+                    continue;
+                }
+                if masked.iter().any(|&(masked_start, masked_end)| start < masked_end && end > masked_start) {
+                    continue;
+                }
+                if let Some(spans) = context.checked_spans {
+                    if !span_contains(spans, line_base + start, line_base + end) {
+                        continue;
+                    }
+                }
+                
+                masked.push((start, end));
+                if context.retain_correct {
+                    tally.words.push(WordCheck {
+                        start,
+                        end,
+                        line: line_num,
+                        column: byte_to_char_column(line, start),
+                        ignore_rule: Some(rule.name.clone()),
+                        valid_in: None,
+                        ..skipped_word(mat.as_str(), self.determine_word_type(mat.as_str(), is_code))
+                    });
+                }
+            }
+        }
+        
+        // Mixed-script words are masked too, since the word regexes would
+        // split them at the lookalike letter
+        if self.detect_mixed_script {
+            for mat in crate::util::SCRIPT_TOKEN_REGEX.find_iter(line) {
+                let Some(script) = crate::util::mixed_script_dominant(mat.as_str()) else {
+                    continue;
+                };
+                if let Some(spans) = context.checked_spans {
+                    if !span_contains(spans, line_base + mat.start(), line_base + mat.end()) {
+                        continue;
+                    }
+                }
+                if tally.suppressions.is_suppressed(line_base + mat.start(), line_base + mat.end()) {
+                    continue;
+                }
+                
+                masked.push((mat.start(), mat.end()));
+                let original = mat.as_str();
+                let normalized = crate::util::normalize_confusables(original, script);
+                let suggestions = if crate::util::mixed_script_dominant(&normalized).is_none() {
+                    vec![normalized]
+                } else {
+                    Vec::new()
+                };
+                
+                tally.total_words += 1;
+                tally.misspelled_words += 1;
+                tally.suggestions_count += suggestions.len();
+                tally.words.push(WordCheck {
+                    word: self.current_language.lowercase(original),
+                    original: original.to_string(),
+                    start: mat.start(),
+                    end: mat.end(),
+                    is_correct: false,
+                    raw_misspelled: false,
+                    suggestions,
+                    line: line_num,
+                    column: byte_to_char_column(line, mat.start()),
+                    confidence: 0.95,
+                    word_type: self.determine_word_type(original, is_code),
+                    subword: None,
+                    kind: IssueKind::MixedScript,
+                    context: None,
+                    ignore_rule: None,
+                    valid_in: None,
+                    severity: Severity::Warning,
+                });
+            }
+        }
+        
+        let found_whitespace = self.check_whitespace && self.check_line_whitespace(context, line, line_num, line_base, tally);
+        
+        // Doubled words are only looked for in prose; in code "value = value"
+        // is normal
+        let detect_repeats = self.advanced_typo_detection && (!is_code || context.checked_spans.is_some());
+        let detect_capitalization = self.advanced_typo_detection && !is_code;
+        // A prose line in capitals is shouting rather than a run of
+        // acronyms, so unknown words in it are checked as plain words
+        let shouting = !is_code && is_shouted(line);
+        let mut previous_word: Option<(usize, usize, String)> = None;
+        let mut found_repeat = false;
+        
+        for mat in context.word_pattern.find_iter(line) {
+            let original_word = mat.as_str();
+            let start = mat.start();
+            let end = mat.end();
+            
+            if let Some(spans) = context.checked_spans {
+                if !span_contains(spans, line_base + start, line_base + end) {
+                    continue;
+                }
+            }
+            if tally.suppressions.is_suppressed(line_base + start, line_base + end) {
+                continue;
+            }
+            
+            if masked.iter().any(|&(masked_start, masked_end)| start < masked_end && end > masked_start) {
+                continue;
+            }
+            
+            // The whole "the the" span is flagged with the first word as its
+            // suggestion, so applying it drops the second occurrence
+            if detect_repeats {
+                let word_lower = self.current_language.lowercase(original_word);
+                if let Some(&(previous_start, previous_end, _)) = previous_word.as_ref().filter(|(_, _, previous)| *previous == word_lower) {
+                    let pair = &line[previous_start..end];
+                    tally.misspelled_words += 1;
+                    tally.repeated_words += 1;
+                    tally.words.push(WordCheck {
+                        word: self.current_language.lowercase(pair),
+                        original: pair.to_string(),
+                        start: previous_start,
+                        end,
+                        is_correct: false,
+                        raw_misspelled: true,
+                        suggestions: vec![line[previous_start..previous_end].to_string()],
+                        line: line_num,
+                        column: byte_to_char_column(line, previous_start),
+                        confidence: 0.9,
+                        word_type: WordType::RepeatedWord,
+                        subword: None,
+                        kind: IssueKind::Spelling,
+                        context: None,
+                        ignore_rule: None,
+                        valid_in: None,
+                        severity: Severity::Warning,
+                    });
+                    found_repeat = true;
+                }
+                previous_word = Some((start, end, word_lower));
+            }
+            
+            // Determine word type
+            let word_type = match self.determine_word_type(original_word, is_code) {
+                WordType::Acronym | WordType::ProperNoun if shouting && !self.acronyms.contains(&original_word.to_lowercase()) => WordType::Normal,
+                word_type => word_type,
+            };
+            
+            // Short words are skipped below, so capitalization is checked
+            // first; a misspelled word at a sentence start is left to the
+            // spelling check
+            if detect_capitalization && matches!(word_type, WordType::Normal | WordType::ShortWord) && !is_abbreviation_part(line, start, end) {
+                let sentence_start = starts_sentence(&line[..start], !tally.mid_sentence);
+                if let Some(fixed) = self.capitalization_fix(original_word, sentence_start) {
+                    let word_lower = self.current_language.lowercase(original_word);
+                    if word_type == WordType::ShortWord
+                        || self.is_lowercase_pronoun(original_word)
+                        || self.check_word_correctness(&word_lower, original_word, &word_type, dictionary, is_code) {
+                        if word_type == WordType::Normal {
+                            tally.total_words += 1;
+                            tally.unique_words.insert(word_lower.clone());
+                        }
+                        tally.misspelled_words += 1;
+                        tally.capitalization_issues += 1;
+                        tally.words.push(WordCheck {
+                            word: word_lower,
+                            original: original_word.to_string(),
+                            start,
+                            end,
+                            is_correct: false,
+                            raw_misspelled: false,
+                            suggestions: vec![fixed],
+                            line: line_num,
+                            column: byte_to_char_column(line, start),
+                            confidence: 0.8,
+                            word_type,
+                            subword: None,
+                            kind: IssueKind::Capitalization,
+                            context: None,
+                            ignore_rule: None,
+                            valid_in: None,
+                            severity: Severity::Hint,
+                        });
+                        continue;
+                    }
+                }
+            }
+            
+            // Skip based on word type
+            if self.should_skip_word(original_word, &word_type, is_code) {
+                if context.retain_correct {
+                    tally.words.push(WordCheck {
+                        start,
+                        end,
+                        line: line_num,
+                        column: byte_to_char_column(line, start),
+                        ..skipped_word(original_word, word_type)
+                    });
+                }
+                continue;
+            }
+            
+            let suppressed = tally.suppressions.words.contains(&original_word.to_lowercase());
+            let check = self.check_token(original_word, word_type, dictionary, is_code, suppressed);
+            
+            tally.unique_words.insert(check.word.clone());
+            tally.total_words += 1;
+            if check.raw_misspelled && check.confidence >= self.confidence_threshold {
+                tally.misspelled_words += 1;
+            }
+            tally.suggestions_count += check.suggestions.len();
+            
+            if check.is_correct && !context.retain_correct {
+                continue;
+            }
+            
+            tally.words.push(WordCheck {
+                start,
+                end,
+                line: line_num,
+                column: byte_to_char_column(line, start),
+                ..check
+            });
+        }
+        
+        if !masked.is_empty() || found_repeat || found_whitespace {
+            tally.words[first_word..].sort_by_key(|w| w.start);
+        }
+        // Past the cap flagged words are still counted, just not kept
+        if let Some(max_errors) = self.max_errors {
+            let mut line_words = tally.words.split_off(first_word);
+            line_words.retain(|w| {
+                if w.is_correct {
+                    true
+                } else if tally.errors_kept < max_errors {
+                    tally.errors_kept += 1;
+                    true
+                } else {
+                    tally.truncated = true;
+                    false
+                }
+            });
+            tally.words.append(&mut line_words);
+        }
+        if self.capture_context {
+            for word in tally.words[first_word..].iter_mut().filter(|w| !w.is_correct) {
+                word.context = Some(word_context(line, word.start, word.end));
+            }
+        }
+        // Offsets so far are within the line; callers get them within the text
+        for word in &mut tally.words[first_word..] {
+            word.start += line_base;
+            word.end += line_base;
+        }
+        tally.mid_sentence = !ends_sentence(line);
+    }
+    
+    /// Flag runs of spaces between words and whitespace at the end of
+    /// `line`, with offsets within the line. Returns whether any were found.
+    fn check_line_whitespace(&self, context: &LineContext, line: &str, line_num: usize, line_base: usize, tally: &mut CheckTally) -> bool {
+        let content_end = line.trim_end().len();
+        let indent = line.len() - line.trim_start().len();
+        let mut issues: Vec<(usize, usize, &str)> = Vec::new();
+        
+        // Lining things up with spaces is deliberate in code, so there only
+        // the comments and strings of comments-only mode are looked at
+        if !context.is_code || context.checked_spans.is_some() {
+            for mat in crate::util::MULTIPLE_SPACES_REGEX.find_iter(&line[..content_end]) {
+                let in_checked_span = context.checked_spans
+                    .is_none_or(|spans| span_contains(spans, line_base + mat.start(), line_base + mat.end()));
+                if mat.start() >= indent && in_checked_span {
+                    issues.push((mat.start(), mat.end(), " "));
+                }
+            }
+        }
+        
+        let trailing = &line[content_end..];
+        let markdown_break = context.is_markdown && trailing.len() >= 2 && trailing.bytes().all(|b| b == b' ');
+        // Blank lines keep their indentation in code
+        let indented_blank_line = context.is_code && content_end == 0;
+        if !(trailing.is_empty() || markdown_break || indented_blank_line) {
+            issues.push((content_end, line.len(), ""));
+        }
+        
+        issues.retain(|&(start, end, _)| !tally.suppressions.is_suppressed(line_base + start, line_base + end));
+        for &(start, end, replacement) in &issues {
+            tally.misspelled_words += 1;
+            tally.whitespace_issues += 1;
+            tally.suggestions_count += 1;
+            tally.words.push(WordCheck {
+                word: line[start..end].to_string(),
+                original: line[start..end].to_string(),
+                start,
+                end,
+                is_correct: false,
+                raw_misspelled: false,
+                suggestions: vec![replacement.to_string()],
+                line: line_num,
+                column: byte_to_char_column(line, start),
+                confidence: 1.0,
+                word_type: WordType::Symbol,
+                subword: None,
+                kind: IssueKind::Whitespace,
+                context: None,
+                ignore_rule: None,
+                valid_in: None,
+                severity: Severity::Hint,
+            });
+        }
+        !issues.is_empty()
+    }
+    
+    /// English "i", "i'm", "i'll" and so on
+    fn is_lowercase_pronoun(&self, word: &str) -> bool {
+        self.current_language == Language::English && (word == "i" || word.starts_with("i'"))
+    }
+    
+    /// The capitalized form of `word` if it should be capitalized: always
+    /// for English "i" and its contractions, otherwise only at the start of
+    /// a sentence. Words with capitals elsewhere ("iPhone") are left alone.
+    fn capitalization_fix(&self, word: &str, sentence_start: bool) -> Option<String> {
+        if !self.is_lowercase_pronoun(word) && !sentence_start {
+            return None;
+        }
+        
+        let mut chars = word.chars();
+        let first = chars.next()?;
+        if !first.is_lowercase() || word.chars().any(char::is_uppercase) {
+            return None;
+        }
+        Some(first.to_uppercase().chain(chars).collect())
+    }
+    
+    fn finish_analysis(&self, tally: CheckTally, lines_checked: usize, start_time: std::time::Instant, is_code: bool, filename: Option<&str>) -> DocumentAnalysis {
+        let word_errors = tally.misspelled_words - tally.whitespace_issues - tally.repeated_words - tally.capitalization_issues;
+        let accuracy = word_accuracy(tally.total_words, word_errors);
+        
+        let check_duration = start_time.elapsed();
+        
+        DocumentAnalysis {
+            total_words: tally.total_words,
+            misspelled_words: tally.misspelled_words,
+            accuracy,
+            words: tally.words,
+            suggestions_count: tally.suggestions_count,
+            language: self.current_language,
+            lines_checked,
+            check_duration_ms: check_duration.as_millis(),
+            likely_code: is_code,
+            file_type: filename.map(|f| f.to_string()),
+            unique_words: tally.unique_words.len(),
+            truncated: tally.truncated,
+        }
+    }
+    
+    fn empty_analysis(&self, filename: Option<&str>) -> DocumentAnalysis {
+        DocumentAnalysis {
+            total_words: 0,
+            misspelled_words: 0,
+            accuracy: 100.0,
+            words: Vec::new(),
+            suggestions_count: 0,
+            language: self.current_language,
+            lines_checked: 0,
+            check_duration_ms: 0,
+            likely_code: false,
+            file_type: filename.map(|f| f.to_string()),
+            unique_words: 0,
+            truncated: false,
+        }
+    }
+    
+    fn determine_word_type(&self, word: &str, is_code: bool) -> WordType {
+        // Check for numbers
+        if word.chars().all(|c| c.is_numeric()) {
+            return WordType::Number;
         }
         
         // Check for symbols
@@ -324,28 +1604,177 @@ impl SpellChecker {
         WordType::Normal
     }
     
-    fn should_skip_word(&self, word: &str, word_type: &WordType) -> bool {
+    fn should_skip_word(&self, word: &str, word_type: &WordType, is_code: bool) -> bool {
+        self.skip_reason(word, word_type, is_code).is_some()
+    }
+    
+    fn skip_reason(&self, word: &str, word_type: &WordType, is_code: bool) -> Option<&'static str> {
         match word_type {
-            WordType::Number | WordType::Symbol | WordType::ShortWord => true,
-            WordType::Acronym => {
-                self.acronyms.contains(&word.to_lowercase())
-            }
-            WordType::CodeIdentifier => {
-                word.len() <= 3 || // Very short identifiers
-                word.chars().all(|c| c.is_numeric()) || // Numbers
-                word.starts_with("0x") || // Hex numbers
-                word.contains("__") // Python dunders
+            WordType::Number => Some("number"),
+            WordType::Symbol => Some("no letters"),
+            WordType::ShortWord => Some("short word"),
+            _ if self.skips_alphanumeric(word) => Some("mixes letters and digits"),
+            _ if self.skip_constants && (is_code || word.contains('_')) && is_constant_name(word) => Some("constant"),
+            WordType::Acronym if self.acronyms.contains(&word.to_lowercase()) => Some("known acronym"),
+            WordType::CodeIdentifier if word.len() <= 3 => Some("short code identifier"),
+            WordType::CodeIdentifier if word.chars().all(|c| c.is_numeric()) => Some("numeric identifier"),
+            WordType::CodeIdentifier if word.starts_with("0x") => Some("hex number"),
+            WordType::CodeIdentifier if word.contains("__") => Some("Python dunder"),
+            WordType::ProperNoun if self.proper_nouns.contains(&word.to_lowercase()) => Some("known proper noun"),
+            _ => None,
+        }
+    }
+    
+    /// Check a single word the way `check_document` checks each token:
+    /// classification, skip rules, dictionary lookup, confidence and
+    /// suggestions. Checks that need surrounding text (capitalization,
+    /// repeated words) don't apply. The result spans the word on line 1.
+    pub fn check_word(&self, word: &str, is_code: bool) -> WordCheck {
+        let word = word.trim();
+        let word_type = self.determine_word_type(word, is_code);
+        if self.should_skip_word(word, &word_type, is_code) {
+            return skipped_word(word, word_type);
+        }
+        
+        self.with_current_dictionary(|dictionary| self.check_token(word, word_type.clone(), dictionary, is_code, false))
+            .unwrap_or_else(|_| self.check_token(word, word_type, &Dictionary::new(self.current_language), is_code, false))
+    }
+    
+    /// Check a token that wasn't skipped, positioned as `skipped_word` is.
+    /// `suppressed` words were allowed by an inline directive.
+    fn check_token(&self, original_word: &str, word_type: WordType, dictionary: &Dictionary, is_code: bool, suppressed: bool) -> WordCheck {
+        let word_lower = self.current_language.lowercase(original_word);
+        
+        // Check in various dictionaries and lists, part by part for
+        // identifiers when splitting is enabled
+        let misspelled_subword = if word_type == WordType::CodeIdentifier && self.split_identifiers {
+            self.find_misspelled_subword(original_word, dictionary)
+        } else {
+            None
+        };
+        
+        let (is_correct, confidence) = match &misspelled_subword {
+            _ if suppressed => (true, 1.0),
+            Some((_, sub)) => (false, self.calculate_confidence(sub, &WordType::Normal, false, is_code)),
+            None if word_type == WordType::CodeIdentifier && self.split_identifiers => (true, 1.0),
+            None => {
+                let is_correct = self.check_word_correctness(&word_lower, original_word, &word_type, dictionary, is_code);
+                // A known word in the wrong case is almost certainly an error
+                let confidence = if !is_correct && self.case_sensitive && dictionary.canonical_case(original_word).is_some() {
+                    0.9
+                } else {
+                    self.calculate_confidence(original_word, &word_type, is_correct, is_code)
+                };
+                (is_correct, confidence)
             }
-            WordType::ProperNoun => {
-                self.proper_nouns.contains(&word.to_lowercase())
+        };
+        
+        let suggestions = if !is_correct && self.suggestions_enabled && confidence >= self.confidence_threshold {
+            match &misspelled_subword {
+                Some((offset, sub)) => self.get_suggestions(&sub.to_lowercase(), dictionary)
+                    .into_iter()
+                    .map(|s| replace_subword(original_word, *offset, sub, &s))
+                    .collect(),
+                None => self.get_suggestions(&word_lower, dictionary),
             }
-            _ => false,
+        } else {
+            Vec::new()
+        };
+        
+        let is_flagged = !is_correct && confidence >= self.confidence_threshold;
+        let valid_in = if is_flagged && misspelled_subword.is_none() {
+            self.secondary_language_for(original_word)
+        } else {
+            None
+        };
+        let severity = match valid_in {
+            Some(_) => Severity::Hint,
+            None if is_flagged => Severity::for_spelling(&word_type, confidence),
+            None => Severity::Hint,
+        };
+        
+        WordCheck {
+            word: word_lower,
+            is_correct: !is_flagged,
+            raw_misspelled: !is_correct,
+            suggestions,
+            confidence,
+            subword: misspelled_subword.map(|(_, sub)| sub),
+            severity,
+            valid_in,
+            ..skipped_word(original_word, word_type)
+        }
+    }
+    
+    /// Walk `word` through the same decisions `check_document` makes and
+    /// report each step. `is_code` selects the rules used inside source
+    /// files. Identifier splitting is not applied.
+    pub fn explain(&self, word: &str, is_code: bool) -> WordExplanation {
+        let word_type = self.determine_word_type(word, is_code);
+        let skip_reason = self.skip_reason(word, &word_type, is_code);
+        let word_lower = self.current_language.lowercase(word);
+        let (in_dictionary, is_correct) = self
+            .with_current_dictionary(|d| {
+                let in_dictionary = d.contains(word, self.case_sensitive, is_code);
+                let is_correct = skip_reason.is_some() || self.check_word_correctness(&word_lower, word, &word_type, d, is_code);
+                (in_dictionary, is_correct)
+            })
+            .unwrap_or((false, skip_reason.is_some()));
+        let confidence = self.calculate_confidence(word, &word_type, is_correct, is_code);
+        
+        WordExplanation {
+            word: word.to_string(),
+            word_type,
+            skip_reason,
+            in_dictionary,
+            in_user_dictionary: self.user_dictionary.contains(&word_lower),
+            in_project_dictionary: self.project_dictionary.contains(&word_lower.to_lowercase()),
+            ignored: self.ignore_list.contains(&word_lower) || self.document_ignore_list.contains(&word_lower),
+            is_correct,
+            confidence,
+            flagged: !is_correct && confidence >= self.confidence_threshold,
+        }
+    }
+    
+    fn skips_alphanumeric(&self, word: &str) -> bool {
+        let digits = word.chars().filter(|c| c.is_numeric()).count();
+        let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+        if digits == 0 || letters == 0 {
+            return false;
+        }
+        
+        match self.alphanumeric_policy {
+            AlphanumericPolicy::AlwaysSkip => true,
+            AlphanumericPolicy::SkipIfMostlyDigits => letters <= digits,
+            AlphanumericPolicy::CheckLettersOnly => false,
+        }
+    }
+    
+    /// Whether the hyphen-separated parts of `word` are each correct on
+    /// their own. Numeric parts ("mid-2020") and short words ("state-of-the-art")
+    /// count as correct; an empty part ("a--b") doesn't. Leading and trailing
+    /// hyphens are ignored.
+    fn hyphen_parts_correct(&self, word: &str, dictionary: &Dictionary, is_code: bool) -> bool {
+        let word = crate::util::normalize_punctuation(word);
+        if !word.contains('-') {
+            return false;
         }
+        
+        word.trim_matches('-').split('-').all(|part| {
+            if part.is_empty() {
+                return false;
+            }
+            if part.chars().all(char::is_numeric) || part.chars().count() <= 2 {
+                return true;
+            }
+            let part_lower = self.current_language.lowercase(part);
+            self.check_word_correctness(&part_lower, part, &WordType::Normal, dictionary, is_code)
+        })
     }
     
     fn check_word_correctness(&self, word_lower: &str, original_word: &str, word_type: &WordType, dictionary: &Dictionary, is_code: bool) -> bool {
-        // Check ignore list
-        if self.ignore_list.contains(word_lower) {
+        // Check ignore lists
+        if self.ignore_list.contains(word_lower) || self.document_ignore_list.contains(word_lower) {
             return true;
         }
         
@@ -354,14 +1783,40 @@ impl SpellChecker {
             return true;
         }
         
-        // Check cache
-        let cache_key = format!("{}_{}", self.current_language.code(), word_lower);
+        // Check project dictionary, which applies whatever the language
+        if self.project_dictionary.contains(&word_lower.to_lowercase()) {
+            return true;
+        }
+        
+        if self.cjk_allow_mixed && dictionary.language().is_cjk() && !is_cjk_text(original_word) {
+            return original_word
+                .split(|c: char| !c.is_alphabetic())
+                .filter(|run| run.chars().count() >= 2)
+                .all(|run| {
+                    self.user_dictionary.contains(&run.to_lowercase())
+                        || self.dictionary_manager.dictionary_contains(&Language::English, run, self.case_sensitive)
+                });
+        }
+        
+        if self.alphanumeric_policy == AlphanumericPolicy::CheckLettersOnly && original_word.chars().any(char::is_numeric) {
+            return original_word
+                .split(|c: char| !c.is_alphabetic())
+                .filter(|run| run.chars().count() >= dictionary.min_word_length())
+                .all(|run| {
+                    let run_lower = self.current_language.lowercase(run);
+                    self.check_word_correctness(&run_lower, run, &WordType::Normal, dictionary, is_code)
+                });
+        }
+        
+        // Check cache; "iphone" and "iPhone" can differ when case matters
+        let cache_key = self.cache_key(if self.case_sensitive { original_word } else { word_lower });
         if let Some(cached) = self.cache.get(&cache_key) {
             return *cached;
         }
         
         // Check main dictionary
-        let in_dictionary = dictionary.contains(original_word, self.case_sensitive, is_code);
+        let in_dictionary = dictionary.contains(original_word, self.case_sensitive, is_code)
+            || (self.check_hyphen_parts && self.hyphen_parts_correct(original_word, dictionary, is_code));
         
         // For proper nouns and acronyms, be more lenient
         let is_correct = match word_type {
@@ -374,10 +1829,27 @@ impl SpellChecker {
             _ => in_dictionary,
         };
         
+        if self.cache.len() >= self.max_cache_entries {
+            self.cache.clear();
+        }
         self.cache.insert(cache_key, is_correct);
         is_correct
     }
     
+    /// Split an identifier into its snake_case/camelCase parts and return the
+    /// first part (with its byte offset) that isn't a known word. Acronym runs
+    /// and parts shorter than three letters are not checked.
+    fn find_misspelled_subword(&self, identifier: &str, dictionary: &Dictionary) -> Option<(usize, String)> {
+        split_identifier(identifier)
+            .into_iter()
+            .filter(|(_, part)| part.chars().count() >= 3 && !part.chars().all(|c| c.is_uppercase()))
+            .find(|(_, part)| {
+                let part_lower = part.to_lowercase();
+                !self.check_word_correctness(&part_lower, part, &WordType::Normal, dictionary, false)
+            })
+            .map(|(offset, part)| (offset, part.to_string()))
+    }
+    
     fn looks_reasonable(&self, word: &str) -> bool {
         if word.is_empty() || word.len() > 25 {
             return false;
@@ -402,32 +1874,34 @@ impl SpellChecker {
             return 1.0;
         }
         
+        let weights = &self.confidence_weights;
         let mut confidence: f32 = 0.5;
         
         match word_type {
-            WordType::Normal => confidence *= 1.2,
-            WordType::CodeIdentifier => confidence *= if is_code { 0.3 } else { 0.8 },
-            WordType::Acronym => confidence *= 0.4,
-            WordType::ProperNoun => confidence *= 0.6,
-            WordType::TechnicalTerm => confidence *= 0.8,
-            _ => confidence *= 0.2,
+            WordType::Normal => confidence *= weights.normal,
+            WordType::CodeIdentifier if is_code => confidence *= weights.code_identifier,
+            WordType::CodeIdentifier => confidence *= weights.code_identifier_in_prose,
+            WordType::Acronym => confidence *= weights.acronym,
+            WordType::ProperNoun => confidence *= weights.proper_noun,
+            WordType::TechnicalTerm => confidence *= weights.technical_term,
+            _ => confidence *= weights.other,
         }
         
         if word.len() < 3 {
-            confidence *= 0.3;
+            confidence *= weights.short_word;
         } else if word.len() > 20 {
-            confidence *= 0.7;
+            confidence *= weights.long_word;
         }
         
         if word.contains('_') || word.contains('-') {
-            confidence *= 1.1;
+            confidence *= weights.joined_word;
         }
         
         if has_common_typo_patterns(word) {
-            confidence *= 1.3;
+            confidence *= weights.typo_pattern;
         }
         
-        confidence.min(1.0).max(0.0)
+        confidence.clamp(0.0, 1.0)
     }
     
     fn get_suggestions(&self, word: &str, dictionary: &Dictionary) -> Vec<String> {
@@ -435,32 +1909,76 @@ impl SpellChecker {
             return Vec::new();
         }
         
-        let dict_words = dictionary.get_words();
-        let max_candidates = 2000;
+        let cache_key = self.cache_key(word);
+        if let Some(cached) = self.suggestion_cache.get(&cache_key) {
+            return cached.clone();
+        }
         
-        let candidates: Vec<&String> = dict_words.iter()
-            .filter(|w| {
-                let len_diff = (w.len() as isize - word.len() as isize).abs();
-                len_diff <= 3
-            })
-            .take(max_candidates)
-            .collect();
+        let mut suggestions = match &self.custom_suggestion_provider {
+            Some(provider) => provider.suggest(word, self.current_language, dictionary),
+            None => self.suggestion_provider.suggest(word, self.current_language, dictionary),
+        };
         
-        let mut suggestions: Vec<(String, usize)> = candidates
-            .par_iter()
-            .map(|&dict_word| {
-                let distance = self.edit_distance(word, dict_word);
-                (dict_word.clone(), distance)
-            })
-            .filter(|(_, distance)| *distance <= 2)
-            .collect();
+        // A word the dictionary lists with other capitals ("iphone" for
+        // "iPhone") most likely just needs its case fixed
+        if let Some(canonical) = dictionary.canonical_case(word) {
+            suggestions.retain(|s| s != word && s != canonical);
+            suggestions.insert(0, canonical.to_string());
+        }
+        suggestions.truncate(self.max_suggestions);
+        
+        if suggestions.len() < self.max_suggestions && self.phonetic_suggestions {
+            let missing = self.max_suggestions - suggestions.len();
+            let sound_alikes = self.phonetic_matches(word, dictionary);
+            suggestions.extend(
+                sound_alikes
+                    .into_iter()
+                    .filter(|candidate| !suggestions.contains(candidate))
+                    .take(missing)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        
+        if self.suggestion_cache.len() >= self.max_cache_entries {
+            self.suggestion_cache.clear();
+        }
+        self.suggestion_cache.insert(cache_key, suggestions.clone());
         
-        suggestions.sort_by_key(|(_, distance)| *distance);
         suggestions
+    }
+    
+    /// Dictionary words with the same metaphone key as `word`, closest
+    /// first. Metaphone models English spelling, so CJK languages get none.
+    fn phonetic_matches(&self, word: &str, dictionary: &Dictionary) -> Vec<String> {
+        if matches!(self.current_language, Language::Chinese | Language::Japanese | Language::Korean) {
+            return Vec::new();
+        }
+        
+        let key = metaphone(word);
+        if key.is_empty() {
+            return Vec::new();
+        }
+        
+        let index = self.phonetic_index.get_or_init(|| {
+            let mut index: HashMap<String, Vec<String>> = HashMap::new();
+            for dict_word in dictionary.get_words() {
+                let key = metaphone(dict_word);
+                if !key.is_empty() {
+                    index.entry(key).or_default().push(dict_word.clone());
+                }
+            }
+            Arc::new(index)
+        });
+        
+        let mut matches: Vec<(usize, &String)> = index
+            .get(&key)
             .into_iter()
-            .take(self.max_suggestions)
-            .map(|(word, _)| word)
-            .collect()
+            .flatten()
+            .filter(|candidate| candidate.as_str() != word)
+            .map(|candidate| (self.edit_distance(word, candidate), candidate))
+            .collect();
+        matches.sort();
+        matches.into_iter().map(|(_, candidate)| candidate.clone()).collect()
     }
     
     fn edit_distance(&self, a: &str, b: &str) -> usize {
@@ -473,53 +1991,87 @@ impl SpellChecker {
             return Ok(());
         }
         
-        let word_lower = sanitized.to_lowercase();
-        
-        // Update cache
-        let cache_key = format!("{}_{}", self.current_language.code(), word_lower);
-        self.cache.insert(cache_key, true);
-        
-        // Update ignore list (remove if present)
-        self.ignore_list.remove(&word_lower);
-        
-        // Update user dictionary
-        self.user_dictionary.insert(word_lower.clone());
-        
-        // Update dictionary file
-        let user_dict_path = crate::language::LanguageManager::user_dict_dir()
-            .join(format!("user_{}.txt", self.current_language.code()));
-        
-        let mut content = String::new();
-        if let Ok(existing) = fs::read_to_string(&user_dict_path) {
-            content = existing;
-        }
-        
-        if !content.lines().any(|line| line.trim() == word_lower) {
-            content.push_str(&format!("{}\n", word_lower));
-            fs::write(&user_dict_path, content)?;
-        }
-        
-        // Update dictionary manager
+        // Update dictionary manager (persists the user word list)
         self.dictionary_manager.add_word_to_dictionary(&sanitized, self.current_language)?;
+        self.refresh_user_data();
         
         Ok(())
     }
     
+    /// Add every word `analysis` flags as misspelled to the user dictionary,
+    /// e.g. to accept a technical document's jargon in one go. Repeated-word
+    /// and capitalization issues are left alone, as are tokens with a letter
+    /// run three times ("zzz"). For identifiers split into parts, only the
+    /// misspelled part is learned. Returns how many words were added.
+    pub fn learn_document(&mut self, analysis: &DocumentAnalysis) -> anyhow::Result<usize> {
+        let mut words: Vec<String> = analysis.words
+            .iter()
+            .filter(|w| !w.is_correct && w.confidence >= self.confidence_threshold)
+            .filter(|w| w.kind == IssueKind::Spelling && w.word_type != WordType::RepeatedWord)
+            .map(|w| sanitize_word(w.subword.as_deref().unwrap_or(&w.original)))
+            .filter(|w| is_valid_word(w) && !has_letter_run(w, 3))
+            .map(|w| self.current_language.lowercase(&w))
+            .collect();
+        words.sort();
+        words.dedup();
+        
+        let added = self.dictionary_manager.add_user_words(words.iter().map(String::as_str), self.current_language)?;
+        self.refresh_user_data();
+        Ok(added)
+    }
+    
+    /// Take a word back out of the user dictionary. Returns false if it
+    /// wasn't user-added.
+    pub fn remove_word_from_dictionary(&mut self, word: &str) -> anyhow::Result<bool> {
+        let removed = self.dictionary_manager.remove_user_word(word, self.current_language)?;
+        self.refresh_user_data();
+        Ok(removed)
+    }
+    
     pub fn ignore_word(&mut self, word: &str) -> anyhow::Result<()> {
         let sanitized = sanitize_word(word);
         if is_valid_word(&sanitized) {
-            self.ignore_list.insert(sanitized.to_lowercase());
+            self.dictionary_manager.ignore_word(&sanitized, self.current_language)?;
+            self.refresh_user_data();
         }
         Ok(())
     }
     
+    /// Ignore `word` in the open document only. Unlike `ignore_word` this
+    /// isn't saved and lasts until `reset_document_scope`.
+    pub fn ignore_word_in_document(&mut self, word: &str) {
+        let sanitized = sanitize_word(word);
+        if is_valid_word(&sanitized) {
+            let word_lower = self.current_language.lowercase(&sanitized);
+            self.cache.remove(&self.cache_key(&sanitized));
+            self.cache.remove(&self.cache_key(&word_lower));
+            self.document_ignore_list.insert(word_lower);
+        }
+    }
+    
+    /// Forget the words ignored with `ignore_word_in_document`, as when
+    /// another document is opened
+    pub fn reset_document_scope(&mut self) {
+        // Case-sensitive cache entries are keyed by the original spelling,
+        // so there's no telling which ones an ignored word touched
+        if !self.document_ignore_list.is_empty() {
+            self.document_ignore_list.clear();
+            self.cache.clear();
+        }
+    }
+    
     pub fn clear_ignored_words(&mut self) {
-        self.ignore_list.clear();
-        self.cache.clear();
+        if let Err(e) = self.dictionary_manager.clear_ignored_words(self.current_language) {
+            eprintln!("Warning: Could not clear ignored words for {}: {}", self.current_language.name(), e);
+        }
+        self.refresh_user_data();
     }
     
-    pub fn import_dictionary(&mut self, path: &Path) -> anyhow::Result<()> {
-        let content = fs::read_to_string(path)?;
+    /// Import a word list, returning how many new words it added
+    pub fn import_dictionary(&mut self, path: &Path, mode: ImportMode) -> anyhow::Result<usize> {
+        // Hunspell dictionaries are often in a legacy encoding, which the
+        // loader reads lossily too
+        let content = String::from_utf8_lossy(&fs::read(path)?).into_owned();
         let detected_language = self.dictionary_manager.detect_language(&content);
         let language_to_use = if detected_language != Language::English {
             detected_language
@@ -527,32 +2079,370 @@ impl SpellChecker {
             self.current_language
         };
         
-        self.dictionary_manager.import_dictionary(path.to_path_buf(), language_to_use)?;
+        let added = self.dictionary_manager.import_dictionary(path.to_path_buf(), language_to_use, mode)?;
         self.cache.clear();
+        self.suggestion_cache.clear();
+        self.phonetic_index = OnceCell::new();
         
-        Ok(())
+        Ok(added)
+    }
+    
+    /// Use the nearest `.atomspell-dict.txt` in `start_dir` or its
+    /// ancestors as the project dictionary, one word per line with `#`
+    /// comments. Replaces any previously loaded project dictionary and
+    /// returns the file used, or `None` (and an empty project dictionary)
+    /// if there is none.
+    pub fn load_project_dictionary(&mut self, start_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+        self.project_dictionary.clear();
+        self.project_dictionary_path = None;
+        
+        let Some(path) = start_dir
+            .ancestors()
+            .map(|dir| dir.join(PROJECT_DICTIONARY_FILE))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+        
+        let content = fs::read_to_string(&path)?;
+        self.project_dictionary = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+        self.project_dictionary_path = Some(path.clone());
+        
+        Ok(Some(path))
+    }
+    
+    pub fn project_dictionary_path(&self) -> Option<&Path> {
+        self.project_dictionary_path.as_deref()
+    }
+    
+    pub fn project_word_count(&self) -> usize {
+        self.project_dictionary.len()
     }
     
     pub fn export_dictionary(&self, path: &Path) -> anyhow::Result<()> {
         self.dictionary_manager.export_dictionary(&self.current_language, path)
     }
     
+    pub fn export_user_words(&self, path: &Path) -> anyhow::Result<()> {
+        self.dictionary_manager.export_user_words(&self.current_language, path)
+    }
+    
+    pub fn set_min_word_length(&mut self, length: usize) -> anyhow::Result<()> {
+        self.dictionary_manager.set_min_word_length(self.current_language, length)?;
+        self.cache.clear();
+        Ok(())
+    }
+    
+    pub fn min_word_length(&self) -> usize {
+        self.dictionary_manager.get_min_word_length(&self.current_language)
+    }
+    
+    /// When enabled, recognised source files are checked only inside
+    /// comments and string literals; the rest of the code is skipped.
+    pub fn set_comments_only(&mut self, enabled: bool) {
+        self.comments_only = enabled;
+    }
+    
+    /// When enabled (the default), HTML and XML files are checked only in
+    /// the text a reader sees and in `alt` and `title` attribute values.
+    /// Tag names, other attributes, comments and the contents of `<script>`
+    /// and `<style>` elements are skipped.
+    pub fn set_html_mode(&mut self, enabled: bool) {
+        self.html_mode = enabled;
+    }
+    
+    /// When enabled (the default), Chinese, Japanese and Korean text is
+    /// split into CJK runs and runs of Latin letters and digits, and each
+    /// run of letters in a Latin word is looked up in English: "iPhone12"
+    /// is checked as "iPhone". When disabled, Latin words are checked
+    /// against the CJK dictionary and digits are dropped.
+    pub fn set_cjk_allow_mixed(&mut self, enabled: bool) {
+        if enabled != self.cjk_allow_mixed {
+            self.cjk_allow_mixed = enabled;
+            self.cache.clear();
+        }
+    }
+    
+    /// When enabled, code identifiers are split on `_` and camelCase
+    /// boundaries and each part is checked on its own.
+    pub fn set_split_identifiers(&mut self, enabled: bool) {
+        self.split_identifiers = enabled;
+    }
+    
+    /// When enabled, the same word twice in a row ("the the") within a line
+    /// of prose is flagged as a `WordType::RepeatedWord` issue, and prose
+    /// outside code files gets `IssueKind::Capitalization` issues for
+    /// lowercase sentence starts and a lowercase "i".
+    pub fn set_advanced_typo_detection(&mut self, enabled: bool) {
+        self.advanced_typo_detection = enabled;
+    }
+    
+    /// When enabled, flagged words carry a `WordContext` snippet of their
+    /// line. Off by default to keep analyses of huge files small.
+    pub fn set_capture_context(&mut self, enabled: bool) {
+        self.capture_context = enabled;
+    }
+    
+    pub fn set_alphanumeric_policy(&mut self, policy: AlphanumericPolicy) {
+        if policy != self.alphanumeric_policy {
+            self.alphanumeric_policy = policy;
+            self.cache.clear();
+        }
+    }
+    
+    pub fn alphanumeric_policy(&self) -> AlphanumericPolicy {
+        self.alphanumeric_policy
+    }
+    
+    /// When enabled (the default), ALL_CAPS tokens such as `MAX_BUFFER_SIZE`
+    /// are treated as constants and skipped in code, and anywhere they
+    /// contain `_`. Shouted prose like "HELLO WROLD" is still checked.
+    pub fn set_skip_constants(&mut self, enabled: bool) {
+        self.skip_constants = enabled;
+    }
+    
+    /// Split `language` prose into words with the regex `pattern`; see
+    /// `Dictionary::set_word_pattern`. An invalid pattern is rejected and
+    /// the current one kept. `None` restores the built-in pattern.
+    pub fn set_word_pattern(&mut self, language: Language, pattern: Option<&str>) -> crate::Result<()> {
+        match pattern {
+            Some(pattern) => {
+                self.word_patterns.insert(language, Regex::new(pattern)?);
+            }
+            None => {
+                self.word_patterns.remove(&language);
+            }
+        }
+        Ok(())
+    }
+    
+    /// When enabled (the default), a hyphenated word missing from the
+    /// dictionary is still correct if each part is ("well-being").
+    pub fn set_check_hyphen_parts(&mut self, enabled: bool) {
+        if enabled != self.check_hyphen_parts {
+            self.check_hyphen_parts = enabled;
+            self.cache.clear();
+        }
+    }
+    
+    /// When enabled, words mixing Latin, Cyrillic or Greek letters ("pаss"
+    /// with a Cyrillic "а") are flagged as `IssueKind::MixedScript`, in code
+    /// as well as prose. Off by default since some text mixes them on purpose.
+    pub fn set_detect_mixed_script(&mut self, enabled: bool) {
+        self.detect_mixed_script = enabled;
+    }
+    
+    /// Look words misspelled in the current language up in `languages` too,
+    /// and report the ones found as hints ("valid in Spanish") rather than
+    /// errors. Each dictionary is loaded the first time it's needed. None by
+    /// default, since every misspelling costs a lookup per language.
+    pub fn set_secondary_languages(&mut self, languages: Vec<Language>) {
+        let mut secondary = Vec::new();
+        for language in languages {
+            if language != Language::AutoDetect && !secondary.contains(&language) {
+                secondary.push(language);
+            }
+        }
+        self.secondary_languages = secondary;
+    }
+    
+    pub fn secondary_languages(&self) -> &[Language] {
+        &self.secondary_languages
+    }
+    
+    /// The first secondary language other than the current one whose
+    /// dictionary has `word`
+    fn secondary_language_for(&self, word: &str) -> Option<Language> {
+        self.secondary_languages
+            .iter()
+            .filter(|language| **language != self.current_language)
+            .find(|language| self.dictionary_manager.dictionary_contains(language, word, self.case_sensitive))
+            .copied()
+    }
+    
+    /// When enabled, two or more spaces between words and whitespace at the
+    /// end of a line are flagged as `IssueKind::Whitespace`. Indentation is
+    /// never flagged, spaces between words only in prose and in the
+    /// comments and strings of comments-only mode, and two trailing spaces
+    /// not at all in Markdown. Off by default.
+    pub fn set_check_whitespace(&mut self, enabled: bool) {
+        self.check_whitespace = enabled;
+    }
+    
     pub fn enable_suggestions(&mut self, enabled: bool) {
         self.suggestions_enabled = enabled;
     }
     
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+    
+    /// Match case when checking, in every language until
+    /// `use_language_case_default` is called
     pub fn set_case_sensitive(&mut self, sensitive: bool) {
-        self.case_sensitive = sensitive;
-        self.cache.clear();
+        self.explicit_case_override = true;
+        self.update_case_sensitive(sensitive);
     }
     
-    pub fn word_count(&self) -> usize {
-        match self.get_current_dictionary() {
-            Ok(dict) => dict.word_count(),
-            Err(_) => 0,
+    /// Go back to each language's `default_case_sensitive`
+    pub fn use_language_case_default(&mut self) {
+        self.explicit_case_override = false;
+        self.update_case_sensitive(self.current_language.default_case_sensitive());
+    }
+    
+    fn update_case_sensitive(&mut self, sensitive: bool) {
+        if sensitive != self.case_sensitive {
+            self.case_sensitive = sensitive;
+            self.cache.clear();
+        }
+    }
+    
+    /// Words whose typo confidence falls below this are reported as correct.
+    /// Clamped to `0.0..=1.0`; lower values catch more (and noisier) typos.
+    pub fn set_confidence_threshold(&mut self, threshold: f32) {
+        self.confidence_threshold = threshold.clamp(0.0, 1.0);
+    }
+    
+    pub fn confidence_weights(&self) -> ConfidenceWeights {
+        self.confidence_weights
+    }
+    
+    /// Replace the multipliers that turn a dictionary miss into a typo
+    /// confidence, e.g. to flag code identifiers less eagerly
+    pub fn set_confidence_weights(&mut self, weights: ConfidenceWeights) {
+        self.confidence_weights = weights;
+    }
+    
+    pub fn set_max_suggestions(&mut self, max: usize) {
+        if max != self.max_suggestions {
+            self.max_suggestions = max;
+            self.suggestion_cache.clear();
+        }
+    }
+    
+    /// How many edits of distance the most common dictionary word is worth
+    /// when ranking suggestions; 0.0 ranks by edit distance alone. Has no
+    /// effect for dictionaries without frequency data, or while a custom
+    /// suggestion provider is installed.
+    pub fn set_frequency_weight(&mut self, weight: f32) {
+        let weight = weight.max(0.0);
+        if weight != self.suggestion_provider.frequency_weight() {
+            self.suggestion_provider.set_frequency_weight(weight);
+            self.suggestion_cache.clear();
+        }
+    }
+    
+    /// When enabled, suggestions one neighboring-key slip away on the
+    /// keyboard layout ("thr" -> "the") rank above other equally close
+    /// words. Off by default; like `set_frequency_weight`, it only affects
+    /// the built-in provider.
+    pub fn set_keyboard_aware(&mut self, enabled: bool) {
+        let layout = enabled.then_some(self.keyboard_layout);
+        if layout != self.suggestion_provider.keyboard_layout() {
+            self.suggestion_provider.set_keyboard_layout(layout);
+            self.suggestion_cache.clear();
+        }
+    }
+    
+    pub fn is_keyboard_aware(&self) -> bool {
+        self.suggestion_provider.keyboard_layout().is_some()
+    }
+    
+    /// The keyboard layout `set_keyboard_aware` ranks against
+    pub fn set_keyboard_layout(&mut self, layout: KeyboardLayout) {
+        self.keyboard_layout = layout;
+        if self.is_keyboard_aware() {
+            self.set_keyboard_aware(true);
+        }
+    }
+    
+    /// Get suggestions from `provider` instead of the built-in
+    /// `EditDistanceProvider`. Phonetic suggestions, when enabled, still
+    /// fill any slots it leaves empty.
+    pub fn set_suggestion_provider(&mut self, provider: Box<dyn SuggestionProvider>) {
+        self.custom_suggestion_provider = Some(Arc::from(provider));
+        self.suggestion_cache.clear();
+    }
+    
+    /// Go back to the built-in suggestion provider
+    pub fn reset_suggestion_provider(&mut self) {
+        if self.custom_suggestion_provider.take().is_some() {
+            self.suggestion_cache.clear();
+        }
+    }
+    
+    /// When enabled, words that sound like the misspelling ("fonetik" ->
+    /// "phonetic") fill any suggestion slots edit distance leaves empty.
+    pub fn set_phonetic_suggestions(&mut self, enabled: bool) {
+        if enabled != self.phonetic_suggestions {
+            self.phonetic_suggestions = enabled;
+            self.suggestion_cache.clear();
+        }
+    }
+    
+    /// Keep at most `max` flagged words in each analysis, so garbage input
+    /// such as a binary file can't produce a huge one. Words past the cap
+    /// are still counted in `misspelled_words` and the analysis is marked
+    /// `truncated`. `None`, the default, keeps every flagged word.
+    pub fn set_max_errors(&mut self, max: Option<usize>) {
+        self.max_errors = max;
+    }
+    
+    pub fn max_errors(&self) -> Option<usize> {
+        self.max_errors
+    }
+    
+    /// Cap on entries in the correctness and suggestion caches; a cache
+    /// that reaches it is cleared before the next insert.
+    pub fn set_max_cache_entries(&mut self, max: usize) {
+        self.max_cache_entries = max.max(1);
+    }
+    
+    pub fn apply_settings(&mut self, settings: &CheckerSettings) {
+        self.set_confidence_threshold(settings.confidence_threshold);
+        self.set_max_suggestions(settings.max_suggestions);
+        match settings.case_sensitive {
+            Some(sensitive) => self.set_case_sensitive(sensitive),
+            None => self.use_language_case_default(),
+        }
+        self.enable_suggestions(settings.suggestions_enabled);
+        self.set_max_cache_entries(settings.max_cache_entries);
+        self.set_detect_mixed_script(settings.detect_mixed_script);
+        self.set_check_whitespace(settings.check_whitespace);
+        
+        self.word_patterns.clear();
+        for (code, pattern) in &settings.word_patterns {
+            if let Err(e) = self.set_word_pattern(Language::from_code(code), Some(pattern)) {
+                eprintln!("Warning: Ignoring word pattern for '{}': {}", code, e);
+            }
+        }
+    }
+    
+    pub fn current_settings(&self) -> CheckerSettings {
+        CheckerSettings {
+            confidence_threshold: self.confidence_threshold,
+            max_suggestions: self.max_suggestions,
+            case_sensitive: self.explicit_case_override.then_some(self.case_sensitive),
+            suggestions_enabled: self.suggestions_enabled,
+            max_cache_entries: self.max_cache_entries,
+            detect_mixed_script: self.detect_mixed_script,
+            check_whitespace: self.check_whitespace,
+            word_patterns: self.word_patterns
+                .iter()
+                .map(|(language, pattern)| (language.code().to_string(), pattern.as_str().to_string()))
+                .collect(),
         }
     }
     
+    pub fn word_count(&self) -> usize {
+        self.with_current_dictionary(Dictionary::word_count).unwrap_or(0)
+    }
+    
     pub fn ignored_word_count(&self) -> usize {
         self.ignore_list.len()
     }
@@ -587,10 +2477,962 @@ fn has_vowels(word: &str) -> bool {
     word.chars().any(|c| vowels.contains(&c))
 }
 
+/// Whether `line[start..end]` isn't part of a longer run of letters or digits
+fn is_whole_token(line: &str, start: usize, end: usize) -> bool {
+    !line[..start].chars().next_back().is_some_and(char::is_alphanumeric)
+        && !line[end..].chars().next().is_some_and(char::is_alphanumeric)
+}
+
+/// A correct, unchecked `WordCheck` for `word` on line 1
+fn skipped_word(word: &str, word_type: WordType) -> WordCheck {
+    WordCheck {
+        word: word.to_lowercase(),
+        original: word.to_string(),
+        start: 0,
+        end: word.len(),
+        is_correct: true,
+        raw_misspelled: false,
+        suggestions: Vec::new(),
+        line: 1,
+        column: 1,
+        confidence: 1.0,
+        word_type,
+        subword: None,
+        kind: IssueKind::Spelling,
+        context: None,
+        ignore_rule: None,
+        valid_in: None,
+        severity: Severity::Hint,
+    }
+}
+
 fn has_common_typo_patterns(word: &str) -> bool {
     let common_patterns = [
         "ie", "ei", "tion", "sion", "able", "ible", "ment", "ness", "ough"
     ];
     
     common_patterns.iter().any(|pattern| word.contains(pattern))
-}
\ No newline at end of file
+}
+
+/// Rebuild `identifier` with the part at `offset` replaced by `suggestion`,
+/// keeping the part's leading capital.
+fn replace_subword(identifier: &str, offset: usize, part: &str, suggestion: &str) -> String {
+    let mut replacement = suggestion.to_string();
+    if part.chars().next().map(|c| c.is_uppercase()).unwrap_or(false) {
+        let mut chars = suggestion.chars();
+        if let Some(first) = chars.next() {
+            replacement = first.to_uppercase().chain(chars).collect();
+        }
+    }
+    format!("{}{}{}", &identifier[..offset], replacement, &identifier[offset + part.len()..])
+}
+
+/// Whether a language from `programming_language_from_filename` is HTML
+/// or XML
+fn is_markup(language: Option<&str>) -> bool {
+    matches!(language, Some("html" | "xml"))
+}
+
+struct CommentSyntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    triple_quotes: bool,
+    multiline_strings: bool,
+}
+
+fn comment_syntax_for(language: &str) -> Option<CommentSyntax> {
+    let c_family = |quotes: &'static [char], multiline_strings: bool| CommentSyntax {
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes,
+        triple_quotes: false,
+        multiline_strings,
+    };
+    let hash_comments = |triple_quotes: bool| CommentSyntax {
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        triple_quotes,
+        multiline_strings: false,
+    };
+    
+    match language {
+        "rust" => Some(c_family(&['"'], true)),
+        "java" | "cpp" | "csharp" => Some(c_family(&['"'], false)),
+        "go" => Some(c_family(&['"', '`'], false)),
+        "javascript" | "typescript" => Some(c_family(&['"', '\'', '`'], false)),
+        "php" => Some(CommentSyntax {
+            line_comments: &["//", "#"],
+            ..c_family(&['"', '\''], false)
+        }),
+        "css" => Some(CommentSyntax {
+            line_comments: &[],
+            ..c_family(&['"', '\''], false)
+        }),
+        "python" => Some(hash_comments(true)),
+        "ruby" | "shell" | "yaml" | "toml" => Some(hash_comments(false)),
+        _ => None,
+    }
+}
+
+/// Byte ranges of all comments and string literals in `text`, in order.
+fn comment_and_string_spans(text: &str, syntax: &CommentSyntax) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    
+    while let Some(c) = text[i..].chars().next() {
+        let rest = &text[i..];
+        
+        let end = if let Some(delim) = ["\"\"\"", "'''"]
+            .into_iter()
+            .find(|d| syntax.triple_quotes && rest.starts_with(d))
+        {
+            rest[3..].find(delim).map(|p| i + 3 + p + 3)
+        } else if let Some((open, close)) = syntax.block_comment.filter(|(open, _)| rest.starts_with(open)) {
+            rest[open.len()..].find(close).map(|p| i + open.len() + p + close.len())
+        } else if syntax.line_comments.iter().any(|prefix| rest.starts_with(prefix)) {
+            rest.find('\n').map(|p| i + p)
+        } else if syntax.quotes.contains(&c) {
+            let multiline = syntax.multiline_strings || c == '`';
+            string_literal_len(rest, c, multiline).map(|len| i + len)
+        } else {
+            i += c.len_utf8();
+            continue;
+        };
+        
+        let end = end.unwrap_or(text.len());
+        spans.push((i, end));
+        i = end;
+    }
+    
+    spans
+}
+
+/// Length of the string literal opening `text` with `quote`, or `None` if it
+/// runs to the end of the input.
+fn string_literal_len(text: &str, quote: char, multiline: bool) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    
+    while let Some((idx, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == quote {
+            return Some(idx + c.len_utf8());
+        } else if c == '\n' && !multiline {
+            return Some(idx);
+        }
+    }
+    
+    None
+}
+
+fn span_contains(spans: &[(usize, usize)], start: usize, end: usize) -> bool {
+    let idx = spans.partition_point(|(span_start, _)| *span_start <= start);
+    idx > 0 && end <= spans[idx - 1].1
+}
+
+/// Up to `CONTEXT_CHARS` characters of `line` either side of `start..end`,
+/// with surrounding whitespace trimmed and "…" marking cut ends
+fn word_context(line: &str, start: usize, end: usize) -> WordContext {
+    let from = line[..start].char_indices().rev().nth(CONTEXT_CHARS - 1).map_or(0, |(i, _)| i);
+    let to = line[end..].char_indices().nth(CONTEXT_CHARS).map_or(line.len(), |(i, _)| end + i);
+    
+    let before = line[from..start].trim_start();
+    let after = line[end..to].trim_end();
+    let prefix = if from > 0 { "…" } else { "" };
+    let suffix = if to < line.len() { "…" } else { "" };
+    
+    WordContext {
+        text: format!("{}{}{}{}{}", prefix, before, &line[start..end], after, suffix),
+        offset: prefix.len() + before.len(),
+    }
+}
+
+/// Punctuation that may open a sentence before its first word
+const OPENING_PUNCTUATION: &[char] = &['"', '\'', '(', '[', '“', '‘', '¿', '¡'];
+/// Punctuation that may follow a sentence's final `.`, `!` or `?`
+const CLOSING_PUNCTUATION: &[char] = &['"', '\'', ')', ']', '”', '’'];
+/// Abbreviations whose trailing period doesn't end a sentence
+const ABBREVIATIONS: &[&str] = &["mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "cf", "approx", "fig", "no"];
+
+/// Whether `text` ends a sentence: it is blank, or ends with `.`, `!` or `?`
+/// (possibly inside quotes or brackets) that isn't an abbreviation's period
+fn ends_sentence(text: &str) -> bool {
+    let text = text.trim_end_matches(|c: char| c.is_whitespace() || OPENING_PUNCTUATION.contains(&c));
+    if text.is_empty() {
+        return true;
+    }
+    
+    let text = text.trim_end_matches(CLOSING_PUNCTUATION);
+    if text.ends_with(['!', '?']) {
+        return true;
+    }
+    let Some(token) = text.strip_suffix('.') else {
+        return false;
+    };
+    
+    // "e.g.", "U.S." and "wait..." have inner periods
+    let token = token
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches(OPENING_PUNCTUATION);
+    !token.contains('.') && !ABBREVIATIONS.contains(&token.to_lowercase().as_str())
+}
+
+/// Whether a word preceded by `before` on its line starts a sentence. A word
+/// with nothing but whitespace or opening punctuation before it continues
+/// the previous line's state, `previous_ended`.
+fn starts_sentence(before: &str, previous_ended: bool) -> bool {
+    let trimmed = before.trim_end_matches(|c: char| c.is_whitespace() || OPENING_PUNCTUATION.contains(&c));
+    if trimmed.is_empty() {
+        return previous_ended;
+    }
+    
+    let separated = before[trimmed.len()..].chars().any(char::is_whitespace);
+    separated && ends_sentence(trimmed)
+}
+
+/// Whether the word at `start..end` is a letter of a dotted abbreviation
+/// such as "i.e." or "e.g."
+fn is_abbreviation_part(line: &str, start: usize, end: usize) -> bool {
+    let dot_then_letter = line[end..].strip_prefix('.').and_then(|rest| rest.chars().next()).is_some_and(char::is_alphabetic);
+    let letter_then_dot = line[..start].strip_suffix('.').and_then(|rest| rest.chars().last()).is_some_and(char::is_alphabetic);
+    dot_then_letter || letter_then_dot
+}
+
+/// Whether `word` looks like a constant: uppercase letters, digits and
+/// underscores only, with at least one letter
+fn is_constant_name(word: &str) -> bool {
+    word.chars().any(char::is_alphabetic)
+        && word.chars().all(|c| c.is_uppercase() || c.is_numeric() || c == '_')
+}
+
+/// Whether `line` is written in capitals: two or more words and no
+/// lowercase letters
+fn is_shouted(line: &str) -> bool {
+    !line.chars().any(char::is_lowercase)
+        && line
+            .split_whitespace()
+            .filter(|word| word.chars().filter(|c| c.is_alphabetic()).count() >= 2)
+            .count() >= 2
+}
+
+/// Whether `word` has the same letter `run` or more times in a row
+fn has_letter_run(word: &str, run: usize) -> bool {
+    let mut previous = None;
+    let mut count = 0;
+    for c in word.chars().flat_map(char::to_lowercase) {
+        count = if Some(c) == previous { count + 1 } else { 1 };
+        if count >= run && c.is_alphabetic() {
+            return true;
+        }
+        previous = Some(c);
+    }
+    false
+}
+
+/// An English checker whose dictionary is only `words`, with its user
+/// data in a fresh directory that is removed when the `TempDir` drops
+#[cfg(test)]
+pub(crate) fn checker_with_words(words: &[&str]) -> (SpellChecker, tempfile::TempDir) {
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(dir.path().join("dictionary(eng).txt"), words.join("\n")).unwrap();
+    let checker = SpellChecker::with_data_dir(Language::English, dir.path()).unwrap();
+    (checker, dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::BytesSource;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+    
+    #[test]
+    fn added_word_is_correct_without_recreating_the_checker() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        assert!(checker.check_document("frobnicate").words[0].raw_misspelled);
+        
+        checker.add_word_to_dictionary("frobnicate").unwrap();
+        assert!(!checker.check_document("frobnicate").words[0].raw_misspelled);
+        assert_eq!(checker.user_word_count(), 1);
+    }
+    
+    #[test]
+    fn added_word_clears_cached_results_for_every_spelling() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_case_sensitive(true);
+        assert!(checker.check_word("frobnicate", false).raw_misspelled);
+        assert!(checker.check_word("frobNicate", false).raw_misspelled);
+        
+        assert!(checker.cache.contains_key(&checker.cache_key("frobNicate")));
+        
+        checker.add_word_to_dictionary("frobnicate").unwrap();
+        assert!(!checker.cache.contains_key(&checker.cache_key("frobNicate")));
+        assert!(!checker.check_word("frobnicate", false).raw_misspelled);
+        assert!(!checker.check_word("frobNicate", false).raw_misspelled);
+    }
+    
+    #[test]
+    fn reader_keeps_only_errors_when_not_retaining_correct_words() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        checker.enable_suggestions(false);
+        
+        let text = "The cat sat xyzzy\n".repeat(50_000);
+        let analysis = checker.check_reader(std::io::Cursor::new(text), None, false);
+        assert_eq!(analysis.lines_checked, 50_000);
+        assert_eq!(analysis.misspelled_words, 50_000);
+        assert_eq!(analysis.words.len(), analysis.misspelled_words);
+    }
+    
+    #[test]
+    fn urls_and_emails_are_not_flagged() {
+        let (mut checker, _dir) = checker_with_words(&["see", "or", "mail"]);
+        checker.set_confidence_threshold(0.0);
+        
+        let analysis = checker.check_document("See https://foo.bar/baz or mail a@b.com");
+        assert_eq!(analysis.misspelled_words, 0);
+        assert!(analysis.words.iter().any(|w| w.word_type == WordType::Url && w.is_correct));
+        assert!(analysis.words.iter().any(|w| w.word_type == WordType::Email && w.is_correct));
+    }
+    
+    #[test]
+    fn repeated_words_are_flagged_without_skewing_accuracy() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat", "had", "gone"]);
+        
+        let analysis = checker.check_document("The cat sat the the cat");
+        let repeats: Vec<&WordCheck> = analysis.words.iter().filter(|w| w.word_type == WordType::RepeatedWord).collect();
+        assert_eq!(repeats.len(), 1);
+        assert_eq!(repeats[0].original, "the the");
+        assert_eq!(repeats[0].suggestions, vec!["the".to_string()]);
+        assert_eq!((analysis.total_words, analysis.misspelled_words), (6, 1));
+        assert_eq!(analysis.accuracy, 100.0);
+        
+        // Grammatical doubles are still flagged for the user to dismiss
+        let analysis = checker.check_document("They had had enough");
+        assert!(analysis.words.iter().any(|w| w.word_type == WordType::RepeatedWord && w.original == "had had"));
+        
+        let analysis = checker.check_document("A a a b");
+        assert_eq!(analysis.misspelled_words, 2);
+        assert_eq!(analysis.accuracy, 100.0);
+        
+        // Two misspellings and the repeat, out of three words
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("The xyzzy xyzzy");
+        assert_eq!((analysis.total_words, analysis.misspelled_words), (3, 3));
+        assert_eq!(analysis.accuracy, 33.0);
+    }
+    
+    #[test]
+    fn capitalization_is_flagged_without_skewing_accuracy() {
+        let (checker, _dir) = checker_with_words(&["went", "the", "cat", "it", "ran"]);
+        
+        let analysis = checker.check_document("i went");
+        let flagged: Vec<&WordCheck> = analysis.words.iter().filter(|w| !w.is_correct).collect();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].kind, IssueKind::Capitalization);
+        assert_eq!(flagged[0].suggestions, vec!["I".to_string()]);
+        assert_eq!((analysis.total_words, analysis.misspelled_words), (1, 1));
+        assert_eq!(analysis.accuracy, 100.0);
+        
+        let analysis = checker.check_document("The cat. it ran");
+        let flagged: Vec<&str> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| w.original.as_str()).collect();
+        assert_eq!(flagged, vec!["it"]);
+        assert_eq!(analysis.accuracy, 100.0);
+    }
+    
+    #[test]
+    fn whitespace_issues_are_flagged_without_skewing_accuracy() {
+        let (mut checker, _dir) = checker_with_words(&["hello", "world"]);
+        checker.set_check_whitespace(true);
+        
+        let analysis = checker.check_document("Hello  world");
+        let issues: Vec<&WordCheck> = analysis.words.iter().filter(|w| w.kind == IssueKind::Whitespace).collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].start, issues[0].end), (5, 7));
+        assert_eq!(issues[0].suggestions, vec![" ".to_string()]);
+        assert_eq!((analysis.total_words, analysis.misspelled_words), (2, 1));
+        assert_eq!(analysis.accuracy, 100.0);
+        
+        let analysis = checker.check_document("Hello world \nWorld");
+        let issues: Vec<&WordCheck> = analysis.words.iter().filter(|w| w.kind == IssueKind::Whitespace).collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].start, issues[0].end), (1, 11, 12));
+        assert_eq!(issues[0].suggestions, vec![String::new()]);
+        
+        // Two trailing spaces are a line break, but only in Markdown
+        let text = "Hello world  \nWorld";
+        assert_eq!(checker.check_document_with_context(text, Some("notes.md")).misspelled_words, 0);
+        assert_eq!(checker.check_document_with_context(text, Some("notes.txt")).misspelled_words, 1);
+    }
+    
+    #[test]
+    fn project_dictionary_discovery_stops_at_the_nearest_ancestor() {
+        let project = TempDir::new().unwrap();
+        let root = project.path();
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(PROJECT_DICTIONARY_FILE), "rootword\n").unwrap();
+        fs::write(root.join("a").join(PROJECT_DICTIONARY_FILE), "# project words\nfrobnitz\n").unwrap();
+        
+        let (mut checker, _dir) = checker_with_words(&["the"]);
+        let found = checker.load_project_dictionary(&nested).unwrap();
+        assert_eq!(found, Some(root.join("a").join(PROJECT_DICTIONARY_FILE)));
+        assert_eq!(checker.project_word_count(), 1);
+        assert!(!checker.check_word("frobnitz", false).raw_misspelled);
+        assert!(checker.check_word("rootword", false).raw_misspelled);
+    }
+    
+    #[test]
+    fn common_words_rank_first_among_equally_close_suggestions() {
+        let (mut checker, _dir) = checker_with_words(&[]);
+        checker.load_dictionary_from_source(&BytesSource::new("the,1000000\nthaw,10\n")).unwrap();
+        checker.set_confidence_threshold(0.5);
+        assert_eq!(checker.check_word("thw", false).suggestions, vec!["the", "thaw"]);
+        
+        // Without frequency weighting, ties are broken alphabetically
+        checker.set_frequency_weight(0.0);
+        assert_eq!(checker.check_word("thw", false).suggestions, vec!["thaw", "the"]);
+    }
+    
+    #[test]
+    fn phonetic_suggestions_find_sound_alikes() {
+        let (mut checker, _dir) = checker_with_words(&["phonetic", "the"]);
+        checker.set_confidence_threshold(0.5);
+        assert!(!checker.check_word("fonetik", false).suggestions.contains(&"phonetic".to_string()));
+        
+        checker.set_phonetic_suggestions(true);
+        assert!(checker.check_word("fonetik", false).suggestions.contains(&"phonetic".to_string()));
+    }
+    
+    #[test]
+    fn custom_suggestion_provider_is_used() {
+        struct FixedProvider;
+        
+        impl SuggestionProvider for FixedProvider {
+            fn suggest(&self, _word: &str, _language: Language, _dictionary: &Dictionary) -> Vec<String> {
+                vec!["alpha".to_string(), "beta".to_string()]
+            }
+        }
+        
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_suggestion_provider(Box::new(FixedProvider));
+        assert_eq!(checker.check_word("cta", false).suggestions, vec!["alpha", "beta"]);
+        
+        checker.reset_suggestion_provider();
+        assert_eq!(checker.check_word("cta", false).suggestions, vec!["cat"]);
+    }
+    
+    #[test]
+    fn alphanumeric_policies() {
+        let (mut checker, _dir) = checker_with_words(&["covid"]);
+        let flagged = |checker: &SpellChecker| -> Vec<bool> {
+            ["covid19", "a1", "b2b"].iter().map(|word| checker.check_word(word, false).raw_misspelled).collect()
+        };
+        
+        checker.set_alphanumeric_policy(AlphanumericPolicy::AlwaysSkip);
+        assert_eq!(flagged(&checker), vec![false, false, false]);
+        
+        checker.set_alphanumeric_policy(AlphanumericPolicy::SkipIfMostlyDigits);
+        assert_eq!(flagged(&checker), vec![true, false, true]);
+        
+        // "b2b" leaves only single letters, which are too short to check
+        checker.set_alphanumeric_policy(AlphanumericPolicy::CheckLettersOnly);
+        assert_eq!(flagged(&checker), vec![false, false, false]);
+        assert!(checker.check_word("cvid19", false).raw_misspelled);
+    }
+    
+    #[test]
+    fn directives_switch_checking_off() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        let flagged = |text: &str| -> Vec<String> {
+            checker.check_document(text).words.into_iter().filter(|w| !w.is_correct).map(|w| w.original).collect()
+        };
+        
+        let text = "The cat qwxz\natomspell:disable\nzzyzx vlorp\natomspell:enable\nThe cat blarg";
+        assert_eq!(flagged(text), vec!["qwxz", "blarg"]);
+        
+        let text = "The cat qwxz atomspell:ignore\nThe cat blarg";
+        assert_eq!(flagged(text), vec!["blarg"]);
+        
+        let text = "The blarg sat\natomspell:ignore-word blarg\nThe cat blarg";
+        assert!(flagged(text).is_empty());
+    }
+    
+    #[test]
+    fn analysis_survives_a_json_round_trip() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_check_whitespace(true);
+        let analysis = checker.check_document_with_context("The cta  sat the the\ni sat", Some("notes.md"));
+        assert!(analysis.words.iter().any(|w| !w.is_correct));
+        
+        let json = serde_json::to_string(&analysis).unwrap();
+        assert_eq!(DocumentAnalysis::from_json(&json).unwrap(), analysis);
+    }
+    
+    #[test]
+    fn hyphenated_words_are_checked_part_by_part() {
+        let (mut checker, _dir) = checker_with_words(&["well", "being"]);
+        assert!(!checker.check_word("well-being", false).raw_misspelled);
+        assert!(checker.check_word("well-xyzzy", false).raw_misspelled);
+        
+        checker.set_check_hyphen_parts(false);
+        assert!(checker.check_word("well-being", false).raw_misspelled);
+    }
+    
+    #[test]
+    fn check_word_returns_a_full_result() {
+        let (mut checker, _dir) = checker_with_words(&["the", "receive", "relieve"]);
+        checker.set_confidence_threshold(0.5);
+        
+        let check = checker.check_word("recieve", false);
+        assert!(!check.is_correct);
+        assert_eq!(check.word_type, WordType::Normal);
+        assert!(check.suggestions.contains(&"receive".to_string()));
+        assert_eq!((check.line, check.column, check.start, check.end), (1, 1, 0, 7));
+        
+        let check = checker.check_word("the", false);
+        assert!(check.is_correct);
+        assert!(check.suggestions.is_empty());
+    }
+    
+    #[test]
+    fn user_ignore_rules_skip_matching_tokens() {
+        let (mut checker, dir) = checker_with_words(&["see", "and"]);
+        checker.set_confidence_threshold(0.0);
+        let text = "See JIRA-123 and #ff00aa";
+        checker.add_ignore_rule(IgnoreRule::new("ticket", r"[A-Z]+-\d+").unwrap()).unwrap();
+        checker.add_ignore_rule(IgnoreRule::new("hex color", r"#[0-9a-fA-F]{6}").unwrap()).unwrap();
+        let analysis = checker.check_document(text);
+        assert_eq!(analysis.misspelled_words, 0);
+        let rules: Vec<&str> = analysis.words.iter().filter_map(|w| w.ignore_rule.as_deref()).collect();
+        assert_eq!(rules, vec!["ticket", "hex color"]);
+        
+        // Saved to rules.json, so a checker on the same data picks them up
+        let reopened = SpellChecker::with_data_dir(Language::English, dir.path()).unwrap();
+        assert_eq!(reopened.ignore_rules().len(), 2);
+    }
+    
+    #[test]
+    fn error_cap_keeps_the_first_errors_and_counts_the_rest() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_max_errors(Some(3));
+        let text = "The blorf cat.\nThe zzqx cat.\nThe wuggle cat.\nThe fnord cat.\nThe plugh cat.\nThe grault cat.";
+        
+        let analysis = checker.check_document(text);
+        assert!(analysis.truncated);
+        assert_eq!(analysis.misspelled_words, 6);
+        let kept: Vec<&str> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| w.word.as_str()).collect();
+        assert_eq!(kept, vec!["blorf", "zzqx", "wuggle"]);
+        
+        checker.set_max_errors(None);
+        let analysis = checker.check_document(text);
+        assert!(!analysis.truncated);
+        assert_eq!(analysis.words.iter().filter(|w| !w.is_correct).count(), 6);
+    }
+    
+    #[test]
+    fn columns_count_characters_and_offsets_count_bytes() {
+        let (mut checker, _dir) = checker_with_words(&["the", "café"]);
+        checker.set_confidence_threshold(0.5);
+        let text = "The café blorf";
+        
+        let analysis = checker.check_document(text);
+        let error = analysis.words.iter().find(|w| !w.is_correct).unwrap();
+        assert_eq!(error.word, "blorf");
+        assert_eq!(error.column, 10);
+        assert_eq!((error.start, error.end), (10, 15));
+        assert_eq!(&text[error.start..error.end], "blorf");
+    }
+    
+    #[test]
+    fn case_sensitivity_follows_the_language_unless_overridden() {
+        let (mut checker, _dir) = checker_with_words(&["the"]);
+        checker.use_dictionary(Dictionary::from_words(Language::German, ["Haus".to_string()]));
+        assert!(checker.is_case_sensitive());
+        
+        checker.set_language(Language::German).unwrap();
+        assert!(!checker.is_case_sensitive());
+        assert!(checker.current_settings().case_sensitive.is_none());
+        
+        // An explicit choice outlasts language switches
+        checker.set_case_sensitive(true);
+        checker.set_language(Language::English).unwrap();
+        checker.set_language(Language::German).unwrap();
+        assert!(checker.is_case_sensitive());
+        assert_eq!(checker.current_settings().case_sensitive, Some(true));
+        
+        checker.use_language_case_default();
+        assert!(!checker.is_case_sensitive());
+    }
+    
+    #[test]
+    fn words_from_a_secondary_language_are_hints() {
+        let (mut checker, _dir) = checker_with_words(&["weekend"]);
+        checker.use_dictionary(Dictionary::from_words(Language::French, ["le".to_string(), "chat".to_string()]));
+        checker.set_language(Language::French).unwrap();
+        checker.set_confidence_threshold(0.5);
+        checker.set_secondary_languages(vec![Language::English]);
+        
+        let analysis = checker.check_document("Le chat weekend blorf");
+        let weekend = analysis.words.iter().find(|w| w.word == "weekend").unwrap();
+        assert_eq!(weekend.valid_in, Some(Language::English));
+        assert_eq!(weekend.severity, Severity::Hint);
+        assert_eq!(weekend.note().as_deref(), Some("valid in English"));
+        let blorf = analysis.words.iter().find(|w| w.word == "blorf").unwrap();
+        assert!(!blorf.is_correct);
+        assert!(blorf.valid_in.is_none());
+        
+        checker.set_secondary_languages(Vec::new());
+        let analysis = checker.check_document("Le chat weekend blorf");
+        assert!(analysis.words.iter().all(|w| w.valid_in.is_none()));
+    }
+    
+    #[test]
+    fn latin_runs_in_japanese_are_checked_in_english() {
+        let (mut checker, _dir) = checker_with_words(&["iPhone"]);
+        checker.use_dictionary(Dictionary::from_words(Language::Japanese, ["新しい", "を", "コーヒー"].map(String::from)));
+        checker.set_language(Language::Japanese).unwrap();
+        checker.set_confidence_threshold(0.5);
+        
+        let analysis = checker.check_document("新しいiPhone12を コーヒー");
+        let tokens: Vec<&str> = analysis.words.iter().map(|w| w.original.as_str()).collect();
+        // The long-vowel mark keeps "コーヒー" one kana token
+        assert_eq!(tokens, vec!["新しい", "iPhone12", "を", "コーヒー"]);
+        assert!(analysis.words.iter().all(|w| w.is_correct));
+        
+        let analysis = checker.check_document("新しいiPhoen12を");
+        let errors: Vec<&str> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| w.original.as_str()).collect();
+        assert_eq!(errors, vec!["iPhoen12"]);
+    }
+    
+    #[test]
+    fn constants_are_skipped_in_code_but_shouting_is_checked() {
+        let (mut checker, _dir) = checker_with_words(&["hello", "world"]);
+        assert_eq!(checker.explain("MAX_BUFFER_SIZE", true).skip_reason, Some("constant"));
+        
+        checker.set_skip_constants(false);
+        assert_eq!(checker.explain("MAX_BUFFER_SIZE", true).skip_reason, None);
+        
+        checker.set_skip_constants(true);
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("HELLO WROLD");
+        let errors: Vec<&str> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| w.original.as_str()).collect();
+        assert_eq!(errors, vec!["WROLD"]);
+    }
+    
+    #[test]
+    fn fix_all_skips_stale_and_overlapping_edits() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        let mut analysis = checker.check_document("The teh cta sat");
+        
+        // A second edit starting inside "teh" loses to the one before it
+        let mut overlapping = analysis.words.iter().find(|w| w.original == "teh").unwrap().clone();
+        overlapping.start += 1;
+        overlapping.original = "eh".to_string();
+        analysis.words.push(overlapping);
+        
+        // "cta" was corrected by hand since the check, so it's left alone
+        let edits = analysis.fix_all_edits("The teh cat sat", 0.0);
+        assert_eq!(edits, vec![(4, 7, "the".to_string())]);
+    }
+    
+    #[test]
+    fn comments_only_checks_rust_comments_but_not_code() {
+        let (mut checker, _dir) = checker_with_words(&["the", "data"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_comments_only(true);
+        
+        let analysis = checker.check_document_with_context("// recieve the data\nlet recieve = 1;\n", Some("main.rs"));
+        let errors: Vec<(usize, &str)> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| (w.line, w.original.as_str())).collect();
+        assert_eq!(errors, vec![(1, "recieve")]);
+    }
+    
+    #[test]
+    fn comments_only_checks_python_comments_but_not_code() {
+        let (mut checker, _dir) = checker_with_words(&["the", "data"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_comments_only(true);
+        
+        let analysis = checker.check_document_with_context("# recieve the data\nrecieve = 1\n", Some("main.py"));
+        let errors: Vec<(usize, &str)> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| (w.line, w.original.as_str())).collect();
+        assert_eq!(errors, vec![(1, "recieve")]);
+    }
+    
+    #[test]
+    fn repeated_suggestion_lookups_are_cached() {
+        struct CountingProvider(Arc<AtomicUsize>);
+        
+        impl SuggestionProvider for CountingProvider {
+            fn suggest(&self, _word: &str, _language: Language, _dictionary: &Dictionary) -> Vec<String> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                vec!["cat".to_string()]
+            }
+        }
+        
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        let calls = Arc::new(AtomicUsize::new(0));
+        checker.set_suggestion_provider(Box::new(CountingProvider(Arc::clone(&calls))));
+        
+        checker.check_document("The cta");
+        checker.check_document("The cta");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        
+        // Going over the limit clears the cache, so "cta" is looked up again
+        checker.set_max_cache_entries(2);
+        checker.check_document("The blorf zzyzx");
+        checker.check_document("The cta");
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+    
+    #[test]
+    fn errors_are_grouped_by_line() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("The cta blorf\nThe cat sat\nThe zzyzx sat");
+        
+        let lines = analysis.errors_by_line();
+        let grouped: Vec<(usize, Vec<&str>)> = lines
+            .iter()
+            .map(|(line, words)| (*line, words.iter().map(|w| w.original.as_str()).collect()))
+            .collect();
+        assert_eq!(grouped, vec![(1, vec!["cta", "blorf"]), (3, vec!["zzyzx"])]);
+        assert_eq!(analysis.error_count_on_line(2), 0);
+    }
+    
+    #[test]
+    fn range_checks_match_a_full_check() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        let text = "The cat sat\nThe cta blorf\nThe sat cat";
+        let line_two = text.find("The cta").unwrap();
+        let range_end = text.find("\nThe sat").unwrap();
+        
+        let full = checker.check_document(text);
+        let range = checker.check_range(text, line_two, range_end, None);
+        assert_eq!(range.words.len(), 3);
+        for word in &range.words {
+            let same = full.words.iter().find(|w| w.start == word.start).unwrap();
+            assert_eq!((word.line, word.column, word.end), (same.line, same.column, same.end));
+        }
+        
+        // Fixing "cta" and merging just that line matches checking it all again
+        let edited = text.replace("cta", "cat");
+        let mut merged = full.clone();
+        merged.merge_range(&edited, line_two, range_end, checker.check_range(&edited, line_two, range_end, None));
+        let recheck = checker.check_document(&edited);
+        assert_eq!(merged.words, recheck.words);
+        assert_eq!((merged.total_words, merged.misspelled_words), (recheck.total_words, recheck.misspelled_words));
+        assert_eq!(merged.accuracy, recheck.accuracy);
+    }
+    
+    #[test]
+    fn explain_reports_why_a_word_was_skipped() {
+        let (checker, _dir) = checker_with_words(&["the"]);
+        
+        let explanation = checker.explain("API", false);
+        assert_eq!(explanation.word_type, WordType::Acronym);
+        assert_eq!(explanation.skip_reason, Some("known acronym"));
+        assert!(explanation.is_correct && !explanation.flagged);
+        assert!(explanation.summary().contains("Skipped: known acronym"));
+        
+        let explanation = checker.explain("blorfing", false);
+        assert!(!explanation.skipped());
+        assert!(!explanation.in_dictionary);
+        assert!(!explanation.is_correct);
+    }
+    
+    #[test]
+    fn flagged_words_carry_their_line_as_context() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_capture_context(true);
+        
+        let analysis = checker.check_document("The cat sat\nThe blorf sat");
+        let error = analysis.words.iter().find(|w| !w.is_correct).unwrap();
+        let context = error.context.as_ref().unwrap();
+        assert!(context.text.contains("blorf"));
+        assert_eq!(&context.text[context.offset..context.offset + 5], "blorf");
+    }
+    
+    #[test]
+    fn added_acronyms_survive_a_reload() {
+        let (mut checker, dir) = checker_with_words(&["the"]);
+        assert!(checker.explain("ZXQW", false).skip_reason.is_none());
+        assert!(checker.add_acronym("zxqw").unwrap());
+        
+        let reopened = SpellChecker::with_data_dir(Language::English, dir.path()).unwrap();
+        assert!(reopened.acronyms().contains(&"ZXQW".to_string()));
+        assert_eq!(reopened.explain("ZXQW", false).skip_reason, Some("known acronym"));
+    }
+    
+    #[test]
+    fn cyrillic_letters_in_latin_words_are_flagged() {
+        let (mut checker, _dir) = checker_with_words(&["the", "hello"]);
+        checker.set_detect_mixed_script(true);
+        
+        // "h\u{435}llo" has a Cyrillic "е"
+        let analysis = checker.check_document("The h\u{435}llo");
+        let error = analysis.words.iter().find(|w| !w.is_correct).unwrap();
+        assert_eq!(error.kind, IssueKind::MixedScript);
+        assert_eq!(error.suggestions, vec!["hello".to_string()]);
+        
+        checker.set_detect_mixed_script(false);
+        assert_eq!(checker.check_document("The hello").misspelled_words, 0);
+    }
+    
+    #[test]
+    fn learned_words_become_correct() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("The blorf cat zzyzx blorf");
+        
+        assert_eq!(checker.learn_document(&analysis).unwrap(), 2);
+        assert_eq!(checker.check_document("The blorf cat zzyzx blorf").misspelled_words, 0);
+        assert_eq!(checker.user_word_count(), 2);
+    }
+    
+    #[test]
+    fn curly_apostrophes_match_straight_ones() {
+        let (mut checker, _dir) = checker_with_words(&["they", "don't", "know"]);
+        checker.set_confidence_threshold(0.5);
+        assert_eq!(checker.check_document("They don\u{2019}t know").misspelled_words, 0);
+        assert_eq!(checker.check_document("They don't know").misspelled_words, 0);
+    }
+    
+    #[test]
+    fn custom_word_pattern_keeps_middle_dots_in_words() {
+        let (mut checker, _dir) = checker_with_words(&["the", "col·lega"]);
+        checker.set_confidence_threshold(0.5);
+        assert!(checker.check_document("The col·lega").misspelled_words > 0);
+        
+        checker.set_word_pattern(Language::English, Some(r"\p{L}+(?:·\p{L}+)*")).unwrap();
+        assert_eq!(checker.check_document("The col·lega").misspelled_words, 0);
+        assert!(checker.set_word_pattern(Language::English, Some("(")).is_err());
+        
+        checker.set_word_pattern(Language::English, None).unwrap();
+        assert!(checker.check_document("The col·lega").misspelled_words > 0);
+    }
+    
+    #[test]
+    fn correction_map_lists_each_misspelling_once() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("The teh cat sat teh");
+        
+        let map = analysis.correction_map();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map["teh"].as_deref(), Some("the"));
+    }
+    
+    #[test]
+    fn wrongly_cased_words_suggest_the_dictionary_case() {
+        let (checker, _dir) = checker_with_words(&["the", "iPhone"]);
+        let check = checker.check_word("iphone", false);
+        assert!(!check.is_correct);
+        assert_eq!(check.suggestions.first().map(String::as_str), Some("iPhone"));
+    }
+    
+    #[test]
+    fn keyboard_neighbours_rank_first_when_keyboard_aware() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "cut"]);
+        checker.set_confidence_threshold(0.5);
+        checker.set_keyboard_aware(false);
+        assert_eq!(checker.check_word("cit", false).suggestions, vec!["cat", "cut"]);
+        
+        // "u" is next to "i"; "a" isn't
+        checker.set_keyboard_aware(true);
+        assert_eq!(checker.check_word("cit", false).suggestions, vec!["cut", "cat"]);
+    }
+    
+    #[test]
+    fn json_output_is_the_same_on_every_run() {
+        let words: Vec<String> = ["bat", "cab", "cad", "cam", "can", "cap", "car", "cat", "caw", "cot", "cut", "rat", "the"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let run = || {
+            let (mut checker, _dir) = checker_with_words(&[]);
+            checker.use_dictionary(Dictionary::from_words(Language::English, words.clone()));
+            checker.set_confidence_threshold(0.5);
+            checker.set_max_suggestions(4);
+            let mut analysis = checker.check_document("The caz sat on the cta");
+            analysis.check_duration_ms = 0;
+            serde_json::to_string(&analysis).unwrap()
+        };
+        assert_eq!(run(), run());
+    }
+    
+    #[test]
+    fn document_scoped_ignores_end_with_the_document() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        
+        checker.ignore_word_in_document("blorf");
+        assert_eq!(checker.check_document("The blorf cat").misspelled_words, 0);
+        assert_eq!(checker.ignored_word_count(), 0);
+        
+        checker.reset_document_scope();
+        assert_eq!(checker.check_document("The blorf cat").misspelled_words, 1);
+    }
+    
+    #[test]
+    fn severity_depends_on_the_word_type() {
+        assert_eq!(Severity::for_spelling(&WordType::Normal, 0.8), Severity::Error);
+        assert_eq!(Severity::for_spelling(&WordType::Normal, 0.6), Severity::Warning);
+        assert_eq!(Severity::for_spelling(&WordType::TechnicalTerm, 0.9), Severity::Error);
+        assert_eq!(Severity::for_spelling(&WordType::CodeIdentifier, 0.9), Severity::Warning);
+        assert_eq!(Severity::for_spelling(&WordType::RepeatedWord, 1.0), Severity::Warning);
+        assert_eq!(Severity::for_spelling(&WordType::ProperNoun, 0.9), Severity::Hint);
+        assert_eq!(Severity::for_spelling(&WordType::Acronym, 0.9), Severity::Hint);
+        assert_eq!(Severity::for_spelling(&WordType::Url, 0.9), Severity::Hint);
+    }
+    
+    #[test]
+    fn html_attribute_values_are_skipped_but_text_is_checked() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        
+        let html = r#"<p class="blorfy" data-x="zzyzx">The cta</p>"#;
+        let analysis = checker.check_document_with_context(html, Some("page.html"));
+        let errors: Vec<&str> = analysis.words.iter().filter(|w| !w.is_correct).map(|w| w.original.as_str()).collect();
+        assert_eq!(errors, vec!["cta"]);
+    }
+    
+    #[test]
+    fn higher_weights_raise_confidence() {
+        let (mut checker, _dir) = checker_with_words(&["the"]);
+        let check = checker.check_word("blorf", false);
+        assert!(check.raw_misspelled && check.is_correct);
+        
+        checker.set_confidence_weights(ConfidenceWeights { normal: 2.0, ..ConfidenceWeights::default() });
+        let check = checker.check_word("blorf", false);
+        assert_eq!(check.confidence, 1.0);
+        assert!(!check.is_correct);
+    }
+    
+    #[test]
+    fn only_the_named_markdown_section_is_checked() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat", "intro", "usage"]);
+        checker.set_confidence_threshold(0.5);
+        let text = "# Intro\nThe cta sat\n\n# Usage\nThe cat blorf\n";
+        
+        let section = checker.check_markdown_section(text, "usage").unwrap();
+        let errors: Vec<(usize, &str)> = section.words.iter().filter(|w| !w.is_correct).map(|w| (w.line, w.original.as_str())).collect();
+        assert_eq!(errors, vec![(5, "blorf")]);
+        
+        let section = checker.check_markdown_section(text, "Intro").unwrap();
+        let errors: Vec<&str> = section.words.iter().filter(|w| !w.is_correct).map(|w| w.original.as_str()).collect();
+        assert_eq!(errors, vec!["cta"]);
+        assert!(checker.check_markdown_section(text, "Missing").is_none());
+    }
+}
@@ -1,13 +1,13 @@
 #[cfg(feature = "cli")]
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 #[cfg(feature = "cli")]
 use colored::*;
 #[cfg(feature = "cli")]
 use indicatif::{ProgressBar, ProgressStyle};
 #[cfg(feature = "cli")]
-use spellchecker::{checker::SpellChecker, language::Language, util::*};
+use spellchecker::{checker::{DocumentAnalysis, SpellChecker, WordCheck}, diff::parse_unified_diff, dictionary::{Dictionary, DictionaryManager, FileSource}, language::Language, sarif::SarifLog, util::*};
 #[cfg(feature = "cli")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "cli")]
 #[derive(Parser)]
@@ -18,6 +18,14 @@ struct Cli {
     command: Commands,
 }
 
+#[cfg(feature = "cli")]
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
 #[cfg(feature = "cli")]
 #[derive(Subcommand)]
 enum Commands {
@@ -38,13 +46,85 @@ enum Commands {
         #[arg(long)]
         stats: bool,
         
-        /// Case sensitive checking
+        /// Case sensitive checking (the default for English)
         #[arg(short = 'c', long)]
         case_sensitive: bool,
         
+        /// Case insensitive checking, whatever the language's default
+        #[arg(long, conflicts_with = "case_sensitive")]
+        ignore_case: bool,
+        
         /// Output JSON format
         #[arg(long)]
         json: bool,
+        
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        
+        /// Check only the Markdown section under this heading
+        #[arg(long)]
+        section: Option<String>,
+    },
+    
+    /// Correct spelling errors in a file
+    Fix {
+        /// Input file to fix
+        file: PathBuf,
+        
+        /// Where to write the corrected file (default: overwrite input)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Language to use (eng, afr, fra, etc.)
+        #[arg(short, long, default_value = "eng")]
+        language: String,
+        
+        /// Prompt for each error instead of applying top suggestions
+        #[arg(short, long)]
+        interactive: bool,
+        
+        /// Minimum confidence for automatic corrections
+        #[arg(short, long, default_value_t = 0.8)]
+        threshold: f32,
+        
+        /// Print a unified diff instead of writing the file
+        #[arg(long)]
+        diff: bool,
+    },
+    
+    /// Check every matching file under a directory
+    Batch {
+        /// Directory to walk recursively
+        dir: PathBuf,
+        
+        /// Comma-separated file extensions to check
+        #[arg(short, long, value_delimiter = ',', default_value = "md,txt")]
+        ext: Vec<String>,
+        
+        /// Language to use (eng, afr, fra, etc.)
+        #[arg(short, long, default_value = "eng")]
+        language: String,
+        
+        /// Skip files and directories matching this glob
+        #[arg(long)]
+        ignore: Option<String>,
+        
+        /// Output suggestions
+        #[arg(short, long)]
+        suggest: bool,
+        
+        /// Output JSON format
+        #[arg(long)]
+        json: bool,
+        
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        
+        /// Exit with an error when the total error count exceeds this
+        #[arg(long)]
+        fail_on: Option<usize>,
     },
     
     /// Analyze word frequency
@@ -80,6 +160,29 @@ enum Commands {
         /// Minimum word length
         #[arg(short = 'm', long, default_value_t = 3)]
         min_length: usize,
+        
+        /// Print statistics about the created dictionary
+        #[arg(long)]
+        stats: bool,
+    },
+    
+    /// Check only the lines a unified diff adds, read from stdin
+    CheckDiff {
+        /// Diff the working tree against this git ref instead of reading stdin
+        #[arg(long)]
+        base: Option<String>,
+        
+        /// Language to use (eng, afr, fra, etc.)
+        #[arg(short, long, default_value = "eng")]
+        language: String,
+        
+        /// Output suggestions
+        #[arg(short, long)]
+        suggest: bool,
+        
+        /// Output JSON format
+        #[arg(long)]
+        json: bool,
     },
     
     /// Check spelling from stdin
@@ -97,6 +200,58 @@ enum Commands {
         json: bool,
     },
     
+    /// Print LSP diagnostics for text read from stdin
+    Lsp {
+        /// Language to use
+        #[arg(short, long, default_value = "eng")]
+        language: String,
+        
+        /// Name of the file being edited, used for code-aware checking
+        #[arg(short, long)]
+        filename: Option<String>,
+    },
+    
+    /// Serve a JSON API over HTTP: POST /check and POST /suggest
+    #[cfg(feature = "server")]
+    Serve {
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 7878)]
+        port: u16,
+        
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        
+        /// Language for requests that don't name one
+        #[arg(short, long, default_value = "eng")]
+        language: String,
+    },
+    
+    /// Show how a word is classified and why it is or isn't flagged
+    Explain {
+        /// Word to explain
+        word: String,
+        
+        /// Language to use
+        #[arg(short, long, default_value = "eng")]
+        language: String,
+        
+        /// Use the rules applied inside source code
+        #[arg(long)]
+        code: bool,
+        
+        /// Output JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// List supported languages and whether each has a dictionary installed
+    Languages {
+        /// Output JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    
     /// Interactive mode
     Interactive {
         /// Language to use
@@ -110,23 +265,39 @@ fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Check { file, language, suggest, stats, case_sensitive, json } => {
-            let content = std::fs::read_to_string(&file)?;
+        Commands::Check { file, language, suggest, stats, case_sensitive, ignore_case, json, format, section } => {
+            let format = if json { OutputFormat::Json } else { format };
+            let reader = std::io::BufReader::new(std::fs::File::open(&file)?);
             let language = Language::from_code(&language);
             
-            if !json {
+            if format == OutputFormat::Text {
                 println!("{}", format!("Checking '{}' in {}...", file.display(), language.name()).bold());
                 println!("{}", "-".repeat(50));
             }
             
             let mut checker = SpellChecker::new(language)?;
             checker.enable_suggestions(suggest);
-            checker.set_case_sensitive(case_sensitive);
+            if case_sensitive || ignore_case {
+                checker.set_case_sensitive(case_sensitive);
+            }
+            load_project_dictionary_for(&mut checker, &file)?;
             
-            let analysis = checker.check_document(&content, Some(&file.to_string_lossy()));
+            let analysis = match &section {
+                Some(heading) => {
+                    let content = std::fs::read_to_string(&file)?;
+                    match checker.check_markdown_section(&content, heading) {
+                        Some(analysis) => analysis,
+                        None => anyhow::bail!("No heading '{}' in {}", heading, file.display()),
+                    }
+                }
+                None => checker.check_reader(reader, Some(&file.to_string_lossy()), false),
+            };
             
-            if json {
+            if format == OutputFormat::Json {
                 println!("{}", serde_json::to_string_pretty(&analysis)?);
+            } else if format == OutputFormat::Sarif {
+                let log = SarifLog::from_analyses([(file.to_string_lossy().into_owned(), &analysis)]);
+                println!("{}", serde_json::to_string_pretty(&log)?);
             } else {
                 println!("\n{}", "Results:".bold().underline());
                 println!("  📊 Total words: {}", analysis.total_words);
@@ -137,18 +308,30 @@ fn main() -> anyhow::Result<()> {
                 
                 if analysis.misspelled_words > 0 {
                     println!("\n{}", "Errors found:".red().bold().underline());
-                    for word in analysis.words.iter().filter(|w| !w.is_correct) {
-                        println!("\n  Line {}: '{}'", word.line, word.word.red().bold());
-                        if suggest && !word.suggestions.is_empty() {
-                            println!("    💡 Suggestions: {}", word.suggestions.join(", ").green());
+                    for (line, words) in analysis.errors_by_line() {
+                        println!("\n  Line {}:", line);
+                        for word in words {
+                            println!("    '{}' (col {})", word.word.red().bold(), word.column);
+                            if suggest && !word.suggestions.is_empty() {
+                                println!("      💡 Suggestions: {}", word.suggestions.join(", ").green());
+                            }
                         }
                     }
                     println!("\n{}", format!("Total errors: {}", analysis.misspelled_words).red());
+                    
+                    let corrections: Vec<String> = analysis.correction_map()
+                        .into_iter()
+                        .filter_map(|(word, suggestion)| Some(format!("{} → {}", word, suggestion?)))
+                        .collect();
+                    if suggest && !corrections.is_empty() {
+                        println!("  💡 Did you mean: {}", corrections.join(", ").green());
+                    }
                 } else if analysis.total_words > 0 {
                     println!("\n{}", "✓ No spelling errors found!".green().bold());
                 }
                 
                 if stats {
+                    let content = std::fs::read_to_string(&file)?;
                     let reading_time = reading_time(&content);
                     let is_cjk = matches!(language, Language::Chinese | Language::Japanese | Language::Korean);
                     let is_code = is_code_file(&file.to_string_lossy());
@@ -167,6 +350,145 @@ fn main() -> anyhow::Result<()> {
             }
         }
         
+        Commands::Fix { file, output, language, interactive, threshold, diff } => {
+            use std::io::{self, Write};
+            
+            let content = std::fs::read_to_string(&file)?;
+            let language = Language::from_code(&language);
+            
+            let mut checker = SpellChecker::new(language)?;
+            checker.enable_suggestions(true);
+            load_project_dictionary_for(&mut checker, &file)?;
+            
+            let analysis = checker.check_document_with_context(&content, Some(&file.to_string_lossy()));
+            
+            let edits = if interactive {
+                let line_offsets = line_start_offsets(&content);
+                
+                collect_fix_edits(&analysis, |word| {
+                    let line_start = line_offsets[word.line - 1];
+                    let line_end = content[word.end..].find('\n').map_or(content.len(), |i| word.end + i);
+                    println!("\nLine {}: {}{}{}",
+                        word.line,
+                        &content[line_start..word.start],
+                        word.original.red().bold(),
+                        content[word.end..line_end].trim_end_matches('\r'));
+                    for (idx, suggestion) in word.suggestions.iter().enumerate() {
+                        println!("  {}) {}", idx + 1, word.replacement(suggestion).green());
+                    }
+                    print!("[number] accept, (r)eplace, (s)kip, (a)dd to dictionary, (q)uit: ");
+                    io::stdout().flush()?;
+                    
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input)?;
+                    let input = input.trim();
+                    
+                    let choice = match input {
+                        "q" => FixChoice::Quit,
+                        "a" => {
+                            checker.add_word_to_dictionary(&word.word)?;
+                            println!("Added '{}' to dictionary", word.word.green());
+                            FixChoice::Added
+                        }
+                        "r" => {
+                            print!("Replacement: ");
+                            io::stdout().flush()?;
+                            let mut replacement = String::new();
+                            io::stdin().read_line(&mut replacement)?;
+                            let replacement = replacement.trim();
+                            if replacement.is_empty() {
+                                FixChoice::Skip
+                            } else {
+                                FixChoice::Replace(replacement.to_string())
+                            }
+                        }
+                        _ => input.parse::<usize>().ok()
+                            .and_then(|n| n.checked_sub(1))
+                            .and_then(|n| word.suggestions.get(n))
+                            .map_or(FixChoice::Skip, |suggestion| FixChoice::Replace(word.replacement(suggestion))),
+                    };
+                    Ok(choice)
+                })?
+            } else {
+                analysis.fix_all_edits(&content, threshold)
+            };
+            
+            let fixed = apply_edits(&content, &edits);
+            
+            let summary = format!("✅ Made {} corrections ({} errors found)", edits.len(), analysis.misspelled_words);
+            
+            if diff {
+                // Keep stdout a clean patch
+                print!("{}", unified_diff(&content, &fixed, &file.to_string_lossy()));
+                eprintln!("{}", summary.bold());
+            } else {
+                if output.is_some() || !edits.is_empty() {
+                    let output = output.unwrap_or_else(|| file.clone());
+                    std::fs::write(&output, &fixed)?;
+                    println!("Wrote '{}'", output.display());
+                }
+                println!("{}", summary.bold());
+            }
+        }
+        
+        Commands::Batch { dir, ext, language, ignore, suggest, json, format, fail_on } => {
+            let format = if json { OutputFormat::Json } else { format };
+            let language = Language::from_code(&language);
+            let mut checker = SpellChecker::new(language)?;
+            checker.enable_suggestions(suggest);
+            
+            let extensions: Vec<String> = ext.iter()
+                .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+                .collect();
+            
+            let results = check_batch(&mut checker, &dir, &extensions, ignore.as_deref())?;
+            
+            let total_errors: usize = results.iter().map(|(_, a)| a.misspelled_words).sum();
+            let total_words: usize = results.iter().map(|(_, a)| a.total_words).sum();
+            
+            if format == OutputFormat::Sarif {
+                let log = SarifLog::from_analyses(results.iter()
+                    .map(|(path, analysis)| (path.to_string_lossy().into_owned(), analysis)));
+                println!("{}", serde_json::to_string_pretty(&log)?);
+            } else if format == OutputFormat::Json {
+                let files: Vec<_> = results.iter()
+                    .map(|(path, analysis)| serde_json::json!({
+                        "file": path.to_string_lossy(),
+                        "analysis": analysis,
+                    }))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&files)?);
+            } else {
+                println!("{}", format!("Checking {} files under '{}' in {}...", results.len(), dir.display(), language.name()).bold());
+                println!("{}", "-".repeat(50));
+                
+                for (path, analysis) in &results {
+                    if analysis.misspelled_words == 0 {
+                        println!("  {} {}", "✓".green(), path.display());
+                        continue;
+                    }
+                    
+                    println!("  {} {} ({} errors, {:.1}% accuracy)", "✗".red(), path.display(), analysis.misspelled_words, analysis.accuracy);
+                    for word in &analysis.words {
+                        print!("      {}:{} '{}'", word.line, word.column, word.word.red());
+                        if suggest && !word.suggestions.is_empty() {
+                            print!(" → {}", word.suggestions.join(", ").green());
+                        }
+                        println!();
+                    }
+                }
+                
+                println!("{}", "-".repeat(50));
+                println!("Files: {}, Words: {}, Errors: {}", results.len(), total_words, total_errors);
+            }
+            
+            if let Some(limit) = fail_on {
+                if total_errors > limit {
+                    anyhow::bail!("{} spelling errors exceed --fail-on {}", total_errors, limit);
+                }
+            }
+        }
+        
         Commands::Frequency { file, top, language, json } => {
             let content = std::fs::read_to_string(&file)?;
             let lang = Language::from_code(&language);
@@ -206,7 +528,7 @@ fn main() -> anyhow::Result<()> {
             }
         }
         
-        Commands::CreateDict { input, output, lang, min_length } => {
+        Commands::CreateDict { input, output, lang, min_length, stats } => {
             let content = std::fs::read_to_string(&input)?;
             let language = Language::from_code(&lang);
             let is_cjk = matches!(language, Language::Chinese | Language::Japanese | Language::Korean);
@@ -226,8 +548,8 @@ fn main() -> anyhow::Result<()> {
             );
             
             let mut dict_content = String::new();
-            for word in unique_words {
-                dict_content.push_str(&word);
+            for word in &unique_words {
+                dict_content.push_str(word);
                 dict_content.push('\n');
                 pb.inc(1);
             }
@@ -240,6 +562,119 @@ fn main() -> anyhow::Result<()> {
             println!("   Words: {}", unique_words.len());
             println!("   Source: {}", input.display());
             println!("   Min word length: {}", min_length);
+            
+            if stats {
+                let dict = Dictionary::from_source(language, &FileSource::new(output.clone()))?;
+                let stats = dict.stats();
+                println!("\n📏 Dictionary statistics:");
+                println!("   Loaded words: {}", stats.total_words);
+                println!("   Average length: {:.1} chars", stats.average_length);
+                println!("   With digits: {}", stats.with_digits);
+                println!("   With hyphens: {}", stats.with_hyphens);
+                println!("   With apostrophes: {}", stats.with_apostrophes);
+                println!("   Length histogram:");
+                for (length, count) in stats.length_histogram.iter().enumerate().filter(|(_, &c)| c > 0) {
+                    println!("   {:>4}: {}", length, count);
+                }
+            }
+        }
+        
+        Commands::CheckDiff { base, language, suggest, json } => {
+            let diff = match &base {
+                Some(base) => {
+                    let output = std::process::Command::new("git")
+                        .args(["diff", "--no-color", "--no-ext-diff", base])
+                        .output()?;
+                    if !output.status.success() {
+                        anyhow::bail!("git diff failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+                    }
+                    String::from_utf8(output.stdout)?
+                }
+                None => {
+                    use std::io::Read;
+                    let mut diff = String::new();
+                    std::io::stdin().read_to_string(&mut diff)?;
+                    diff
+                }
+            };
+            
+            let language = Language::from_code(&language);
+            let mut checker = SpellChecker::new(language)?;
+            checker.enable_suggestions(suggest);
+            
+            let mut results = Vec::new();
+            for file in parse_unified_diff(&diff) {
+                let path = Path::new(&file.path);
+                let content = std::fs::read_to_string(path).ok();
+                if content.is_some() {
+                    load_project_dictionary_for(&mut checker, path)?;
+                }
+                
+                // When the file on disk matches the diff's new version, check
+                // all of it so comments and sentences keep their context, and
+                // keep errors on added lines. Otherwise check each added line
+                // on its own and move it to its line number.
+                let matches_diff = content.as_deref().is_some_and(|content| {
+                    let lines: Vec<&str> = content.lines().collect();
+                    file.added.iter().all(|added| added.line.checked_sub(1).and_then(|i| lines.get(i)) == Some(&added.text.as_str()))
+                });
+                
+                let errors: Vec<_> = match content {
+                    Some(content) if matches_diff => {
+                        let added_lines: std::collections::HashSet<usize> = file.added.iter().map(|a| a.line).collect();
+                        checker.check_document_with_context(&content, Some(&file.path)).words
+                            .into_iter()
+                            .filter(|w| !w.is_correct && added_lines.contains(&w.line))
+                            .collect()
+                    }
+                    _ => file.added
+                        .iter()
+                        .flat_map(|added| {
+                            let analysis = checker.check_document_with_context(&added.text, Some(&file.path));
+                            analysis.words.into_iter().filter(|w| !w.is_correct).map(move |mut w| {
+                                w.line = added.line;
+                                w
+                            })
+                        })
+                        .collect(),
+                };
+                results.push((file, errors));
+            }
+            
+            if json {
+                let files: Vec<_> = results.iter()
+                    .map(|(file, errors)| serde_json::json!({
+                        "file": file.path,
+                        "lines_checked": file.added.len(),
+                        "errors": errors,
+                    }))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&files)?);
+            } else {
+                let lines: usize = results.iter().map(|(file, _)| file.added.len()).sum();
+                let total_errors: usize = results.iter().map(|(_, errors)| errors.len()).sum();
+                println!("{}", format!("Checking {} added lines in {} files in {}...", lines, results.len(), language.name()).bold());
+                println!("{}", "-".repeat(50));
+                
+                for (file, errors) in &results {
+                    if errors.is_empty() {
+                        println!("  {} {}", "✓".green(), file.path);
+                        continue;
+                    }
+                    
+                    println!("  {} {} ({} errors)", "✗".red(), file.path, errors.len());
+                    for word in errors {
+                        print!("      {}:{} '{}'", word.line, word.column, word.word.red());
+                        if suggest && !word.suggestions.is_empty() {
+                            print!(" → {}", word.suggestions.join(", ").green());
+                        }
+                        println!();
+                    }
+                }
+                
+                println!("{}", "-".repeat(50));
+                println!("Files: {}, Lines: {}, Errors: {}", results.len(), lines, total_errors);
+            }
         }
         
         Commands::Stdin { language, suggest, json } => {
@@ -257,7 +692,7 @@ fn main() -> anyhow::Result<()> {
             let mut checker = SpellChecker::new(language)?;
             checker.enable_suggestions(suggest);
             
-            let analysis = checker.check_document(&content, None);
+            let analysis = checker.check_document(&content);
             
             if json {
                 println!("{}", serde_json::to_string_pretty(&analysis)?);
@@ -282,6 +717,78 @@ fn main() -> anyhow::Result<()> {
             }
         }
         
+        Commands::Lsp { language, filename } => {
+            use std::io::{self, Read};
+            
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            
+            let language = Language::from_code(&language);
+            let checker = SpellChecker::new(language)?;
+            
+            let analysis = checker.check_document_with_context(&content, filename.as_deref());
+            println!("{}", serde_json::to_string(&analysis.to_lsp_diagnostics(&content))?);
+        }
+        
+        #[cfg(feature = "server")]
+        Commands::Serve { port, host, language } => {
+            use spellchecker::server::Server;
+            use std::sync::Arc;
+            
+            let server = Arc::new(Server::bind((host.as_str(), port), Language::from_code(&language))?);
+            let handle = Arc::clone(&server);
+            ctrlc::set_handler(move || handle.shutdown())?;
+            
+            if let Some(addr) = server.local_addr() {
+                println!("Listening on http://{} (Ctrl+C to stop)", addr);
+            }
+            server.run();
+            println!("Server stopped");
+        }
+        
+        Commands::Explain { word, language, code, json } => {
+            let language = Language::from_code(&language);
+            let checker = SpellChecker::new(language)?;
+            let explanation = checker.explain(&word, code);
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&explanation)?);
+            } else {
+                let verdict = if explanation.flagged {
+                    "flagged".red()
+                } else {
+                    "accepted".green()
+                };
+                println!("{} '{}' is {}", "Explain:".bold(), word, verdict);
+                for line in explanation.summary().lines() {
+                    println!("  {}", line);
+                }
+            }
+        }
+        
+        Commands::Languages { json } => {
+            let availability = DictionaryManager::new().language_availability();
+            
+            if json {
+                let languages: Vec<_> = availability
+                    .iter()
+                    .map(|(language, installed)| serde_json::json!({
+                        "code": language.code(),
+                        "name": language.name(),
+                        "dictionary": installed
+                    }))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&languages)?);
+            } else {
+                println!("{:<8} {:<20} {}", "Code".bold(), "Language".bold(), "Dictionary".bold());
+                println!("{}", "-".repeat(40));
+                for (language, installed) in &availability {
+                    let status = if *installed { "installed".green() } else { "missing".red() };
+                    println!("{:<8} {:<20} {}", language.code(), language.name(), status);
+                }
+            }
+        }
+        
         Commands::Interactive { language } => {
             use std::io::{self, Write};
             
@@ -293,6 +800,7 @@ fn main() -> anyhow::Result<()> {
             println!("Language: {}", language.name());
             println!("Type 'quit' or 'exit' to exit");
             println!("Type 'check <text>' to check text");
+            println!("Type 'word <word>' to check a single word");
             println!("Type 'add <word>' to add word to dictionary");
             println!("Type 'ignore <word>' to ignore word");
             println!("{}", "-".repeat(50));
@@ -313,9 +821,8 @@ fn main() -> anyhow::Result<()> {
                     break;
                 }
                 
-                if input.starts_with("check ") {
-                    let text = &input[6..];
-                    let analysis = checker.check_document(text, None);
+                if let Some(text) = input.strip_prefix("check ") {
+                    let analysis = checker.check_document(text);
                     
                     println!("Words: {}, Errors: {}, Accuracy: {:.1}%", 
                         analysis.total_words, analysis.misspelled_words, analysis.accuracy);
@@ -328,15 +835,23 @@ fn main() -> anyhow::Result<()> {
                             }
                         }
                     }
-                } else if input.starts_with("add ") {
-                    let word = &input[4..];
+                } else if let Some(word) = input.strip_prefix("word ") {
+                    let check = checker.check_word(word, false);
+                    if check.is_correct {
+                        println!("  '{}' is correct", check.original.green());
+                    } else {
+                        println!("  '{}' is misspelled ({:.0}% confidence)", check.original.red(), check.confidence * 100.0);
+                        if !check.suggestions.is_empty() {
+                            println!("    Suggestions: {}", check.suggestions.join(", ").green());
+                        }
+                    }
+                } else if let Some(word) = input.strip_prefix("add ") {
                     if let Err(e) = checker.add_word_to_dictionary(word) {
                         println!("Error: {}", e);
                     } else {
                         println!("Added '{}' to dictionary", word.green());
                     }
-                } else if input.starts_with("ignore ") {
-                    let word = &input[7..];
+                } else if let Some(word) = input.strip_prefix("ignore ") {
                     if let Err(e) = checker.ignore_word(word) {
                         println!("Error: {}", e);
                     } else {
@@ -352,9 +867,204 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Check every file under `dir` with one of `extensions` (lowercase, no
+/// dot), in file name order, skipping paths matching the `ignore` glob and
+/// files that aren't UTF-8 text. Only the flagged words are kept.
+#[cfg(feature = "cli")]
+fn check_batch(checker: &mut SpellChecker, dir: &Path, extensions: &[String], ignore: Option<&str>) -> anyhow::Result<Vec<(PathBuf, DocumentAnalysis)>> {
+    let walker = walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            match ignore {
+                Some(pattern) if entry.depth() > 0 => !glob_matches(pattern, &relative.to_string_lossy()),
+                _ => true,
+            }
+        });
+    
+    let mut results = Vec::new();
+    let mut project_dir: Option<PathBuf> = None;
+    for entry in walker.filter_map(Result::ok) {
+        let path = entry.path();
+        let matches_ext = path.extension()
+            .map(|e| extensions.contains(&e.to_string_lossy().to_lowercase()))
+            .unwrap_or(false);
+        if !entry.file_type().is_file() || !matches_ext {
+            continue;
+        }
+        
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                eprintln!("Skipping '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        if is_probably_binary(&bytes) {
+            eprintln!("Skipping '{}': not a text file", path.display());
+            continue;
+        }
+        let content = match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Skipping '{}': {}", path.display(), e);
+                continue;
+            }
+        };
+        
+        if project_dir.as_deref() != path.parent() {
+            load_project_dictionary_for(checker, path)?;
+            project_dir = path.parent().map(Path::to_path_buf);
+        }
+        
+        let mut analysis = checker.check_document_with_context(&content, Some(&path.to_string_lossy()));
+        analysis.words.retain(|w| !w.is_correct);
+        results.push((path.to_path_buf(), analysis));
+    }
+    
+    Ok(results)
+}
+
+/// What to do with one issue during an interactive fix
+#[cfg(feature = "cli")]
+enum FixChoice {
+    Replace(String),
+    Skip,
+    /// The word was added to the dictionary, so its later issues are skipped
+    Added,
+    Quit,
+}
+
+/// Collect the edits for the issues in `analysis` as `choose` decides them,
+/// sorted by position
+#[cfg(feature = "cli")]
+fn collect_fix_edits(
+    analysis: &DocumentAnalysis,
+    mut choose: impl FnMut(&WordCheck) -> anyhow::Result<FixChoice>,
+) -> anyhow::Result<Vec<(usize, usize, String)>> {
+    let mut added = std::collections::HashSet::new();
+    let mut edits = Vec::new();
+    
+    for word in analysis.words.iter().filter(|w| !w.is_correct) {
+        // A repeated-word issue spans both words, so once either
+        // side is fixed the other issue no longer applies
+        if added.contains(&word.word) || overlaps_edit(&edits, word.start, word.end) {
+            continue;
+        }
+        
+        match choose(word)? {
+            FixChoice::Replace(replacement) => edits.push((word.start, word.end, replacement)),
+            FixChoice::Skip => {}
+            FixChoice::Added => {
+                added.insert(word.word.clone());
+            }
+            FixChoice::Quit => break,
+        }
+    }
+    
+    edits.sort_by_key(|(start, _, _)| *start);
+    Ok(edits)
+}
+
+/// Whether bytes `start..end` overlap any of `edits`
+#[cfg(feature = "cli")]
+fn overlaps_edit(edits: &[(usize, usize, String)], start: usize, end: usize) -> bool {
+    edits.iter().any(|(edit_start, edit_end, _)| start < *edit_end && *edit_start < end)
+}
+
+/// Load the project dictionary nearest to `file`, searching from its
+/// absolute directory so ancestors of the working directory are included.
+/// Only the directory has to exist, so this works for files not yet saved
+#[cfg(feature = "cli")]
+fn load_project_dictionary_for(checker: &mut SpellChecker, file: &Path) -> anyhow::Result<()> {
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    checker.load_project_dictionary(&std::fs::canonicalize(dir)?)?;
+    Ok(())
+}
+
 #[cfg(not(feature = "cli"))]
 fn main() {
     println!("CLI feature not enabled. Build with --features cli");
     println!("Example: cargo build --features cli");
     println!("Or: cargo run --bin spellchecker_cli --features cli -- [args]");
-}
\ No newline at end of file
+}
+
+#[cfg(all(test, feature = "cli"))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    /// An English checker whose dictionary is only `words`, with its user
+    /// data in a fresh directory that is removed when the `TempDir` drops
+    fn checker_with_words(words: &[&str]) -> (SpellChecker, TempDir) {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("dictionary(eng).txt"), words.join("\n")).unwrap();
+        let checker = SpellChecker::with_data_dir(Language::English, dir.path()).unwrap();
+        (checker, dir)
+    }
+    
+    #[test]
+    fn batch_checks_each_matching_file_in_name_order() {
+        let (mut checker, _data) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        
+        let docs = TempDir::new().unwrap();
+        std::fs::write(docs.path().join("b.txt"), "The cat sat.").unwrap();
+        std::fs::write(docs.path().join("a.md"), "The dgo sat.").unwrap();
+        std::fs::write(docs.path().join("c.rs"), "blorf").unwrap();
+        
+        let extensions = vec!["md".to_string(), "txt".to_string()];
+        let results = check_batch(&mut checker, docs.path(), &extensions, None).unwrap();
+        
+        let names: Vec<_> = results.iter()
+            .map(|(path, _)| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["a.md", "b.txt"]);
+        let flagged: Vec<_> = results[0].1.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(flagged, ["dgo"]);
+        assert!(results[1].1.words.is_empty());
+    }
+    
+    #[test]
+    fn accepting_every_fix_skips_issues_that_overlap_an_earlier_one() {
+        let (mut checker, _data) = checker_with_words(&["allot", "alto"]);
+        checker.enable_suggestions(true);
+        checker.set_confidence_threshold(0.5);
+        
+        let content = "alot alot";
+        let analysis = checker.check_document(content);
+        
+        let edits = collect_fix_edits(&analysis, |word| {
+            Ok(word.suggestions.first()
+                .map_or(FixChoice::Skip, |suggestion| FixChoice::Replace(word.replacement(suggestion))))
+        }).unwrap();
+        
+        // The repeated-word issue spans both words and is never offered
+        assert_eq!(edits, vec![(0, 4, "allot".to_string()), (5, 9, "allot".to_string())]);
+        assert_eq!(apply_edits(content, &edits), "allot allot");
+    }
+    
+    #[test]
+    fn adding_a_word_skips_its_later_issues_and_quit_stops() {
+        let (mut checker, _data) = checker_with_words(&["the"]);
+        checker.set_confidence_threshold(0.5);
+        
+        let analysis = checker.check_document("blorf the blorf zarg qux");
+        let mut offered = Vec::new();
+        let edits = collect_fix_edits(&analysis, |word| {
+            offered.push(word.word.clone());
+            Ok(match word.word.as_str() {
+                "blorf" => FixChoice::Added,
+                "zarg" => FixChoice::Replace("zag".to_string()),
+                _ => FixChoice::Quit,
+            })
+        }).unwrap();
+        
+        assert_eq!(offered, ["blorf", "zarg", "qux"]);
+        assert_eq!(edits, vec![(16, 20, "zag".to_string())]);
+    }
+}
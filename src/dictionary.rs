@@ -1,39 +1,225 @@
 use crate::language::{Language, LanguageManager};
+use crate::util::normalize_punctuation;
 use dashmap::DashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+#[cfg(not(target_arch = "wasm32"))]
+use parking_lot::Mutex;
 use regex::Regex;
-use std::collections::HashSet;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+/// How long a dictionary file must be quiet before a watched change is
+/// reloaded, so editors that write in several steps trigger one reload
+#[cfg(not(target_arch = "wasm32"))]
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Compounds are split into at most this many dictionary words
+const MAX_COMPOUND_PARTS: usize = 3;
+/// Shorter parts would let almost any word split into fragments
+const MIN_COMPOUND_PART: usize = 3;
+/// Longer words aren't tried as compounds, which bounds the splitting work
+const MAX_COMPOUND_LENGTH: usize = 60;
+/// Linking elements (Fugen-s) allowed between compound parts
+const COMPOUND_LINKS: &[&str] = &["es", "s"];
+
+/// Where a dictionary's word list is read from. `FileSource` reads from disk;
+/// `BytesSource` holds bytes the caller already has, which is how targets
+/// without a filesystem (such as WebAssembly) supply a dictionary.
+pub trait DictionarySource {
+    /// Open the word list for reading
+    fn open(&self) -> anyhow::Result<Box<dyn BufRead + '_>>;
+    
+    /// Whether the list is a JSON object of word counts rather than lines
+    fn is_json(&self) -> bool;
+    
+    /// Whether the list is a Hunspell `.dic` file: a word count line, then
+    /// `stem/FLAGS` entries. Flags are dropped without affix expansion.
+    fn is_hunspell(&self) -> bool {
+        false
+    }
+}
+
+/// A word list file on disk. A `.json` extension selects the JSON format and
+/// `.dic` a Hunspell word list.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+    
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl DictionarySource for FileSource {
+    fn open(&self) -> anyhow::Result<Box<dyn BufRead + '_>> {
+        Ok(Box::new(BufReader::new(File::open(&self.path)?)))
+    }
+    
+    fn is_json(&self) -> bool {
+        self.path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    }
+    
+    fn is_hunspell(&self) -> bool {
+        self.path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("dic"))
+    }
+}
+
+/// A word list held in memory, in the same formats `FileSource` accepts
+#[derive(Debug, Clone)]
+pub struct BytesSource {
+    bytes: Vec<u8>,
+    json: bool,
+    hunspell: bool,
+}
+
+impl BytesSource {
+    /// A plain, CSV or tab-separated word list
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self { bytes: bytes.into(), json: false, hunspell: false }
+    }
+    
+    /// A JSON object mapping words to counts
+    pub fn json(bytes: impl Into<Vec<u8>>) -> Self {
+        Self { bytes: bytes.into(), json: true, hunspell: false }
+    }
+    
+    /// A Hunspell `.dic` word list
+    pub fn hunspell(bytes: impl Into<Vec<u8>>) -> Self {
+        Self { bytes: bytes.into(), json: false, hunspell: true }
+    }
+}
+
+impl DictionarySource for BytesSource {
+    fn open(&self) -> anyhow::Result<Box<dyn BufRead + '_>> {
+        Ok(Box::new(self.bytes.as_slice()))
+    }
+    
+    fn is_json(&self) -> bool {
+        self.json
+    }
+    
+    fn is_hunspell(&self) -> bool {
+        self.hunspell
+    }
+}
+
+/// Summary of a dictionary's word list, from `Dictionary::stats`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DictStats {
+    pub total_words: usize,
+    pub ignored_words: usize,
+    /// Number of words of each length in characters, indexed by length
+    pub length_histogram: Vec<usize>,
+    pub average_length: f32,
+    pub with_digits: usize,
+    pub with_hyphens: usize,
+    pub with_apostrophes: usize,
+}
 
 #[derive(Debug, Clone)]
 pub struct Dictionary {
     words: HashSet<String>,
+    user_added: HashSet<String>,
     ignored_words: HashSet<String>,
+    frequencies: HashMap<String, u64>,
+    max_frequency: u64,
+    /// The spelling of mixed-case entries ("iPhone", "English"), keyed by
+    /// normalized word. All-caps entries aren't kept: some word lists are
+    /// entirely uppercase.
+    canonical_case: HashMap<String, String>,
     word_pattern: Regex,
+    /// Whether `word_pattern` was set by `set_word_pattern` and should be
+    /// used to split prose into words
+    custom_word_pattern: bool,
     min_word_length: usize,
     language: Language,
     is_loaded: bool,
     word_count_cache: usize,
     ignored_count_cache: usize,
     file_path: Option<PathBuf>,
+    /// Where `load` looks for the dictionary file and the user word lists
+    data_dir: PathBuf,
 }
 
 impl Dictionary {
     pub fn new(language: Language) -> Self {
+        Self::with_data_dir(language, LanguageManager::system_dict_dir())
+    }
+    
+    /// Like `new`, but with user word lists kept under `data_dir` instead
+    /// of `LanguageManager::system_dict_dir`
+    pub fn with_data_dir(language: Language, data_dir: impl Into<PathBuf>) -> Self {
         let word_pattern = Self::get_word_pattern_for_language(&language);
         
         Self {
             words: HashSet::new(),
+            user_added: HashSet::new(),
             ignored_words: HashSet::new(),
+            frequencies: HashMap::new(),
+            max_frequency: 0,
+            canonical_case: HashMap::new(),
             word_pattern,
-            min_word_length: 2,
+            custom_word_pattern: false,
+            min_word_length: Self::default_min_word_length(&language),
             language,
             is_loaded: false,
             word_count_cache: 0,
             ignored_count_cache: 0,
             file_path: None,
+            data_dir: data_dir.into(),
+        }
+    }
+    
+    /// Build a dictionary from `source` alone, without reading the base
+    /// dictionary or user word lists from disk
+    pub fn from_source(language: Language, source: &dyn DictionarySource) -> anyhow::Result<Self> {
+        let mut dict = Self::new(language);
+        dict.load_from_source(source)?;
+        dict.is_loaded = true;
+        dict.ignored_count_cache = dict.ignored_words.len();
+        Ok(dict)
+    }
+    
+    /// Build a dictionary from an in-memory word list, e.g. for benchmarks
+    /// or tests. Words shorter than the language's minimum length are dropped.
+    /// Hand the result to `DictionaryManager::insert_dictionary` or
+    /// `SpellChecker::use_dictionary` to check against it.
+    pub fn from_words(language: Language, words: impl IntoIterator<Item = String>) -> Self {
+        let mut dict = Self::new(language);
+        for word in words {
+            let word = word.trim();
+            if !word.is_empty() && word.chars().count() >= dict.min_word_length {
+                dict.insert_base_word(word);
+            }
+        }
+        dict.word_count_cache = dict.words.len();
+        dict.ignored_count_cache = dict.ignored_words.len();
+        dict.is_loaded = true;
+        dict
+    }
+    
+    /// Single characters are valid words in CJK scripts; elsewhere they're
+    /// almost always initials or stray letters.
+    pub fn default_min_word_length(language: &Language) -> usize {
+        match language {
+            Language::Chinese | Language::Japanese | Language::Korean => 1,
+            _ => 2,
         }
     }
     
@@ -48,6 +234,12 @@ impl Dictionary {
             Language::Russian => {
                 Regex::new(r"[\p{Cyrillic}a-zA-Z0-9'-]+").unwrap()
             }
+            Language::Arabic => {
+                Regex::new(r"[\p{Arabic}\p{M}a-zA-Z0-9'-]+").unwrap()
+            }
+            Language::Hebrew => {
+                Regex::new(r"[\p{Hebrew}\p{M}a-zA-Z0-9'-]+").unwrap()
+            }
             _ => {
                 Regex::new(r"[\p{L}0-9'-]+").unwrap()
             }
@@ -59,12 +251,12 @@ impl Dictionary {
             return Ok(());
         }
         
-        let language_manager = LanguageManager::new();
+        let language_manager = LanguageManager::with_data_dir(&self.data_dir);
         
         // Try to load main dictionary
         if let Some(dict_path) = language_manager.get_dictionary_path(&self.language) {
             println!("Loading dictionary for {} from: {:?}", self.language.name(), dict_path);
-            self.load_file(&dict_path)?;
+            self.load_from_source(&FileSource::new(&dict_path))?;
             self.file_path = Some(dict_path);
         } else {
             println!("No dictionary file found for {}. Creating empty dictionary.", self.language.name());
@@ -87,61 +279,118 @@ impl Dictionary {
     }
     
     pub fn load_file(&mut self, path: &Path) -> anyhow::Result<()> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+        self.load_from_source(&FileSource::new(path))
+    }
+    
+    /// Load words from a plain list (one per line), a CSV or tab-separated
+    /// list whose optional second column is a usage count
+    /// (`the,23135851162`), a JSON object mapping words to counts, or a
+    /// Hunspell `.dic` file. Counts feed `frequency`.
+    pub fn load_from_source(&mut self, source: &dyn DictionarySource) -> anyhow::Result<()> {
+        let reader = source.open()?;
         
-        let mut new_words = HashSet::new();
+        let entries: Vec<(String, Option<u64>)> = if source.is_json() {
+            let counts: HashMap<String, u64> = serde_json::from_reader(reader)?;
+            counts.into_iter().map(|(word, count)| (word, Some(count))).collect()
+        } else if source.is_hunspell() {
+            // `.dic` files are often in a legacy encoding named by the `.aff`
+            // file; decode lossily rather than reject them
+            let mut entries = Vec::new();
+            for (index, line) in reader.split(b'\n').enumerate() {
+                let line = String::from_utf8_lossy(&line?).into_owned();
+                let line = line.trim_start_matches('\u{feff}').trim();
+                if index == 0 && line.parse::<usize>().is_ok() {
+                    continue;
+                }
+                if let Some(stem) = hunspell_stem(line) {
+                    entries.push((stem, None));
+                }
+            }
+            entries
+        } else {
+            let mut entries = Vec::new();
+            for line in reader.lines() {
+                let line = line?;
+                let line = line.trim_start_matches('\u{feff}');
+                let mut columns = line.split([',', '\t']).map(|c| c.trim().trim_matches('"'));
+                let word = columns.next().unwrap_or_default().to_string();
+                let count = columns.next().and_then(|c| c.parse().ok());
+                entries.push((word, count));
+            }
+            entries
+        };
         
-        for line in reader.lines() {
-            let line = line?;
-            let word = line.trim();
+        for (word, count) in entries {
+            let word = word.trim();
+            if word.is_empty() || word.chars().count() < self.min_word_length {
+                continue;
+            }
             
-            if !word.is_empty() && word.len() >= self.min_word_length {
-                let normalized = self.normalize_word(word);
-                new_words.insert(normalized);
+            let normalized = self.insert_base_word(word);
+            if let Some(count) = count {
+                let total = self.frequencies.entry(normalized).or_insert(0);
+                *total = total.saturating_add(count);
+                self.max_frequency = self.max_frequency.max(*total);
             }
         }
         
-        self.words.extend(new_words);
         self.word_count_cache = self.words.len();
         
         Ok(())
     }
     
+    /// Add a word from a word list, remembering its casing if it mixes
+    /// upper and lower case, and return its normalized form
+    fn insert_base_word(&mut self, word: &str) -> String {
+        let normalized = self.normalize_word(word);
+        if word.chars().any(char::is_uppercase) && word.chars().any(char::is_lowercase) {
+            self.canonical_case.insert(normalized.clone(), normalize_punctuation(word).into_owned());
+        }
+        self.words.insert(normalized.clone());
+        normalized
+    }
+    
+    /// How `word` is spelled in the word list if it was listed in mixed
+    /// case, e.g. "iPhone" for "iphone"
+    pub fn canonical_case(&self, word: &str) -> Option<&str> {
+        self.canonical_case.get(&self.normalize_word(word.trim())).map(String::as_str)
+    }
+    
     fn normalize_word(&self, word: &str) -> String {
+        let word = normalize_punctuation(word);
         match self.language {
             Language::Chinese | Language::Japanese | Language::Korean => {
-                word.to_string()
+                word.into_owned()
             }
             _ => {
-                word.to_lowercase()
+                self.language.lowercase(&word)
             }
         }
     }
     
     fn load_user_words(&mut self) {
-        let mut path = LanguageManager::user_dict_dir();
+        let mut path = LanguageManager::user_dict_dir(&self.data_dir);
         path.push(format!("user_{}.txt", self.language.code()));
         
         if let Ok(file) = File::open(&path) {
             let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(word) = line {
-                    let word = word.trim().to_string();
-                    if !word.is_empty() {
-                        self.words.insert(self.normalize_word(&word));
-                    }
+            for word in reader.lines().map_while(Result::ok) {
+                let word = word.trim().to_string();
+                if !word.is_empty() {
+                    let normalized = self.normalize_word(&word);
+                    self.words.insert(normalized.clone());
+                    self.user_added.insert(normalized);
                 }
             }
         }
     }
     
     fn save_user_words(&self) -> anyhow::Result<()> {
-        let mut path = LanguageManager::user_dict_dir();
+        let mut path = LanguageManager::user_dict_dir(&self.data_dir);
         path.push(format!("user_{}.txt", self.language.code()));
         
         let mut file = File::create(&path)?;
-        let mut sorted_words: Vec<&String> = self.words.iter().collect();
+        let mut sorted_words: Vec<&String> = self.user_added.iter().collect();
         sorted_words.sort();
         
         for word in sorted_words {
@@ -152,17 +401,15 @@ impl Dictionary {
     }
     
     fn load_ignored_words(&mut self) {
-        let mut path = LanguageManager::user_dict_dir();
+        let mut path = LanguageManager::user_dict_dir(&self.data_dir);
         path.push(format!("ignored_{}.txt", self.language.code()));
         
         if let Ok(file) = File::open(&path) {
             let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(word) = line {
-                    let word = word.trim().to_string();
-                    if !word.is_empty() {
-                        self.ignored_words.insert(self.normalize_word(&word));
-                    }
+            for word in reader.lines().map_while(Result::ok) {
+                let word = word.trim().to_string();
+                if !word.is_empty() {
+                    self.ignored_words.insert(self.normalize_word(&word));
                 }
             }
             self.ignored_count_cache = self.ignored_words.len();
@@ -170,7 +417,7 @@ impl Dictionary {
     }
     
     fn save_ignored_words(&self) -> anyhow::Result<()> {
-        let mut path = LanguageManager::user_dict_dir();
+        let mut path = LanguageManager::user_dict_dir(&self.data_dir);
         path.push(format!("ignored_{}.txt", self.language.code()));
         
         let mut file = File::create(&path)?;
@@ -185,9 +432,10 @@ impl Dictionary {
     }
     
     pub fn contains(&self, word: &str, case_sensitive: bool, is_code_context: bool) -> bool {
-        let word = word.trim();
+        let word = normalize_punctuation(word.trim());
+        let word = word.as_ref();
         
-        if word.is_empty() || word.len() < self.min_word_length {
+        if word.is_empty() || word.chars().count() < self.min_word_length {
             return true;
         }
         
@@ -202,30 +450,95 @@ impl Dictionary {
             return true;
         }
         
-        // Skip words with numbers (except in CJK)
-        if !matches!(self.language, Language::Chinese | Language::Japanese | Language::Korean) {
-            if word.chars().any(|c| c.is_ascii_digit()) && word.len() > 3 {
-                // Allow numbers in longer words (like "word123")
-                let letter_count = word.chars().filter(|c| c.is_alphabetic()).count();
-                if letter_count < 3 {
-                    return true;
-                }
-            }
-        }
-        
         // Check in dictionary
         match self.language {
+            // Matched exactly, with no case, compound or contraction rules.
+            // Latin words in CJK text are the checker's to look up
+            // elsewhere; see `SpellChecker::set_cjk_allow_mixed`.
             Language::Chinese | Language::Japanese | Language::Korean => {
                 self.words.contains(&normalized)
             }
             _ => {
-                if case_sensitive {
-                    self.words.contains(word)
+                let found = if case_sensitive {
+                    self.words.contains(&normalized) && self.case_matches(word, &normalized)
                 } else {
                     self.words.contains(&normalized)
+                };
+                found
+                    || (self.language.forms_compounds() && self.is_valid_compound(word))
+                    || self.is_contraction(word, case_sensitive)
+            }
+        }
+    }
+    
+    /// Whether `word` is written in a case its entry allows: a mixed-case
+    /// entry ("iPhone") as listed, anything else in lowercase or
+    /// capitalized. All caps fits every entry.
+    fn case_matches(&self, word: &str, normalized: &str) -> bool {
+        if !word.chars().any(char::is_lowercase) {
+            return true;
+        }
+        match self.canonical_case.get(normalized) {
+            Some(canonical) => canonical == word,
+            None => word.chars().skip(1).all(|c| !c.is_uppercase()),
+        }
+    }
+    
+    /// Whether `word` is a dictionary word followed by one of the
+    /// language's contraction suffixes, e.g. "children's" or "don't". Only
+    /// trailing suffixes are stripped, never leading or inner apostrophes.
+    fn is_contraction(&self, word: &str, case_sensitive: bool) -> bool {
+        self.language.contraction_suffixes().iter().any(|suffix| {
+            let Some(split) = word.len().checked_sub(suffix.len()) else {
+                return false;
+            };
+            if !word.is_char_boundary(split) || !word[split..].eq_ignore_ascii_case(suffix) {
+                return false;
+            }
+            
+            // Stems under the minimum word length ("I'm") pass the lookup
+            // the same way a short word does
+            let stem = &word[..split];
+            stem.chars().any(char::is_alphabetic)
+                && !stem.ends_with('\'')
+                && (self.contains(stem, case_sensitive, false)
+                    || self.language.short_contraction_stems().contains(&stem.to_lowercase().as_str()))
+        })
+    }
+    
+    /// Whether `word` is two or three dictionary words run together, as in
+    /// German "Haustür" (Haus + Tür), optionally joined by a linking "s" or
+    /// "es" ("Arbeitszimmer"). Longer prefixes are tried first.
+    pub fn is_valid_compound(&self, word: &str) -> bool {
+        let normalized = self.normalize_word(word.trim());
+        normalized.chars().count() <= MAX_COMPOUND_LENGTH
+            && self.splits_into_words(&normalized, MAX_COMPOUND_PARTS)
+    }
+    
+    fn splits_into_words(&self, word: &str, max_parts: usize) -> bool {
+        if max_parts < 2 {
+            return false;
+        }
+        
+        for (split, _) in word.char_indices().rev() {
+            let (head, tail) = word.split_at(split);
+            if head.chars().count() < MIN_COMPOUND_PART || !self.words.contains(head) {
+                continue;
+            }
+            
+            let tails = std::iter::once(tail)
+                .chain(COMPOUND_LINKS.iter().filter_map(|link| tail.strip_prefix(link)));
+            for rest in tails {
+                if rest.chars().count() < MIN_COMPOUND_PART {
+                    continue;
+                }
+                if self.words.contains(rest) || self.splits_into_words(rest, max_parts - 1) {
+                    return true;
                 }
             }
         }
+        
+        false
     }
     
     pub fn is_likely_code_identifier(&self, word: &str) -> bool {
@@ -259,23 +572,115 @@ impl Dictionary {
         &self.words
     }
     
+    /// Summarize the word list in a single pass, e.g. to spot a CSV header
+    /// or other junk that leaked in through an import
+    pub fn stats(&self) -> DictStats {
+        let mut stats = DictStats {
+            total_words: self.words.len(),
+            ignored_words: self.ignored_words.len(),
+            ..DictStats::default()
+        };
+        
+        let mut total_length = 0;
+        for word in &self.words {
+            let length = word.chars().count();
+            if stats.length_histogram.len() <= length {
+                stats.length_histogram.resize(length + 1, 0);
+            }
+            stats.length_histogram[length] += 1;
+            total_length += length;
+            
+            if word.chars().any(|c| c.is_numeric()) {
+                stats.with_digits += 1;
+            }
+            if word.contains('-') {
+                stats.with_hyphens += 1;
+            }
+            if word.contains(['\'', '’']) {
+                stats.with_apostrophes += 1;
+            }
+        }
+        
+        if stats.total_words > 0 {
+            stats.average_length = total_length as f32 / stats.total_words as f32;
+        }
+        stats
+    }
+    
+    /// Usage count for `word` from the loaded frequency data, if any
+    pub fn frequency(&self, word: &str) -> Option<u64> {
+        self.frequencies.get(&self.normalize_word(word)).copied()
+    }
+    
+    /// `frequency` scaled logarithmically to `0.0..=1.0` against the most
+    /// common word; 0.0 for unknown words or without frequency data
+    pub fn relative_frequency(&self, word: &str) -> f32 {
+        match self.frequency(word) {
+            Some(count) if self.max_frequency > 0 => {
+                ((count as f64).ln_1p() / (self.max_frequency as f64).ln_1p()) as f32
+            }
+            _ => 0.0,
+        }
+    }
+    
+    pub fn has_frequencies(&self) -> bool {
+        !self.frequencies.is_empty()
+    }
+    
+    pub fn get_user_words(&self) -> &HashSet<String> {
+        &self.user_added
+    }
+    
+    pub fn get_ignored_words(&self) -> &HashSet<String> {
+        &self.ignored_words
+    }
+    
     pub fn get_word_pattern(&self) -> &Regex {
         &self.word_pattern
     }
     
+    /// Split prose in this language into words with `pattern` instead of
+    /// the built-in word regex. Every match is checked as one word, so the
+    /// pattern must match whole words only: `\p{L}+(?:·\p{L}+)*` keeps
+    /// Catalan "paral·lel" together.
+    pub fn set_word_pattern(&mut self, pattern: Regex) {
+        self.word_pattern = pattern;
+        self.custom_word_pattern = true;
+    }
+    
+    /// The pattern from `set_word_pattern`, if any
+    pub fn custom_word_pattern(&self) -> Option<&Regex> {
+        self.custom_word_pattern.then_some(&self.word_pattern)
+    }
+    
     pub fn language(&self) -> &Language {
         &self.language
     }
     
+    /// The main dictionary file this was loaded from, if any
+    pub fn file_path(&self) -> Option<&Path> {
+        self.file_path.as_deref()
+    }
+    
     pub fn is_loaded(&self) -> bool {
         self.is_loaded
     }
     
+    pub fn min_word_length(&self) -> usize {
+        self.min_word_length
+    }
+    
+    /// Words shorter than this (in characters) are accepted without lookup.
+    pub fn set_min_word_length(&mut self, length: usize) {
+        self.min_word_length = length.max(1);
+    }
+    
     pub fn add_word(&mut self, word: &str) -> anyhow::Result<()> {
         let normalized = self.normalize_word(word.trim());
         
-        if !normalized.is_empty() && normalized.len() >= self.min_word_length {
+        if !normalized.is_empty() && normalized.chars().count() >= self.min_word_length {
             self.words.insert(normalized.clone());
+            self.user_added.insert(normalized.clone());
             self.word_count_cache = self.words.len();
             
             self.ignored_words.remove(&normalized);
@@ -287,6 +692,31 @@ impl Dictionary {
         Ok(())
     }
     
+    /// Add several user words, writing the user word list once. Returns how
+    /// many weren't user words already.
+    pub fn add_user_words<'a>(&mut self, words: impl IntoIterator<Item = &'a str>) -> anyhow::Result<usize> {
+        let mut added = 0;
+        for word in words {
+            let normalized = self.normalize_word(word.trim());
+            if normalized.is_empty() || normalized.chars().count() < self.min_word_length {
+                continue;
+            }
+            
+            self.words.insert(normalized.clone());
+            self.ignored_words.remove(&normalized);
+            if self.user_added.insert(normalized) {
+                added += 1;
+            }
+        }
+        
+        self.word_count_cache = self.words.len();
+        self.ignored_count_cache = self.ignored_words.len();
+        if added > 0 {
+            self.save_user_words()?;
+        }
+        Ok(added)
+    }
+    
     pub fn ignore_word(&mut self, word: &str) -> anyhow::Result<()> {
         let normalized = self.normalize_word(word.trim());
         
@@ -307,8 +737,32 @@ impl Dictionary {
         Ok(())
     }
     
+    /// Drop a word the user added and rewrite the user word list. Returns
+    /// false if `word` wasn't user-added.
+    pub fn remove_user_word(&mut self, word: &str) -> anyhow::Result<bool> {
+        let normalized = self.normalize_word(word.trim());
+        if !self.user_added.contains(&normalized) {
+            return Ok(false);
+        }
+        
+        self.remove_word(&normalized);
+        self.save_user_words()?;
+        Ok(true)
+    }
+    
+    /// Up to `limit` words containing `query` (case-insensitive), sorted
+    pub fn filtered_words(&self, query: &str, limit: usize) -> Vec<&String> {
+        filter_sorted(&self.words, query, limit)
+    }
+    
+    /// Like `filtered_words`, over the ignored words
+    pub fn filtered_ignored_words(&self, query: &str, limit: usize) -> Vec<&String> {
+        filter_sorted(&self.ignored_words, query, limit)
+    }
+    
     pub fn remove_word(&mut self, word: &str) -> bool {
         let removed = self.words.remove(word);
+        self.user_added.remove(word);
         if removed {
             self.word_count_cache = self.words.len();
         }
@@ -316,12 +770,41 @@ impl Dictionary {
     }
     
     pub fn save_to_file(&self, path: &Path) -> anyhow::Result<()> {
+        self.write_word_list(path, &self.words)
+    }
+    
+    /// Write only the words the user added, leaving out the base dictionary
+    pub fn export_user_words(&self, path: &Path) -> anyhow::Result<()> {
+        self.write_word_list(path, &self.user_added)
+    }
+    
+    /// Write `words` sorted, one per line. A `.csv` path gets `word,count`
+    /// rows for words with a known frequency so `load_file` can read it back.
+    /// A `.dic` path gets a Hunspell word list: the word count, then one
+    /// stem per line in its listed case, with no affix flags.
+    fn write_word_list(&self, path: &Path, words: &HashSet<String>) -> anyhow::Result<()> {
+        let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+        let is_csv = extension.as_deref() == Some("csv");
+        let is_hunspell = extension.as_deref() == Some("dic");
+        
         let mut file = File::create(path)?;
-        let mut sorted_words: Vec<&String> = self.words.iter().collect();
+        let mut sorted_words: Vec<&String> = words.iter().collect();
         sorted_words.sort();
         
+        if is_hunspell {
+            writeln!(file, "{}", sorted_words.len())?;
+        }
+        
         for word in sorted_words {
-            writeln!(file, "{}", word)?;
+            match self.frequency(word) {
+                // A bare `/` would start the affix flags
+                _ if is_hunspell => {
+                    let stem = self.canonical_case(word).unwrap_or(word);
+                    writeln!(file, "{}", stem.replace('/', "\\/"))?;
+                }
+                Some(count) if is_csv => writeln!(file, "{},{}", word, count)?,
+                _ => writeln!(file, "{}", word)?,
+            }
         }
         
         Ok(())
@@ -336,10 +819,56 @@ impl Dictionary {
     }
 }
 
+/// The bare word of a Hunspell `.dic` entry: `stem/FLAGS` followed by
+/// optional whitespace-separated morphological fields. `\/` is a literal
+/// slash in the stem.
+fn hunspell_stem(entry: &str) -> Option<String> {
+    let entry = entry.split([' ', '\t']).next().unwrap_or_default();
+    
+    let mut stem = String::new();
+    let mut chars = entry.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.as_str().starts_with('/') => {
+                stem.push('/');
+                chars.next();
+            }
+            '/' => break,
+            c => stem.push(c),
+        }
+    }
+    
+    (!stem.is_empty()).then_some(stem)
+}
+
+/// How an imported word list combines with the dictionary already loaded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Add the imported words to the existing base and user words
+    #[default]
+    Merge,
+    /// Discard the existing dictionary and use only the imported words
+    Replace,
+}
+
+/// Progress of a language's dictionary, from `DictionaryManager::loading_state`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadState {
+    NotLoaded,
+    Loading,
+    Loaded,
+    /// The last load failed with this error
+    Failed(String),
+}
+
 #[derive(Clone)]
 pub struct DictionaryManager {
     dictionaries: Arc<DashMap<Language, Dictionary>>,
+    /// Loads in progress or failed; loaded languages are in `dictionaries`
+    load_states: Arc<DashMap<Language, LoadState>>,
     language_manager: LanguageManager,
+    #[cfg(not(target_arch = "wasm32"))]
+    watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
 }
 
 impl Default for DictionaryManager {
@@ -350,38 +879,251 @@ impl Default for DictionaryManager {
 
 impl DictionaryManager {
     pub fn new() -> Self {
-        let manager = LanguageManager::new();
+        Self::with_data_dir(LanguageManager::system_dict_dir())
+    }
+    
+    /// Like `new`, but with dictionaries and user word lists looked for
+    /// under `data_dir` instead of `LanguageManager::system_dict_dir`
+    pub fn with_data_dir(data_dir: impl Into<PathBuf>) -> Self {
+        let manager = LanguageManager::with_data_dir(data_dir);
         let dictionaries = Arc::new(DashMap::new());
         
         Self {
             dictionaries,
+            load_states: Arc::new(DashMap::new()),
             language_manager: manager,
+            #[cfg(not(target_arch = "wasm32"))]
+            watcher: Arc::new(Mutex::new(None)),
         }
     }
     
+    pub fn data_dir(&self) -> &Path {
+        self.language_manager.data_dir()
+    }
+    
+    fn new_dictionary(&self, language: Language) -> Dictionary {
+        Dictionary::with_data_dir(language, self.data_dir())
+    }
+    
     pub fn get_dictionary(&self, language: &Language) -> anyhow::Result<Dictionary> {
         if let Some(dict) = self.dictionaries.get(language) {
             return Ok(dict.clone());
         }
         
-        let mut dict = Dictionary::new(*language);
+        // Don't read the file a second time while a background load runs
+        if matches!(self.load_states.get(language).as_deref(), Some(LoadState::Loading)) {
+            anyhow::bail!("Dictionary for {} is still loading", language.name());
+        }
+        
+        let mut dict = self.new_dictionary(*language);
         dict.load()?;
         self.dictionaries.insert(*language, dict.clone());
+        self.load_states.remove(language);
         
         Ok(dict)
     }
     
+    /// Run `f` on `language`'s dictionary, loading it the first time. The
+    /// dictionary is borrowed in place rather than copied the way
+    /// `get_dictionary` does. It stays locked while `f` runs, so `f` must
+    /// not load another dictionary.
+    pub fn with_dictionary<R>(&self, language: &Language, f: impl FnOnce(&Dictionary) -> R) -> anyhow::Result<R> {
+        if !self.dictionaries.contains_key(language) {
+            self.get_dictionary(language)?;
+        }
+        match self.dictionaries.get(language) {
+            Some(dict) => Ok(f(&dict)),
+            None => anyhow::bail!("Dictionary for {} is not loaded", language.name()),
+        }
+    }
+    
+    /// Whether `language`'s dictionary has `word`, loading the dictionary
+    /// the first time
+    pub fn dictionary_contains(&self, language: &Language, word: &str, case_sensitive: bool) -> bool {
+        self.with_dictionary(language, |dict| dict.contains(word, case_sensitive, false))
+            .unwrap_or(false)
+    }
+    
+    pub fn loading_state(&self, language: &Language) -> LoadState {
+        if self.dictionaries.contains_key(language) {
+            return LoadState::Loaded;
+        }
+        self.load_states
+            .get(language)
+            .map(|state| state.clone())
+            .unwrap_or(LoadState::NotLoaded)
+    }
+    
+    /// Load `language`'s dictionary on a background thread, unless it is
+    /// loaded or already loading. Until the load finishes, `get_dictionary`
+    /// fails for that language instead of blocking. On wasm32, which has
+    /// no threads, the load finishes before this returns.
+    pub fn load_in_background(&self, language: Language) {
+        let mut dict = self.new_dictionary(language);
+        self.load_in_background_with(language, move || {
+            dict.load()?;
+            Ok(dict)
+        });
+    }
+    
+    /// Like `load_in_background`, but build the dictionary with `load`,
+    /// e.g. from a `DictionarySource`
+    pub fn load_in_background_with<F>(&self, language: Language, load: F)
+    where
+        F: FnOnce() -> anyhow::Result<Dictionary> + Send + 'static,
+    {
+        if self.dictionaries.contains_key(&language) {
+            return;
+        }
+        
+        match self.load_states.entry(language) {
+            dashmap::mapref::entry::Entry::Occupied(mut entry) => {
+                if *entry.get() == LoadState::Loading {
+                    return;
+                }
+                entry.insert(LoadState::Loading);
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                entry.insert(LoadState::Loading);
+            }
+        }
+        
+        let dictionaries = Arc::clone(&self.dictionaries);
+        let load_states = Arc::clone(&self.load_states);
+        let finish = move || match load() {
+            Ok(dict) => {
+                // A dictionary inserted meanwhile, e.g. by `insert_dictionary`, wins
+                dictionaries.entry(language).or_insert(dict);
+                load_states.remove(&language);
+            }
+            Err(e) => {
+                load_states.insert(language, LoadState::Failed(e.to_string()));
+            }
+        };
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(finish);
+        #[cfg(target_arch = "wasm32")]
+        finish();
+    }
+    
+    /// Use `dict` for `language`, replacing any dictionary already loaded.
+    /// Words added to it are saved under this manager's data directory.
+    pub fn insert_dictionary(&mut self, language: Language, mut dict: Dictionary) {
+        dict.data_dir = self.data_dir().to_path_buf();
+        self.dictionaries.insert(language, dict);
+    }
+    
     pub fn reload_dictionary(&mut self, language: &Language) -> anyhow::Result<()> {
-        let mut dict = Dictionary::new(*language);
-        dict.load()?;
-        self.dictionaries.insert(*language, dict);
-        Ok(())
+        reload_into(&self.dictionaries, *language)
+    }
+    
+    /// Watch the dictionary files of all available languages and reload a
+    /// cached dictionary when its file changes on disk, including when it
+    /// is replaced by a rename. Each reloaded language is sent on the
+    /// returned channel. Watching stops on `unwatch` or when a new watch
+    /// replaces this one. Not supported on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn watch(&self) -> anyhow::Result<Receiver<Language>> {
+        // Watch parent directories rather than the files themselves so an
+        // atomic rename over the file is still seen
+        let mut watched_files = HashMap::new();
+        let mut watched_dirs = HashSet::new();
+        for language in self.language_manager.available_languages() {
+            let Some(path) = self.language_manager.get_dictionary_path(language) else {
+                continue;
+            };
+            if let Some(key) = watch_key(&path) {
+                if let Some(dir) = key.parent() {
+                    watched_dirs.insert(dir.to_path_buf());
+                }
+                watched_files.insert(key, *language);
+            }
+        }
+        
+        let (event_tx, event_rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let _ = event_tx.send(event);
+            }
+        })?;
+        
+        for dir in &watched_dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        
+        let (reload_tx, reload_rx) = mpsc::channel();
+        let dictionaries = Arc::clone(&self.dictionaries);
+        
+        std::thread::spawn(move || {
+            let mut pending: HashMap<Language, Instant> = HashMap::new();
+            
+            loop {
+                match event_rx.recv_timeout(WATCH_DEBOUNCE / 5) {
+                    Ok(event) => {
+                        if matches!(event.kind, EventKind::Access(_)) {
+                            continue;
+                        }
+                        for path in &event.paths {
+                            if let Some(language) = watch_key(path).and_then(|key| watched_files.get(&key)) {
+                                pending.insert(*language, Instant::now());
+                            }
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    // The watcher was dropped
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                
+                let ready: Vec<Language> = pending.iter()
+                    .filter(|(_, changed)| changed.elapsed() >= WATCH_DEBOUNCE)
+                    .map(|(language, _)| *language)
+                    .collect();
+                
+                for language in ready {
+                    pending.remove(&language);
+                    if !dictionaries.contains_key(&language) {
+                        continue;
+                    }
+                    match reload_into(&dictionaries, language) {
+                        Ok(()) => {
+                            let _ = reload_tx.send(language);
+                        }
+                        Err(e) => eprintln!("Warning: Could not reload dictionary for {}: {}", language.name(), e),
+                    }
+                }
+            }
+        });
+        
+        *self.watcher.lock() = Some(watcher);
+        Ok(reload_rx)
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    pub fn watch(&self) -> anyhow::Result<Receiver<Language>> {
+        anyhow::bail!("Watching dictionary files isn't supported on this platform")
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn unwatch(&self) {
+        *self.watcher.lock() = None;
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    pub fn unwatch(&self) {}
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_watching(&self) -> bool {
+        self.watcher.lock().is_some()
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    pub fn is_watching(&self) -> bool {
+        false
     }
     
     pub fn add_custom_dictionary(&mut self, path: PathBuf, language: Language) -> anyhow::Result<()> {
-        let mut dict = Dictionary::new(language);
-        dict.import_from_file(&path)?;
-        self.dictionaries.insert(language, dict);
+        self.import_dictionary(path, language, ImportMode::Merge)?;
         Ok(())
     }
     
@@ -389,7 +1131,7 @@ impl DictionaryManager {
         if let Some(mut dict) = self.dictionaries.get_mut(&language) {
             dict.add_word(word)
         } else {
-            let mut dict = Dictionary::new(language);
+            let mut dict = self.new_dictionary(language);
             dict.load()?;
             dict.add_word(word)?;
             self.dictionaries.insert(language, dict);
@@ -397,11 +1139,30 @@ impl DictionaryManager {
         }
     }
     
+    pub fn add_user_words<'a>(&mut self, words: impl IntoIterator<Item = &'a str>, language: Language) -> anyhow::Result<usize> {
+        if let Some(mut dict) = self.dictionaries.get_mut(&language) {
+            dict.add_user_words(words)
+        } else {
+            let mut dict = self.new_dictionary(language);
+            dict.load()?;
+            let added = dict.add_user_words(words)?;
+            self.dictionaries.insert(language, dict);
+            Ok(added)
+        }
+    }
+    
+    pub fn remove_user_word(&mut self, word: &str, language: Language) -> anyhow::Result<bool> {
+        match self.dictionaries.get_mut(&language) {
+            Some(mut dict) => dict.remove_user_word(word),
+            None => Ok(false),
+        }
+    }
+    
     pub fn ignore_word(&mut self, word: &str, language: Language) -> anyhow::Result<()> {
         if let Some(mut dict) = self.dictionaries.get_mut(&language) {
             dict.ignore_word(word)
         } else {
-            let mut dict = Dictionary::new(language);
+            let mut dict = self.new_dictionary(language);
             dict.load()?;
             dict.ignore_word(word)?;
             self.dictionaries.insert(language, dict);
@@ -413,7 +1174,7 @@ impl DictionaryManager {
         if let Some(mut dict) = self.dictionaries.get_mut(&language) {
             dict.clear_ignored_words()
         } else {
-            let mut dict = Dictionary::new(language);
+            let mut dict = self.new_dictionary(language);
             dict.load()?;
             dict.clear_ignored_words()?;
             self.dictionaries.insert(language, dict);
@@ -421,8 +1182,56 @@ impl DictionaryManager {
         }
     }
     
-    pub fn import_dictionary(&mut self, path: PathBuf, language: Language) -> anyhow::Result<()> {
-        self.add_custom_dictionary(path, language)
+    pub fn set_min_word_length(&mut self, language: Language, length: usize) -> anyhow::Result<()> {
+        if let Some(mut dict) = self.dictionaries.get_mut(&language) {
+            dict.set_min_word_length(length);
+        } else {
+            let mut dict = self.new_dictionary(language);
+            dict.set_min_word_length(length);
+            dict.load()?;
+            self.dictionaries.insert(language, dict);
+        }
+        Ok(())
+    }
+    
+    pub fn get_min_word_length(&self, language: &Language) -> usize {
+        self.dictionaries
+            .get(language)
+            .map(|d| d.min_word_length())
+            .unwrap_or_else(|| Dictionary::default_min_word_length(language))
+    }
+    
+    /// Import a word list for `language`. Returns how many words the
+    /// dictionary gained (for `Replace`, relative to the one it replaced).
+    pub fn import_dictionary(&mut self, path: PathBuf, language: Language, mode: ImportMode) -> anyhow::Result<usize> {
+        if mode == ImportMode::Merge {
+            if let Some(mut dict) = self.dictionaries.get_mut(&language) {
+                let previous_count = dict.word_count();
+                dict.import_from_file(&path)?;
+                return Ok(dict.word_count().saturating_sub(previous_count));
+            }
+        }
+        
+        let mut dict = self.new_dictionary(language);
+        let previous_count = match mode {
+            ImportMode::Merge => {
+                dict.load()?;
+                dict.word_count()
+            }
+            // The saved user and ignored words outlive the base word list;
+            // without them the next added word would overwrite their files
+            ImportMode::Replace => {
+                dict.load_user_words();
+                dict.load_ignored_words();
+                self.dictionaries.get(&language).map_or(0, |existing| existing.word_count())
+            }
+        };
+        
+        // Until the import succeeds the old dictionary stays in place
+        dict.import_from_file(&path)?;
+        let added = dict.word_count().saturating_sub(previous_count);
+        self.dictionaries.insert(language, dict);
+        Ok(added)
     }
     
     pub fn export_dictionary(&self, language: &Language, path: &Path) -> anyhow::Result<()> {
@@ -430,10 +1239,26 @@ impl DictionaryManager {
         dict.export_to_file(path)
     }
     
+    pub fn export_user_words(&self, language: &Language, path: &Path) -> anyhow::Result<()> {
+        let dict = self.get_dictionary(language)?;
+        dict.export_user_words(path)
+    }
+    
     pub fn get_available_languages(&self) -> Vec<Language> {
         self.language_manager.available_languages().to_vec()
     }
     
+    /// Every supported language and whether a dictionary file was found for
+    /// it. Languages without one still load, but with an empty word list.
+    pub fn language_availability(&self) -> Vec<(Language, bool)> {
+        self.language_manager
+            .available_languages()
+            .iter()
+            .filter(|language| **language != Language::AutoDetect)
+            .map(|language| (*language, self.language_manager.get_dictionary_path(language).is_some()))
+            .collect()
+    }
+    
     pub fn detect_language(&self, text: &str) -> Language {
         self.language_manager.detect_language(text)
     }
@@ -449,4 +1274,286 @@ impl DictionaryManager {
     pub fn get_cached_dictionary(&self, language: &Language) -> Option<Dictionary> {
         self.dictionaries.get(language).map(|d| d.value().clone())
     }
-}
\ No newline at end of file
+}
+
+/// Load a fresh copy of `language`'s dictionary into `dictionaries`,
+/// keeping any configured minimum word length and data directory
+fn reload_into(dictionaries: &DashMap<Language, Dictionary>, language: Language) -> anyhow::Result<()> {
+    let mut dict = Dictionary::new(language);
+    if let Some(existing) = dictionaries.get(&language) {
+        dict.data_dir = existing.data_dir.clone();
+        dict.set_min_word_length(existing.min_word_length());
+    }
+    dict.load()?;
+    dictionaries.insert(language, dict);
+    Ok(())
+}
+
+/// Normalise a dictionary path for matching watcher events: the canonical
+/// parent directory joined with the file name, which still works after the
+/// file itself has been removed or renamed
+#[cfg(not(target_arch = "wasm32"))]
+fn watch_key(path: &Path) -> Option<PathBuf> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Some(parent.canonicalize().ok()?.join(path.file_name()?))
+}
+
+fn filter_sorted<'a>(words: &'a HashSet<String>, query: &str, limit: usize) -> Vec<&'a String> {
+    let query = query.trim().to_lowercase();
+    let mut matches: Vec<&String> = words
+        .iter()
+        .filter(|word| query.is_empty() || word.to_lowercase().contains(&query))
+        .collect();
+    matches.sort();
+    matches.truncate(limit);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+    
+    /// A manager whose English dictionary file holds only `list`, in a
+    /// fresh directory that is removed when the `TempDir` drops
+    fn manager_with_words(list: &[&str]) -> (DictionaryManager, TempDir) {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("dictionary(eng).txt"), list.join("\n")).unwrap();
+        let manager = DictionaryManager::with_data_dir(dir.path());
+        (manager, dir)
+    }
+    
+    #[test]
+    fn single_han_character_is_checked_with_min_length_one() {
+        let dict = Dictionary::from_words(Language::Chinese, words(&["水", "中国"]));
+        assert_eq!(dict.min_word_length(), 1);
+        assert!(dict.contains("水", false, false));
+        assert!(!dict.contains("火", false, false));
+        
+        // Under a longer minimum the same character is accepted unchecked
+        let mut dict = dict;
+        dict.set_min_word_length(2);
+        assert!(dict.contains("火", false, false));
+    }
+    
+    #[test]
+    fn filtered_words_match_case_insensitively_in_order() {
+        let dict = Dictionary::from_words(Language::English, words(&["cherry", "apple", "pineapple", "grape"]));
+        assert_eq!(dict.filtered_words("APP", 10), vec!["apple", "pineapple"]);
+        assert_eq!(dict.filtered_words("", 2), vec!["apple", "cherry"]);
+        assert!(dict.filtered_words("kiwi", 10).is_empty());
+    }
+    
+    #[test]
+    fn merge_import_keeps_the_union() {
+        let (mut manager, dir) = manager_with_words(&["apple", "banana", "date"]);
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "apple\nbanana\ncherry\n").unwrap();
+        
+        manager.get_dictionary(&Language::English).unwrap();
+        assert_eq!(manager.import_dictionary(path, Language::English, ImportMode::Merge).unwrap(), 1);
+        
+        let dict = manager.get_dictionary(&Language::English).unwrap();
+        let mut merged: Vec<&String> = dict.get_words().iter().collect();
+        merged.sort();
+        assert_eq!(merged, vec!["apple", "banana", "cherry", "date"]);
+    }
+    
+    #[test]
+    fn failed_replace_import_keeps_the_old_dictionary() {
+        let (mut manager, dir) = manager_with_words(&["apple", "banana"]);
+        manager.get_dictionary(&Language::English).unwrap();
+        let missing = dir.path().join("missing.txt");
+        assert!(manager.import_dictionary(missing, Language::English, ImportMode::Replace).is_err());
+        assert_eq!(manager.get_dictionary(&Language::English).unwrap().word_count(), 2);
+    }
+    
+    #[test]
+    fn replace_import_keeps_saved_user_words() {
+        let (mut manager, dir) = manager_with_words(&["apple"]);
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "cherry\n").unwrap();
+        
+        manager.add_word_to_dictionary("zyzzyva", Language::English).unwrap();
+        manager.import_dictionary(path, Language::English, ImportMode::Replace).unwrap();
+        manager.add_word_to_dictionary("quokka", Language::English).unwrap();
+        
+        let reloaded = DictionaryManager::with_data_dir(dir.path()).get_dictionary(&Language::English).unwrap();
+        let mut user_words: Vec<&String> = reloaded.get_user_words().iter().collect();
+        user_words.sort();
+        assert_eq!(user_words, vec!["quokka", "zyzzyva"]);
+    }
+    
+    #[test]
+    fn loads_from_an_in_memory_byte_slice() {
+        let dict = Dictionary::from_source(Language::English, &BytesSource::new(&b"hello\nworld,42\n"[..])).unwrap();
+        assert!(dict.is_loaded());
+        assert!(dict.file_path().is_none());
+        assert_eq!(dict.word_count(), 2);
+        assert!(dict.contains("hello", false, false));
+        assert_eq!(dict.frequency("world"), Some(42));
+        
+        let dict = Dictionary::from_source(Language::English, &BytesSource::json(&br#"{"hello": 7}"#[..])).unwrap();
+        assert_eq!(dict.frequency("hello"), Some(7));
+    }
+    
+    #[test]
+    fn german_compounds_split_into_dictionary_words() {
+        let dict = Dictionary::from_words(Language::German, words(&["Haus", "Tür", "Arbeit", "Zimmer"]));
+        assert!(dict.is_valid_compound("Haustür"));
+        assert!(dict.contains("Haustür", false, false));
+        assert!(dict.is_valid_compound("Arbeitszimmer"));
+        assert!(!dict.is_valid_compound("Hausxyz"));
+        
+        // English doesn't form compounds this way
+        let dict = Dictionary::from_words(Language::English, words(&["house", "door"]));
+        assert!(!dict.contains("housedoor", false, false));
+    }
+    
+    #[test]
+    fn from_words_builds_a_loaded_dictionary() {
+        let dict = Dictionary::from_words(Language::English, words(&["Hello", " world ", "", "a"]));
+        assert!(dict.is_loaded());
+        assert_eq!(dict.word_count(), 2);
+        assert!(dict.contains("hello", false, false));
+        assert!(dict.contains("world", false, false));
+        assert!(dict.get_user_words().is_empty());
+    }
+    
+    #[test]
+    fn inserted_dictionary_replaces_the_loaded_one() {
+        let (mut manager, _dir) = manager_with_words(&["apple"]);
+        assert!(manager.get_dictionary(&Language::English).unwrap().contains("apple", false, false));
+        
+        manager.insert_dictionary(Language::English, Dictionary::from_words(Language::English, words(&["hello", "world"])));
+        let dict = manager.get_dictionary(&Language::English).unwrap();
+        assert_eq!(dict.word_count(), 2);
+        assert!(!dict.contains("apple", false, false));
+    }
+    
+    #[test]
+    fn contractions_and_possessives_need_a_dictionary_stem() {
+        let dict = Dictionary::from_words(Language::English, words(&["children"]));
+        assert!(dict.contains("children's", false, false));
+        assert!(dict.contains("it's", false, false));
+        assert!(dict.contains("we'll", false, false));
+        assert!(dict.contains("I'm", false, false));
+        assert!(!dict.contains("teh's", false, false));
+        assert!(!dict.contains("xq's", false, false));
+        assert!(!dict.contains("'children", false, false));
+    }
+    
+    #[test]
+    fn hunspell_export_round_trips_the_word_set() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("words.dic");
+        
+        let dict = Dictionary::from_words(Language::English, words(&["cherry", "Paris", "and/or", "apple"]));
+        dict.export_to_file(&path).unwrap();
+        let exported = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(exported.lines().next(), Some("4"));
+        assert!(exported.contains("and\\/or"));
+        
+        let reloaded = Dictionary::from_source(Language::English, &FileSource::new(&path)).unwrap();
+        assert_eq!(reloaded.get_words(), dict.get_words());
+        assert!(reloaded.contains("Paris", true, false));
+    }
+    
+    #[test]
+    fn watched_dictionary_reloads_when_its_file_changes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("dictionary(nld).txt");
+        std::fs::write(&path, "huis\n").unwrap();
+        let manager = DictionaryManager::with_data_dir(dir.path());
+        manager.get_dictionary(&Language::Dutch).unwrap();
+        
+        let reloaded = manager.watch().unwrap();
+        std::fs::write(&path, "huis\ntuin\n").unwrap();
+        assert_eq!(reloaded.recv_timeout(Duration::from_secs(10)), Ok(Language::Dutch));
+        assert!(manager.dictionary_contains(&Language::Dutch, "tuin", false));
+        manager.unwatch();
+    }
+    
+    #[test]
+    fn user_word_export_leaves_out_the_base_dictionary() {
+        let (mut manager, dir) = manager_with_words(&["apple", "banana"]);
+        manager.add_word_to_dictionary("cherry", Language::English).unwrap();
+        
+        let path = dir.path().join("mine.txt");
+        manager.export_user_words(&Language::English, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "cherry\n");
+    }
+    
+    #[test]
+    fn length_histogram_counts_every_word() {
+        let dict = Dictionary::from_words(Language::English, words(&["cat", "dog", "horse", "elephant", "zebra"]));
+        let stats = dict.stats();
+        assert_eq!(stats.length_histogram.iter().sum::<usize>(), stats.total_words);
+        assert_eq!(stats.total_words, 5);
+        assert_eq!((stats.length_histogram[3], stats.length_histogram[5], stats.length_histogram[8]), (2, 2, 1));
+    }
+    
+    #[test]
+    fn background_loads_report_their_state() {
+        fn wait_for(manager: &DictionaryManager, language: Language, state: LoadState) {
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while manager.loading_state(&language) != state {
+                assert!(Instant::now() < deadline, "{:?} never reached {:?}", language, state);
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        
+        let dir = TempDir::new().unwrap();
+        let manager = DictionaryManager::with_data_dir(dir.path());
+        assert_eq!(manager.loading_state(&Language::English), LoadState::NotLoaded);
+        
+        let (release, wait) = mpsc::channel::<()>();
+        manager.load_in_background_with(Language::English, move || {
+            wait.recv()?;
+            Ok(Dictionary::from_words(Language::English, words(&["apple"])))
+        });
+        assert_eq!(manager.loading_state(&Language::English), LoadState::Loading);
+        assert!(manager.get_dictionary(&Language::English).is_err());
+        
+        release.send(()).unwrap();
+        wait_for(&manager, Language::English, LoadState::Loaded);
+        assert!(manager.get_dictionary(&Language::English).unwrap().contains("apple", false, false));
+        
+        manager.load_in_background_with(Language::French, || anyhow::bail!("no word list"));
+        wait_for(&manager, Language::French, LoadState::Failed("no word list".to_string()));
+    }
+    
+    #[test]
+    fn dic_import_skips_the_count_line_and_flags() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("words.dic");
+        std::fs::write(&path, "3\nhello/S\nworld/MS po:noun\ncolou\\/r\n").unwrap();
+        
+        let mut manager = DictionaryManager::with_data_dir(dir.path());
+        assert_eq!(manager.import_dictionary(path, Language::English, ImportMode::Replace).unwrap(), 3);
+        
+        let dict = manager.get_dictionary(&Language::English).unwrap();
+        let mut imported: Vec<&String> = dict.get_words().iter().collect();
+        imported.sort();
+        assert_eq!(imported, vec!["colou/r", "hello", "world"]);
+    }
+    
+    #[test]
+    fn availability_follows_the_dictionary_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("dictionary(nld).txt"), "huis\n").unwrap();
+        let manager = DictionaryManager::with_data_dir(dir.path());
+        
+        let availability: HashMap<Language, bool> = manager.language_availability().into_iter().collect();
+        assert_eq!(availability.get(&Language::Dutch), Some(&true));
+        assert_eq!(availability.get(&Language::Turkish), Some(&false));
+        assert!(!availability.contains_key(&Language::AutoDetect));
+    }
+}
@@ -0,0 +1,144 @@
+use serde::Serialize;
+
+/// A line added by a diff, numbered as in the new version of its file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AddedLine {
+    pub line: usize,
+    pub text: String,
+}
+
+/// The lines a diff adds to one file. Renamed files use their new path;
+/// deleted files don't appear.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileDiff {
+    pub path: String,
+    pub added: Vec<AddedLine>,
+}
+
+/// Extract the added lines of each file in a unified diff, as produced by
+/// `git diff` or `diff -u`. Hunk line counts are tracked so added lines that
+/// themselves start with `+++` aren't mistaken for file headers.
+pub fn parse_unified_diff(diff: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    // False after `+++ /dev/null`, the header of a deleted file
+    let mut in_file = false;
+    let mut new_line = 0;
+    let mut old_remaining: usize = 0;
+    let mut new_remaining: usize = 0;
+    
+    for line in diff.lines() {
+        if old_remaining > 0 || new_remaining > 0 {
+            match line.chars().next() {
+                Some('+') => {
+                    if in_file {
+                        if let Some(file) = files.last_mut() {
+                            file.added.push(AddedLine { line: new_line, text: line[1..].to_string() });
+                        }
+                    }
+                    new_line += 1;
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+                Some('-') => old_remaining = old_remaining.saturating_sub(1),
+                Some('\\') => {}
+                // Context line; some tools strip the leading space of blank ones
+                _ => {
+                    new_line += 1;
+                    old_remaining = old_remaining.saturating_sub(1);
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+            }
+            continue;
+        }
+        
+        if let Some(path) = line.strip_prefix("+++ ") {
+            in_file = match new_file_path(path) {
+                Some(path) => {
+                    files.push(FileDiff { path, added: Vec::new() });
+                    true
+                }
+                None => false,
+            };
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            if let Some((old, new)) = parse_hunk_header(header) {
+                old_remaining = old.1;
+                new_line = new.0;
+                new_remaining = new.1;
+            }
+        }
+    }
+    
+    files.retain(|file| !file.added.is_empty());
+    files
+}
+
+/// The path from a `+++` header, without quotes, a `b/` prefix or a
+/// trailing timestamp; `None` for `/dev/null`
+fn new_file_path(header: &str) -> Option<String> {
+    let path = header.split('\t').next().unwrap_or(header).trim_end();
+    let path = path.trim_matches('"');
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix("b/").unwrap_or(path).to_string())
+}
+
+/// `-12,3 +14,5 @@ fn name` into `((12, 3), (14, 5))`. A missing count
+/// means one line.
+fn parse_hunk_header(header: &str) -> Option<((usize, usize), (usize, usize))> {
+    let mut ranges = header.split_whitespace();
+    let old = parse_range(ranges.next()?.strip_prefix('-')?)?;
+    let new = parse_range(ranges.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn added_lines_are_numbered_as_in_the_new_file() {
+        let diff = "\
+diff --git a/notes.txt b/notes.txt
+--- a/notes.txt
++++ b/notes.txt
+@@ -1,4 +1,5 @@
+ first
+-second
++secnod
++inserted
+ third
+ fourth
+@@ -10,2 +11,3 @@ heading
+ tenth
+++++ plus signs
+ eleventh
+diff --git a/old.txt b/renamed.txt
+rename from old.txt
+rename to renamed.txt
+--- a/old.txt
++++ b/renamed.txt
+@@ -3,0 +4 @@
++appended
+diff --git a/gone.txt b/gone.txt
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-removed
+";
+        let line = |line: usize, text: &str| AddedLine { line, text: text.to_string() };
+        assert_eq!(parse_unified_diff(diff), vec![
+            FileDiff {
+                path: "notes.txt".to_string(),
+                added: vec![line(2, "secnod"), line(3, "inserted"), line(12, "+++ plus signs")],
+            },
+            FileDiff { path: "renamed.txt".to_string(), added: vec![line(4, "appended")] },
+        ]);
+    }
+}
@@ -1,17 +1,99 @@
-use crate::checker::{DocumentAnalysis, WordCheck, WordType};
+pub mod syntax;
+
+use crate::checker::{DocumentAnalysis, IssueKind, Severity, WordCheck, WordType};
+use crate::language::Language;
+use crate::theme::AtomTheme;
 use eframe::egui;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use syntax::{keywords_for, line_comment_prefix};
+
+/// Lexical category of a token for syntax highlighting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Number,
+    String,
+    Comment,
+    Plain,
+}
+
+/// Colors used by syntax highlighting, resolved from the current visuals
+#[derive(Debug, Clone, Copy)]
+pub struct SyntaxColors {
+    pub text: egui::Color32,
+    pub keyword: egui::Color32,
+    pub number: egui::Color32,
+    pub string: egui::Color32,
+    pub comment: egui::Color32,
+}
+
+impl SyntaxColors {
+    pub fn new(text: egui::Color32, dark: bool) -> Self {
+        if dark {
+            Self {
+                text,
+                keyword: egui::Color32::from_rgb(198, 120, 221),
+                number: egui::Color32::from_rgb(209, 154, 102),
+                string: egui::Color32::from_rgb(152, 195, 121),
+                comment: egui::Color32::from_rgb(127, 132, 142),
+            }
+        } else {
+            Self {
+                text,
+                keyword: egui::Color32::from_rgb(166, 38, 164),
+                number: egui::Color32::from_rgb(152, 104, 1),
+                string: egui::Color32::from_rgb(80, 161, 79),
+                comment: egui::Color32::from_rgb(120, 120, 120),
+            }
+        }
+    }
+    
+    pub fn color_for(&self, kind: TokenKind) -> egui::Color32 {
+        match kind {
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Number => self.number,
+            TokenKind::String => self.string,
+            TokenKind::Comment => self.comment,
+            TokenKind::Plain => self.text,
+        }
+    }
+}
+
+/// Something chosen from the editor's right-click menu, for the app to carry
+/// out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextAction {
+    /// Replace the byte range `start..end` of the document, if it still
+    /// holds `original`
+    Replace { start: usize, end: usize, original: String, replacement: String },
+    AddWord(String),
+    IgnoreWord(String),
+    IgnoreInDocument(String),
+    LookUp(String),
+}
 
 #[derive(Clone)]
 pub struct TextEditor {
     line_height: f32,
     font_size: f32,
     show_whitespace: bool,
+    tab_width: usize,
     wrap_lines: bool,
-    error_cache: HashMap<usize, WordCheck>,
+    /// Flagged words by their byte offset in the document
+    error_cache: BTreeMap<usize, WordCheck>,
     last_analysis: Option<DocumentAnalysis>,
     programming_language: Option<String>,
-    scroll_offset: f32,
+    /// Lines are aligned to the right edge, for Arabic and Hebrew
+    right_to_left: bool,
+    pending_scroll_line: Option<usize>,
+    theme: AtomTheme,
+    syntax_highlighting: bool,
+    selection: Option<(usize, usize)>,
+    cursor: Option<(usize, usize)>,
+    /// Line and column to move the cursor to on the next frame
+    pending_cursor: Option<(usize, usize)>,
+    context_word: Option<WordCheck>,
+    context_action: Option<ContextAction>,
 }
 
 impl Default for TextEditor {
@@ -26,11 +108,20 @@ impl TextEditor {
             line_height: 24.0,
             font_size: 14.0,
             show_whitespace: false,
+            tab_width: 4,
             wrap_lines: true,
-            error_cache: HashMap::new(),
+            error_cache: BTreeMap::new(),
             last_analysis: None,
             programming_language: None,
-            scroll_offset: 0.0,
+            right_to_left: false,
+            pending_scroll_line: None,
+            theme: AtomTheme::OneDark,
+            syntax_highlighting: true,
+            selection: None,
+            cursor: None,
+            pending_cursor: None,
+            context_word: None,
+            context_action: None,
         }
     }
     
@@ -51,25 +142,8 @@ impl TextEditor {
     }
     
     pub fn detect_programming_language(&mut self, filename: &str) {
-        self.programming_language = match filename.rsplit('.').next() {
-            Some("rs") => Some("rust".to_string()),
-            Some("py") => Some("python".to_string()),
-            Some("js") | Some("ts") | Some("jsx") | Some("tsx") => Some("javascript".to_string()),
-            Some("java") => Some("java".to_string()),
-            Some("cpp") | Some("cc") | Some("cxx") | Some("c") => Some("cpp".to_string()),
-            Some("go") => Some("go".to_string()),
-            Some("rb") => Some("ruby".to_string()),
-            Some("php") => Some("php".to_string()),
-            Some("html") | Some("htm") => Some("html".to_string()),
-            Some("css") => Some("css".to_string()),
-            Some("md") => Some("markdown".to_string()),
-            Some("json") => Some("json".to_string()),
-            Some("toml") => Some("toml".to_string()),
-            Some("yaml") | Some("yml") => Some("yaml".to_string()),
-            Some("xml") => Some("xml".to_string()),
-            Some("sh") | Some("bash") => Some("shell".to_string()),
-            _ => None,
-        };
+        self.programming_language = crate::util::programming_language_from_filename(filename)
+            .map(String::from);
     }
     
     pub fn show(
@@ -105,6 +179,15 @@ impl TextEditor {
             ui.visuals().window_fill,
         );
         
+        if let Some(line) = self.pending_scroll_line.take() {
+            let line_top = rect.top() + line.saturating_sub(1) as f32 * self.line_height;
+            let line_rect = egui::Rect::from_min_size(
+                egui::pos2(rect.left(), line_top),
+                egui::vec2(rect.width(), self.line_height),
+            );
+            ui.scroll_to_rect(line_rect, Some(egui::Align::Center));
+        }
+        
         if show_line_numbers {
             self.draw_line_numbers(ui, rect, content);
         }
@@ -122,21 +205,126 @@ impl TextEditor {
                 .desired_rows(10)
                 .font(egui::FontId::monospace(self.font_size))
                 .frame(false)
+                .horizontal_align(if self.right_to_left { egui::Align::RIGHT } else { egui::Align::LEFT })
                 .text_color(ui.visuals().text_color());
             
             if self.wrap_lines {
                 text_edit = text_edit.desired_rows(10);
             }
             
-            let edit_response = ui.add(text_edit);
-            if edit_response.changed() {
+            let output = text_edit.show(ui);
+            if output.response.changed() {
                 *modified = true;
             }
             
-            edit_response
+            if let Some((line, column)) = self.pending_cursor.take() {
+                let cursor = egui::text::CCursor::new(char_index_at(content, line, column));
+                let mut state = output.state.clone();
+                state.cursor.set_char_range(Some(egui::text::CCursorRange::one(cursor)));
+                state.store(ui.ctx(), output.response.id);
+                output.response.request_focus();
+            }
+            
+            self.cursor = output.cursor_range.map(|range| {
+                let pcursor = range.primary.pcursor;
+                (pcursor.paragraph + 1, pcursor.offset + 1)
+            });
+            
+            self.selection = output.cursor_range
+                .filter(|range| !range.is_empty())
+                .map(|range| {
+                    let chars = range.as_sorted_char_range();
+                    (char_to_byte(content, chars.start), char_to_byte(content, chars.end))
+                });
+            
+            let response = output.response;
+            if response.secondary_clicked() {
+                self.context_word = response.interact_pointer_pos()
+                    .and_then(|pos| self.error_at_screen_pos(content, rect, line_numbers_width, pos))
+                    .cloned();
+            }
+            response.context_menu(|ui| self.show_context_menu(ui));
+            
+            response
         }).inner
     }
     
+    /// The flagged word or whitespace under `pos`, using the same monospace
+    /// geometry the text is drawn with. Positions past the end of a line
+    /// have none.
+    fn error_at_screen_pos(&self, content: &str, rect: egui::Rect, line_numbers_width: f32, pos: egui::Pos2) -> Option<&WordCheck> {
+        if pos.y < rect.top() {
+            return None;
+        }
+        
+        let line_idx = ((pos.y - rect.top()) / self.line_height) as usize;
+        let line = content.lines().nth(line_idx)?;
+        let char_width = self.font_size * 0.6;
+        let text_x = self.line_start_x(rect, line_numbers_width, line, char_width);
+        if pos.x < text_x {
+            return None;
+        }
+        
+        let cell = ((pos.x - text_x) / char_width) as usize;
+        let line_start = *crate::util::line_start_offsets(content).get(line_idx)?;
+        
+        // Walk the line in display cells so tabs count at their full width
+        let mut x = 0;
+        let (byte_idx, _) = line.char_indices().find(|&(_, c)| {
+            x = next_visual_x(x, c, self.tab_width);
+            cell < x
+        })?;
+        
+        self.get_error_at_offset(line_start + byte_idx)
+    }
+    
+    fn show_context_menu(&mut self, ui: &mut egui::Ui) {
+        let Some(word) = self.context_word.clone() else {
+            ui.label("No spelling suggestions");
+            return;
+        };
+        
+        if word.suggestions.is_empty() {
+            ui.label("No suggestions");
+        }
+        for suggestion in &word.suggestions {
+            if ui.button(word.suggestion_label(suggestion)).clicked() {
+                self.context_action = Some(ContextAction::Replace {
+                    start: word.start,
+                    end: word.end,
+                    original: word.original.clone(),
+                    replacement: word.replacement(suggestion),
+                });
+                ui.close_menu();
+            }
+        }
+        
+        if word.word_type != WordType::RepeatedWord && word.kind == IssueKind::Spelling {
+            ui.separator();
+            if ui.button("➕ Add to dictionary").clicked() {
+                self.context_action = Some(ContextAction::AddWord(word.word.clone()));
+                ui.close_menu();
+            }
+            if ui.button("🙈 Ignore word").clicked() {
+                self.context_action = Some(ContextAction::IgnoreWord(word.word.clone()));
+                ui.close_menu();
+            }
+            if ui.button("📄 Ignore in this file").clicked() {
+                self.context_action = Some(ContextAction::IgnoreInDocument(word.word.clone()));
+                ui.close_menu();
+            }
+            if ui.button("📖 Look up").clicked() {
+                self.context_action = Some(ContextAction::LookUp(word.original.clone()));
+                ui.close_menu();
+            }
+        }
+    }
+    
+    /// The action picked from the right-click menu since the last call
+    pub fn take_context_action(&mut self) -> Option<ContextAction> {
+        self.context_action.take()
+    }
+    
     fn draw_line_numbers(&self, ui: &egui::Ui, rect: egui::Rect, content: &str) {
         let painter = ui.painter();
         let line_count = content.lines().count().max(1);
@@ -176,6 +364,17 @@ impl TextEditor {
         );
     }
     
+    /// Screen x where `line` starts: the left edge of the text area, or for
+    /// right-to-left text wherever it has to start to end at the right edge
+    fn line_start_x(&self, rect: egui::Rect, line_numbers_width: f32, line: &str, char_width: f32) -> f32 {
+        if self.right_to_left {
+            let width = column_to_visual_x(line, line.chars().count() + 1, self.tab_width);
+            rect.right() - 5.0 - width as f32 * char_width
+        } else {
+            rect.left() + line_numbers_width + 5.0
+        }
+    }
+    
     fn calculate_line_numbers_width(&self, content: &str) -> f32 {
         let line_count = content.lines().count().max(1);
         let max_digits = line_count.to_string().len();
@@ -187,24 +386,36 @@ impl TextEditor {
         let lines: Vec<&str> = content.lines().collect();
         
         let text_color = ui.visuals().text_color();
-        let error_color = ui.visuals().error_fg_color;
-        let warning_color = egui::Color32::from_rgb(255, 165, 0);
+        let error_color = self.theme.error_underline_color();
+        let warning_color = self.theme.suggestion_underline_color();
         let info_color = egui::Color32::from_rgb(100, 149, 237);
         
+        let syntax_colors = SyntaxColors::new(text_color, ui.visuals().dark_mode);
+        let highlight_language = self.programming_language.as_deref().filter(|_| self.syntax_highlighting);
+        
         let char_width = self.font_size * 0.6;
         
         for (line_idx, line) in lines.iter().enumerate() {
             let line_y = rect.top() + (line_idx as f32 * self.line_height);
-            let text_x = rect.left() + line_numbers_width + 5.0;
+            let text_x = self.line_start_x(rect, line_numbers_width, line, char_width);
+            let text_pos = egui::pos2(text_x, line_y + (self.line_height * 0.7));
+            let display_line = expand_tabs(line, self.tab_width);
             
-            // Draw regular text
-            painter.text(
-                egui::pos2(text_x, line_y + (self.line_height * 0.7)),
-                egui::Align2::LEFT_CENTER,
-                *line,
-                egui::FontId::monospace(self.font_size),
-                text_color,
-            );
+            if let Some(language) = highlight_language {
+                self.highlight_syntax(painter, text_pos, &display_line, language, &syntax_colors);
+            } else {
+                painter.text(
+                    text_pos,
+                    egui::Align2::LEFT_CENTER,
+                    &display_line,
+                    egui::FontId::monospace(self.font_size),
+                    text_color,
+                );
+            }
+            
+            if self.show_whitespace {
+                self.draw_whitespace(painter, text_pos, line, char_width, ui.visuals().weak_text_color());
+            }
             
             if let Some(analysis) = &self.last_analysis {
                 let line_num = line_idx + 1;
@@ -214,15 +425,15 @@ impl TextEditor {
                     .collect();
                 
                 for error in line_errors {
-                    let error_start_in_line = error.column.saturating_sub(1);
-                    let error_x = text_x + (error_start_in_line as f32 * char_width);
-                    let error_width = error.word.len() as f32 * char_width;
+                    let visual_start = column_to_visual_x(line, error.column, self.tab_width);
+                    let visual_end = column_to_visual_x(line, error.column + error.original.chars().count(), self.tab_width);
+                    let error_x = text_x + (visual_start as f32 * char_width);
+                    let error_width = visual_end.saturating_sub(visual_start) as f32 * char_width;
                     
-                    let color = match error.word_type {
-                        WordType::CodeIdentifier => info_color,
-                        WordType::ProperNoun => warning_color,
-                        WordType::Acronym => warning_color,
-                        _ => error_color,
+                    let color = match error.severity {
+                        Severity::Error => error_color,
+                        Severity::Warning => warning_color,
+                        Severity::Hint => info_color,
                     };
                     
                     self.draw_wavy_underline(
@@ -237,6 +448,54 @@ impl TextEditor {
         }
     }
     
+    /// Draw `line` with each token colored by kind, vertically centred on
+    /// `pos` like the plain text it replaces
+    fn highlight_syntax(
+        &self,
+        painter: &egui::Painter,
+        pos: egui::Pos2,
+        line: &str,
+        language: &str,
+        colors: &SyntaxColors,
+    ) {
+        let font = egui::FontId::monospace(self.font_size);
+        let mut job = egui::text::LayoutJob::default();
+        
+        for (start, end) in tokenize_line(line, language) {
+            let token = &line[start..end];
+            let color = colors.color_for(classify_token(token, language));
+            job.append(token, 0.0, egui::TextFormat::simple(font.clone(), color));
+        }
+        
+        let galley = painter.layout_job(job);
+        let top_left = egui::pos2(pos.x, pos.y - galley.size().y / 2.0);
+        painter.galley(top_left, galley, colors.text);
+    }
+    
+    /// Mark spaces with `·` and tabs with `→` over the drawn line
+    fn draw_whitespace(&self, painter: &egui::Painter, pos: egui::Pos2, line: &str, char_width: f32, color: egui::Color32) {
+        let font = egui::FontId::monospace(self.font_size);
+        let mut x = 0;
+        
+        for c in line.chars() {
+            let glyph = match c {
+                ' ' => Some("·"),
+                '\t' => Some("→"),
+                _ => None,
+            };
+            if let Some(glyph) = glyph {
+                painter.text(
+                    egui::pos2(pos.x + x as f32 * char_width, pos.y),
+                    egui::Align2::LEFT_CENTER,
+                    glyph,
+                    font.clone(),
+                    color,
+                );
+            }
+            x = next_visual_x(x, c, self.tab_width);
+        }
+    }
+    
     fn draw_wavy_underline(
         &self,
         painter: &egui::Painter,
@@ -269,10 +528,35 @@ impl TextEditor {
     }
     
     pub fn set_font_size(&mut self, size: f32) {
-        self.font_size = size.max(8.0).min(36.0);
+        self.font_size = size.clamp(8.0, 36.0);
         self.line_height = size * 1.6;
     }
     
+    /// Theme used to pick underline colors
+    pub fn set_theme(&mut self, theme: AtomTheme) {
+        self.theme = theme;
+    }
+    
+    /// 1-based line and character column of the text cursor, as of the
+    /// last frame
+    pub fn cursor_position(&self) -> Option<(usize, usize)> {
+        self.cursor
+    }
+    
+    /// Byte range of the current non-empty text selection
+    pub fn selection_range(&self) -> Option<(usize, usize)> {
+        self.selection
+    }
+    
+    /// Align text to the right edge if `language` is written right to left
+    pub fn set_language(&mut self, language: Language) {
+        self.right_to_left = language.is_rtl();
+    }
+    
+    pub fn set_syntax_highlighting(&mut self, enabled: bool) {
+        self.syntax_highlighting = enabled;
+    }
+    
     pub fn set_wrap_lines(&mut self, wrap: bool) {
         self.wrap_lines = wrap;
     }
@@ -281,16 +565,199 @@ impl TextEditor {
         self.show_whitespace = show;
     }
     
+    pub fn set_tab_width(&mut self, width: usize) {
+        self.tab_width = width.max(1);
+    }
+    
+    /// The flagged word covering byte `offset` of the document
+    pub fn get_error_at_offset(&self, offset: usize) -> Option<&WordCheck> {
+        let (_, word) = self.error_cache.range(..=offset).next_back()?;
+        (offset < word.end).then_some(word)
+    }
+    
+    /// The flagged word covering the 1-based character `column` of `line`
     pub fn get_error_at_position(&self, line: usize, column: usize) -> Option<&WordCheck> {
         if let Some(analysis) = &self.last_analysis {
             analysis.words.iter()
-                .find(|w| !w.is_correct && w.line == line && w.column <= column && column <= w.column + w.word.len())
+                .find(|w| !w.is_correct && w.line == line && w.column <= column && column < w.column + w.original.chars().count())
         } else {
             None
         }
     }
     
+    /// Scroll the 1-based `line` into view on the next frame
     pub fn scroll_to_line(&mut self, line: usize) {
-        self.scroll_offset = (line as f32 - 5.0).max(0.0) * self.line_height;
+        self.pending_scroll_line = Some(line);
+    }
+    
+    /// Put the text cursor at 1-based `line` and character `column` on the
+    /// next frame, scrolling it into view
+    pub fn set_cursor(&mut self, line: usize, column: usize) {
+        self.pending_cursor = Some((line, column));
+        self.scroll_to_line(line);
+    }
+}
+
+/// Display cell after `c` when it starts at cell `x`; tabs advance to the
+/// next multiple of `tab_width`
+fn next_visual_x(x: usize, c: char, tab_width: usize) -> usize {
+    if c == '\t' {
+        let tab_width = tab_width.max(1);
+        (x / tab_width + 1) * tab_width
+    } else {
+        x + 1
+    }
+}
+
+/// Display cell at which the 1-based character `column` of `line` is
+/// drawn, with tabs expanded to `tab_width`
+pub fn column_to_visual_x(line: &str, column: usize, tab_width: usize) -> usize {
+    line.chars()
+        .take(column.saturating_sub(1))
+        .fold(0, |x, c| next_visual_x(x, c, tab_width))
+}
+
+fn expand_tabs(line: &str, tab_width: usize) -> String {
+    if !line.contains('\t') {
+        return line.to_string();
+    }
+    
+    let mut expanded = String::with_capacity(line.len());
+    let mut x = 0;
+    for c in line.chars() {
+        let next_x = next_visual_x(x, c, tab_width);
+        if c == '\t' {
+            expanded.extend(std::iter::repeat_n(' ', next_x - x));
+        } else {
+            expanded.push(c);
+        }
+        x = next_x;
+    }
+    expanded
+}
+
+fn char_to_byte(text: &str, char_index: usize) -> usize {
+    text.char_indices().nth(char_index).map(|(idx, _)| idx).unwrap_or(text.len())
+}
+
+/// Character index of 1-based `line` and `column` in `text`, clamped to
+/// the end of the line and of the text
+fn char_index_at(text: &str, line: usize, column: usize) -> usize {
+    let mut index = 0;
+    for (number, line_text) in text.split('\n').enumerate() {
+        let length = line_text.chars().count();
+        if number + 1 == line {
+            return index + column.saturating_sub(1).min(length);
+        }
+        index += length + 1;
+    }
+    text.chars().count()
+}
+
+/// Classify a single token produced by `tokenize_line`
+pub fn classify_token(token: &str, language: &str) -> TokenKind {
+    if token.starts_with(line_comment_prefix(language)) {
+        TokenKind::Comment
+    } else if token.starts_with(['"', '\'']) {
+        TokenKind::String
+    } else if token.starts_with(|c: char| c.is_ascii_digit()) {
+        TokenKind::Number
+    } else if keywords_for(language).contains(&token) {
+        TokenKind::Keyword
+    } else {
+        TokenKind::Plain
+    }
+}
+
+/// Split a line into byte ranges of tokens. A string literal is one token
+/// even when it contains spaces, and a line comment runs to the end of the
+/// line. Everything between words is kept so the ranges cover the line.
+fn tokenize_line(line: &str, language: &str) -> Vec<(usize, usize)> {
+    let comment_prefix = line_comment_prefix(language);
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    
+    while idx < line.len() {
+        let rest = &line[idx..];
+        let c = rest.chars().next().unwrap_or_default();
+        
+        let len = if rest.starts_with(comment_prefix) {
+            rest.len()
+        } else if c == '"' || (c == '\'' && language != "rust") {
+            string_token_len(rest, c)
+        } else if c.is_alphanumeric() || c == '_' {
+            rest.find(|ch: char| !(ch.is_alphanumeric() || ch == '_' || (c.is_ascii_digit() && ch == '.')))
+                .unwrap_or(rest.len())
+        } else {
+            c.len_utf8()
+        };
+        
+        tokens.push((idx, idx + len));
+        idx += len;
     }
-}
\ No newline at end of file
+    
+    tokens
+}
+
+/// Length of a string literal starting at `quote`, honouring backslash
+/// escapes; an unterminated string runs to the end of the line
+fn string_token_len(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (offset, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return offset + c.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    
+    #[test]
+    fn errors_are_found_by_document_offset_across_lines() {
+        let (mut checker, _dir) = checker_with_words(&["the"]);
+        checker.set_confidence_threshold(0.5);
+        let text = "The blorf\nThe zzqx";
+        
+        let analysis = checker.check_document(text);
+        let errors: Vec<&WordCheck> = analysis.words.iter().filter(|w| !w.is_correct).collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[1].line, 2);
+        assert!(errors[1].start > text.lines().next().unwrap().len());
+        let second_start = errors[1].start;
+        
+        let mut editor = TextEditor::new();
+        editor.set_analysis(analysis);
+        assert_eq!(editor.get_error_at_offset(5).map(|w| w.word.as_str()), Some("blorf"));
+        assert_eq!(editor.get_error_at_offset(second_start + 2).map(|w| w.word.as_str()), Some("zzqx"));
+        assert!(editor.get_error_at_offset(1).is_none());
+    }
+    
+    #[test]
+    fn tokens_are_classified_by_language() {
+        assert_eq!(classify_token("fn", "rust"), TokenKind::Keyword);
+        assert_eq!(classify_token("\"fn\"", "rust"), TokenKind::String);
+        assert_eq!(classify_token("// fn", "rust"), TokenKind::Comment);
+        assert_eq!(classify_token("42", "rust"), TokenKind::Number);
+        assert_eq!(classify_token("value", "rust"), TokenKind::Plain);
+        assert_eq!(classify_token("# note", "python"), TokenKind::Comment);
+        assert_eq!(classify_token("def", "python"), TokenKind::Keyword);
+    }
+    
+    #[test]
+    fn visual_columns_expand_tabs_to_the_next_stop() {
+        assert_eq!(column_to_visual_x("abc", 1, 4), 0);
+        assert_eq!(column_to_visual_x("abc", 3, 4), 2);
+        assert_eq!(column_to_visual_x("\tabc", 2, 4), 4);
+        assert_eq!(column_to_visual_x("ab\tc", 4, 4), 4);
+        assert_eq!(column_to_visual_x("ab\tc", 4, 8), 8);
+        assert_eq!(column_to_visual_x("\t\tx", 3, 2), 4);
+    }
+}
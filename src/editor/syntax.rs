@@ -0,0 +1,115 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+static KEYWORDS: Lazy<HashMap<&'static str, Vec<&'static str>>> = Lazy::new(|| {
+    let mut map = HashMap::new();
+    
+    map.insert("rust", vec![
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+        "trait", "true", "type", "unsafe", "use", "where", "while",
+    ]);
+    
+    map.insert("python", vec![
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+        "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return",
+        "True", "try", "while", "with", "yield",
+    ]);
+    
+    let javascript = vec![
+        "async", "await", "break", "case", "catch", "class", "const", "continue", "default",
+        "delete", "do", "else", "export", "extends", "false", "finally", "for", "function",
+        "if", "import", "in", "instanceof", "let", "new", "null", "return", "super", "switch",
+        "this", "throw", "true", "try", "typeof", "undefined", "var", "void", "while", "yield",
+    ];
+    let mut typescript = javascript.clone();
+    typescript.extend([
+        "abstract", "any", "as", "boolean", "declare", "enum", "implements", "interface",
+        "keyof", "namespace", "never", "number", "private", "protected", "public", "readonly",
+        "string", "type", "unknown",
+    ]);
+    map.insert("javascript", javascript);
+    map.insert("typescript", typescript);
+    
+    map.insert("java", vec![
+        "abstract", "boolean", "break", "case", "catch", "char", "class", "continue",
+        "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+        "float", "for", "if", "implements", "import", "instanceof", "int", "interface", "long",
+        "new", "null", "package", "private", "protected", "public", "return", "static",
+        "super", "switch", "this", "throw", "throws", "true", "try", "void", "while",
+    ]);
+    
+    map.insert("go", vec![
+        "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough",
+        "false", "for", "func", "go", "goto", "if", "import", "interface", "map", "nil",
+        "package", "range", "return", "select", "struct", "switch", "true", "type", "var",
+    ]);
+    
+    map.insert("cpp", vec![
+        "auto", "bool", "break", "case", "catch", "char", "class", "const", "constexpr",
+        "continue", "default", "delete", "do", "double", "else", "enum", "extern", "false",
+        "float", "for", "goto", "if", "include", "inline", "int", "long", "namespace", "new",
+        "nullptr", "private", "protected", "public", "return", "short", "signed", "sizeof",
+        "static", "struct", "switch", "template", "this", "throw", "true", "try", "typedef",
+        "typename", "union", "unsigned", "using", "virtual", "void", "volatile", "while",
+    ]);
+    
+    map.insert("ruby", vec![
+        "alias", "and", "begin", "break", "case", "class", "def", "defined", "do", "else",
+        "elsif", "end", "ensure", "false", "for", "if", "in", "module", "next", "nil", "not",
+        "or", "redo", "rescue", "retry", "return", "self", "super", "then", "true", "undef",
+        "unless", "until", "when", "while", "yield",
+    ]);
+    
+    map.insert("php", vec![
+        "abstract", "array", "as", "break", "case", "catch", "class", "const", "continue",
+        "default", "do", "echo", "else", "elseif", "extends", "false", "final", "finally",
+        "fn", "for", "foreach", "function", "global", "if", "implements", "include",
+        "interface", "match", "namespace", "new", "null", "private", "protected", "public",
+        "require", "return", "static", "switch", "throw", "trait", "true", "try", "use",
+        "while", "yield",
+    ]);
+    
+    map.insert("csharp", vec![
+        "abstract", "as", "async", "await", "base", "bool", "break", "case", "catch", "char",
+        "class", "const", "continue", "decimal", "default", "delegate", "do", "double", "else",
+        "enum", "event", "false", "finally", "float", "for", "foreach", "if", "in", "int",
+        "interface", "internal", "is", "namespace", "new", "null", "object", "out",
+        "override", "private", "protected", "public", "readonly", "ref", "return", "sealed",
+        "static", "string", "struct", "switch", "this", "throw", "true", "try", "using", "var",
+        "virtual", "void", "while",
+    ]);
+    
+    map
+});
+
+/// Keywords highlighted for `language` (as named by
+/// `programming_language_from_filename`); empty for unknown languages
+pub fn keywords_for(language: &str) -> &'static [&'static str] {
+    KEYWORDS.get(language).map(Vec::as_slice).unwrap_or(&[])
+}
+
+/// Prefix that starts a line comment in `language`
+pub fn line_comment_prefix(language: &str) -> &'static str {
+    match language {
+        "python" | "ruby" | "shell" | "yaml" | "toml" => "#",
+        _ => "//",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn go_has_its_own_keywords() {
+        let keywords = keywords_for("go");
+        for keyword in ["func", "package", "defer", "go", "chan"] {
+            assert!(keywords.contains(&keyword), "{}", keyword);
+        }
+        assert!(!keywords.contains(&"fn"));
+        assert!(keywords_for("klingon").is_empty());
+    }
+}
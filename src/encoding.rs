@@ -0,0 +1,139 @@
+use crate::{Result, SpellCheckerError};
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use serde::{Deserialize, Serialize};
+
+/// How a text file was encoded, so it can be written back the same way
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEncoding {
+    /// The WHATWG name, e.g. "UTF-8" or "windows-1252"
+    pub name: String,
+    /// Whether the file starts with a byte order mark
+    pub bom: bool,
+}
+
+impl Default for FileEncoding {
+    fn default() -> Self {
+        Self::new(UTF_8, false)
+    }
+}
+
+impl FileEncoding {
+    fn new(encoding: &'static Encoding, bom: bool) -> Self {
+        Self { name: encoding.name().to_string(), bom }
+    }
+    
+    /// Guess the encoding of `bytes`: a byte order mark if there is one,
+    /// then UTF-8 if they're valid UTF-8, then UTF-16 for mostly-ASCII
+    /// text with a NUL in every other byte. Anything else goes to
+    /// chardetng, which tells legacy encodings like Windows-1251 and
+    /// Shift_JIS apart but never guesses UTF-16.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+            return Self::new(encoding, true);
+        }
+        if std::str::from_utf8(bytes).is_ok() {
+            return Self::default();
+        }
+        
+        let pairs = bytes.len() / 2;
+        let nuls_at = |parity: usize| bytes.iter().skip(parity).step_by(2).filter(|&&b| b == 0).count();
+        if pairs > 0 {
+            let (even, odd) = (nuls_at(0), nuls_at(1));
+            if odd * 2 > pairs && even * 10 < pairs {
+                return Self::new(UTF_16LE, false);
+            }
+            if even * 2 > pairs && odd * 10 < pairs {
+                return Self::new(UTF_16BE, false);
+            }
+        }
+        
+        let mut detector = EncodingDetector::new();
+        detector.feed(bytes, true);
+        Self::new(detector.guess(None, true), false)
+    }
+    
+    fn encoding(&self) -> &'static Encoding {
+        Encoding::for_label(self.name.as_bytes()).unwrap_or(UTF_8)
+    }
+    
+    pub fn is_utf16(&self) -> bool {
+        matches!(self.encoding().name(), "UTF-16LE" | "UTF-16BE")
+    }
+    
+    /// Decode `bytes`, skipping the byte order mark if the file has one.
+    /// Fails with `InvalidEncoding` if they aren't valid in this encoding.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String> {
+        let encoding = self.encoding();
+        let bytes = match Encoding::for_bom(bytes) {
+            Some((bom_encoding, bom_length)) if self.bom && bom_encoding == encoding => &bytes[bom_length..],
+            _ => bytes,
+        };
+        
+        encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|text| text.into_owned())
+            .ok_or(SpellCheckerError::InvalidEncoding)
+    }
+    
+    /// Encode `text` for writing back, with a byte order mark if the file
+    /// had one. Fails with `InvalidEncoding` if `text` has characters this
+    /// encoding can't represent.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let encoding = self.encoding();
+        
+        // encoding_rs only encodes to UTF-16 by way of UTF-8
+        if self.is_utf16() {
+            let little_endian = encoding == UTF_16LE;
+            let mut bytes = Vec::with_capacity(text.len() * 2 + 2);
+            let units = self.bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+            for unit in units {
+                bytes.extend(if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() });
+            }
+            return Ok(bytes);
+        }
+        
+        let (encoded, _, had_errors) = encoding.encode(text);
+        if had_errors {
+            return Err(SpellCheckerError::InvalidEncoding);
+        }
+        
+        let mut bytes = Vec::with_capacity(encoded.len() + 3);
+        if self.bom && encoding == UTF_8 {
+            bytes.extend_from_slice(b"\xEF\xBB\xBF");
+        }
+        bytes.extend_from_slice(&encoded);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn windows_1252_file_decodes_and_encodes_back() {
+        let bytes = b"caf\xe9 au lait";
+        let encoding = FileEncoding::detect(bytes);
+        assert_eq!(encoding.name, "windows-1252");
+        assert!(!encoding.bom);
+        
+        let text = encoding.decode(bytes).unwrap();
+        assert_eq!(text, "café au lait");
+        assert_eq!(encoding.encode(&text).unwrap(), bytes);
+        assert!(encoding.encode("日本").is_err());
+    }
+    
+    #[test]
+    fn cyrillic_and_shift_jis_files_are_detected() {
+        let (bytes, _, _) = encoding_rs::WINDOWS_1251.encode("Привет, как твои дела? Это простой русский текст.");
+        let encoding = FileEncoding::detect(&bytes);
+        assert_eq!(encoding.name, "windows-1251");
+        assert_eq!(encoding.decode(&bytes).unwrap(), "Привет, как твои дела? Это простой русский текст.");
+        
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode("これは日本語のテキストです。今日はいい天気ですね。");
+        let encoding = FileEncoding::detect(&bytes);
+        assert_eq!(encoding.name, "Shift_JIS");
+        assert_eq!(encoding.decode(&bytes).unwrap(), "これは日本語のテキストです。今日はいい天気ですね。");
+    }
+}
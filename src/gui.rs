@@ -1,20 +1,36 @@
 use crate::checker::{DocumentAnalysis, SpellChecker};
-use crate::editor::TextEditor;
+use crate::dictionary::{ImportMode, LoadState};
+use crate::editor::{ContextAction, TextEditor};
+use crate::encoding::FileEncoding;
+use crate::history::{CheckHistory, EditHistory};
 use crate::language::{Language, LanguageManager};
+use crate::lookup::{DefinitionProvider, UrlLookupProvider};
+use crate::notifications::{NotificationLevel, Notifications};
+use crate::report::ReportFormat;
+use crate::session::SessionState;
+use crate::settings::CheckerSettings;
 use crate::sidebar::Sidebar;
 use crate::theme::AtomTheme;
+use crate::util::is_probably_binary;
 use crate::{open_repository, open_sponsor_page};
 use eframe::egui;
 use rfd::FileDialog;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use std::time::Instant;
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct AppState {
+    // The document is restored through `config.last_session` rather than
+    // stored with the rest of the state
+    #[serde(skip)]
     pub current_file: Option<PathBuf>,
+    #[serde(skip)]
     pub document_content: String,
+    #[serde(skip)]
     pub is_document_modified: bool,
     pub auto_check: bool,
     pub show_line_numbers: bool,
@@ -26,12 +42,23 @@ pub struct AppState {
     pub font_size: f32,
     pub wrap_text: bool,
     pub show_whitespace: bool,
+    pub tab_width: usize,
     pub last_directory: Option<PathBuf>,
     pub sidebar_state: Sidebar,
     pub show_about: bool,
-    pub show_settings: bool,
+    pub show_preferences: bool,
     pub enable_syntax_highlighting: bool,
     pub check_interval_ms: u64,
+    pub watch_dictionaries: bool,
+    /// Keep the accuracy trend in `history.json` between sessions
+    pub persist_check_history: bool,
+    /// How the current file was encoded, so saving writes it back the same way
+    pub file_encoding: FileEncoding,
+    /// Target word count for the open document
+    pub word_goal: Option<usize>,
+    /// Word goals of documents by path, restored when they're reopened
+    pub word_goals: HashMap<PathBuf, usize>,
+    pub config: crate::Config,
 }
 
 impl Default for AppState {
@@ -50,36 +77,160 @@ impl Default for AppState {
             font_size: 14.0,
             wrap_text: true,
             show_whitespace: false,
+            tab_width: 4,
             last_directory: None,
             sidebar_state: Sidebar::new(),
             show_about: false,
-            show_settings: false,
+            show_preferences: false,
             enable_syntax_highlighting: true,
             check_interval_ms: 1500,
+            watch_dictionaries: true,
+            persist_check_history: false,
+            file_encoding: FileEncoding::default(),
+            word_goal: None,
+            word_goals: HashMap::new(),
+            config: crate::Config::default(),
         }
     }
 }
 
+impl AppState {
+    /// Move `path` to the front of the recent files, dropping the oldest
+    /// beyond `Config::max_recent_files`
+    fn push_recent_file(&mut self, path: PathBuf) {
+        self.recent_files.retain(|p| *p != path);
+        self.recent_files.insert(0, path);
+        self.recent_files.truncate(self.config.max_recent_files);
+    }
+    
+    /// Put the preferences back to their defaults, keeping the open
+    /// document, its word goal and the theme
+    fn reset_preferences(&mut self) {
+        *self = AppState {
+            current_file: self.current_file.take(),
+            document_content: std::mem::take(&mut self.document_content),
+            is_document_modified: self.is_document_modified,
+            recent_files: std::mem::take(&mut self.recent_files),
+            selected_language: self.selected_language,
+            last_directory: self.last_directory.take(),
+            sidebar_state: self.sidebar_state.clone(),
+            theme: self.theme,
+            file_encoding: std::mem::take(&mut self.file_encoding),
+            word_goal: self.word_goal,
+            word_goals: std::mem::take(&mut self.word_goals),
+            show_preferences: true,
+            ..AppState::default()
+        };
+    }
+}
+
 pub struct SpellCheckerApp {
     state: AppState,
     text_editor: TextEditor,
-    spell_checker: Arc<std::sync::Mutex<SpellChecker>>,
-    last_check_time: Instant,
-    check_interval: std::time::Duration,
+    spell_checker: Arc<std::sync::RwLock<SpellChecker>>,
+    check_debounce: CheckDebounce,
     is_dragging_file: bool,
     drop_highlight: bool,
     stats: CheckStats,
     language_manager: LanguageManager,
     analysis: Option<DocumentAnalysis>,
     pending_add_word: Option<String>,
+    pending_remove_word: Option<String>,
+    pending_add_acronym: Option<String>,
+    pending_remove_acronym: Option<String>,
+    pending_learn_document: bool,
     pending_ignore_word: Option<String>,
+    pending_ignore_in_document: Option<String>,
     pending_replace: Option<(String, String)>,
+    /// `(start, end, original, replacement)` edits from the last analysis
+    pending_fix_all: Option<Vec<(usize, usize, String, String)>>,
     pending_import_dict: bool,
     pending_export_dict: bool,
+    pending_export_user_words: bool,
     pending_clear_ignored: bool,
+    pending_export_report: Option<ReportFormat>,
     last_spell_check: Option<DocumentAnalysis>,
-    show_notification: Option<(String, egui::Color32)>,
-    notification_timer: Instant,
+    notifications: Notifications,
+    dictionary_reloads: Option<Receiver<Language>>,
+    history: EditHistory,
+    check_history: CheckHistory,
+    definition_provider: Box<dyn DefinitionProvider>,
+    project_dictionary_dir: Option<PathBuf>,
+    check_sender: Sender<CheckResult>,
+    check_receiver: Receiver<CheckResult>,
+    /// Bumped for every background check; only the latest one's result is used
+    check_generation: u64,
+    checking: bool,
+    /// Whether the current language's dictionary was still loading last frame
+    loading_dictionary: bool,
+    /// The title last sent to the window, so it's only resent on change
+    window_title: String,
+    /// Auto-detection was turned off by picking one of the candidates it
+    /// was unsure between; it comes back on when another file is opened
+    detection_overridden: bool,
+    /// Whether each language has a dictionary file, for the language pickers
+    dictionary_availability: HashMap<Language, bool>,
+    /// A file to open once the user agrees to discard unsaved changes
+    pending_discard: Option<PathBuf>,
+}
+
+/// Flagged words kept per check, so opening a binary or minified file
+/// doesn't produce an analysis too big to list or underline
+const MAX_ERRORS_SHOWN: usize = 1000;
+
+/// Documents shorter than this are too short to judge their language, so
+/// an unsure detection doesn't prompt for one
+const MIN_WORDS_FOR_LANGUAGE_PROMPT: usize = 20;
+
+/// A finished background check, tagged with the generation it was started for
+struct CheckResult {
+    generation: u64,
+    analysis: DocumentAnalysis,
+    duration: std::time::Duration,
+}
+
+/// Decides when auto-check runs while the user types: once edits have
+/// paused for the debounce delay, or after `max_wait` of continuous typing
+/// so a long burst still gets checked now and then
+struct CheckDebounce {
+    delay: std::time::Duration,
+    max_wait: std::time::Duration,
+    /// The first and the latest edit since the last check
+    pending: Option<(Instant, Instant)>,
+}
+
+impl CheckDebounce {
+    fn new(state: &AppState) -> Self {
+        let mut debounce = Self {
+            delay: std::time::Duration::ZERO,
+            max_wait: std::time::Duration::ZERO,
+            pending: None,
+        };
+        debounce.configure(state);
+        debounce
+    }
+    
+    fn configure(&mut self, state: &AppState) {
+        self.delay = std::time::Duration::from_millis(state.config.auto_check_debounce_ms);
+        self.max_wait = std::time::Duration::from_millis(state.check_interval_ms);
+    }
+    
+    fn edited(&mut self, now: Instant) {
+        let first = self.pending.map_or(now, |(first, _)| first);
+        self.pending = Some((first, now));
+    }
+    
+    /// Whether there are unchecked edits and it's time to check them
+    fn should_check(&self, now: Instant) -> bool {
+        self.pending.is_some_and(|(first, last)| {
+            now.saturating_duration_since(last) >= self.delay
+                || now.saturating_duration_since(first) >= self.max_wait
+        })
+    }
+    
+    fn checked(&mut self) {
+        self.pending = None;
+    }
 }
 
 #[derive(Default)]
@@ -88,6 +239,8 @@ struct CheckStats {
     errors: usize,
     last_check_duration: std::time::Duration,
     detected_language: Option<Language>,
+    /// Candidate languages from the last detection with their confidence
+    detection_scores: Vec<(Language, f32)>,
     check_count: usize,
     total_characters: usize,
     total_lines: usize,
@@ -103,75 +256,282 @@ impl SpellCheckerApp {
         
         let language_manager = LanguageManager::new();
         
-        let spell_checker = match SpellChecker::new(state.selected_language) {
-            Ok(checker) => Arc::new(std::sync::Mutex::new(checker)),
-            Err(e) => {
-                eprintln!("Failed to create spell checker: {}", e);
-                Arc::new(std::sync::Mutex::new(
-                    SpellChecker::new(Language::English).unwrap()
-                ))
-            }
-        };
+        // The dictionary loads in the background so the window opens at once
+        let mut spell_checker = SpellChecker::new_in_background(state.selected_language);
+        spell_checker.set_max_errors(Some(MAX_ERRORS_SHOWN));
+        let data_dir = spell_checker.data_dir().to_path_buf();
+        let spell_checker = Arc::new(std::sync::RwLock::new(spell_checker));
         
         let mut text_editor = TextEditor::new();
         text_editor.set_font_size(state.font_size);
         text_editor.set_wrap_lines(state.wrap_text);
         text_editor.set_show_whitespace(state.show_whitespace);
+        text_editor.set_tab_width(state.tab_width);
+        text_editor.set_theme(state.theme);
         
-        Self {
+        let (check_sender, check_receiver) = mpsc::channel();
+        
+        let mut app = Self {
             state: state.clone(),
             text_editor,
             spell_checker,
-            last_check_time: Instant::now(),
-            check_interval: std::time::Duration::from_millis(state.check_interval_ms),
+            check_debounce: CheckDebounce::new(&state),
             is_dragging_file: false,
             drop_highlight: false,
             stats: CheckStats::default(),
             language_manager,
             analysis: None,
             pending_add_word: None,
+            pending_remove_word: None,
+            pending_add_acronym: None,
+            pending_remove_acronym: None,
+            pending_learn_document: false,
             pending_ignore_word: None,
+            pending_ignore_in_document: None,
             pending_replace: None,
+            pending_fix_all: None,
             pending_import_dict: false,
             pending_export_dict: false,
+            pending_export_user_words: false,
             pending_clear_ignored: false,
+            pending_export_report: None,
             last_spell_check: None,
-            show_notification: None,
-            notification_timer: Instant::now(),
+            notifications: Notifications::default(),
+            dictionary_reloads: None,
+            history: EditHistory::default(),
+            check_history: if state.persist_check_history { CheckHistory::load(&data_dir) } else { CheckHistory::default() },
+            definition_provider: Box::new(UrlLookupProvider::default()),
+            project_dictionary_dir: None,
+            check_sender,
+            check_receiver,
+            check_generation: 0,
+            checking: false,
+            loading_dictionary: true,
+            window_title: String::new(),
+            detection_overridden: false,
+            dictionary_availability: HashMap::new(),
+            pending_discard: None,
+        };
+        
+        {
+            let mut checker = app.spell_checker.write().unwrap();
+            checker.set_advanced_typo_detection(app.state.config.enable_advanced_typo_detection);
+            checker.set_capture_context(true);
+        }
+        app.apply_dictionary_watch();
+        app.load_project_dictionary();
+        app.refresh_dictionary_availability();
+        app.restore_session();
+        app.history.settle(&app.state.document_content);
+        app
+    }
+    
+    /// Reopen the document the last session left open, with its unsaved
+    /// changes if it had any
+    fn restore_session(&mut self) {
+        let Some(session) = self.state.config.last_session.clone() else {
+            return;
+        };
+        
+        if let Some(content) = session.unsaved_content() {
+            self.state.current_file = session.file.clone();
+            self.state.document_content = content;
+            self.state.is_document_modified = true;
+            self.load_project_dictionary();
+            self.check_spelling();
+        } else if let Some(path) = session.file.clone() {
+            if let Err(e) = self.open_file(path.clone()) {
+                self.notify_warning(format!("Could not reopen '{}': {}", path.display(), e));
+                return;
+            }
+        }
+        
+        if let Some((line, column)) = session.cursor {
+            self.text_editor.set_cursor(line, column);
+        }
+    }
+    
+    /// Open `path`, asking first if that would discard unsaved changes
+    fn request_open(&mut self, path: PathBuf) {
+        if self.state.is_document_modified {
+            self.pending_discard = Some(path);
+        } else if let Err(e) = self.open_file(path) {
+            self.notify_error(format!("Failed to open file: {}", e));
+        }
+    }
+    
+    fn refresh_dictionary_availability(&mut self) {
+        let availability = self.spell_checker.read().unwrap().language_availability();
+        self.dictionary_availability = availability.into_iter().collect();
+    }
+    
+    fn has_dictionary(&self, language: Language) -> bool {
+        self.dictionary_availability.get(&language).copied().unwrap_or(true)
+    }
+    
+    /// Start or stop watching dictionary files to match the settings
+    fn apply_dictionary_watch(&mut self) {
+        if !self.state.watch_dictionaries {
+            self.spell_checker.read().unwrap().unwatch_dictionaries();
+            self.dictionary_reloads = None;
+        } else if self.dictionary_reloads.is_none() {
+            let result = self.spell_checker.read().unwrap().watch_dictionaries();
+            match result {
+                Ok(receiver) => self.dictionary_reloads = Some(receiver),
+                Err(e) => self.notify_warning(format!("Could not watch dictionary files: {}", e)),
+            }
         }
     }
     
+    /// Pick up user data and check the document once a dictionary loading
+    /// in the background is ready
+    fn handle_dictionary_loading(&mut self) {
+        let (language, state) = {
+            let checker = self.spell_checker.read().unwrap();
+            (checker.current_language(), checker.dictionary_state())
+        };
+        
+        let was_loading = std::mem::replace(&mut self.loading_dictionary, state == LoadState::Loading);
+        if !was_loading {
+            return;
+        }
+        
+        match state {
+            LoadState::Loaded => {
+                self.spell_checker.write().unwrap().dictionary_reloaded(language);
+                self.check_spelling();
+            }
+            LoadState::Failed(e) => {
+                self.notify_error(format!("Could not load {} dictionary: {}", language.name(), e));
+            }
+            LoadState::Loading | LoadState::NotLoaded => {}
+        }
+    }
+    
+    fn handle_dictionary_reloads(&mut self) {
+        let reloaded: Vec<Language> = match &self.dictionary_reloads {
+            Some(receiver) => receiver.try_iter().collect(),
+            None => return,
+        };
+        
+        if reloaded.is_empty() {
+            return;
+        }
+        
+        {
+            let mut checker = self.spell_checker.write().unwrap();
+            for language in &reloaded {
+                checker.dictionary_reloaded(*language);
+            }
+        }
+        
+        self.check_spelling();
+        self.notify_info("Dictionary reloaded from disk".to_string());
+    }
+    
+    /// Start checking the document on a background thread. The result is
+    /// picked up by `poll_check_results`; starting another check first
+    /// supersedes this one.
     fn check_spelling(&mut self) {
+        self.check_debounce.checked();
         if !self.state.auto_check || self.state.document_content.trim().is_empty() {
             return;
         }
         
-        let start_time = Instant::now();
-        
         let language_to_use = if self.state.auto_detect_language {
             let detected = self.language_manager.detect_language(&self.state.document_content);
             self.stats.detected_language = Some(detected);
+            self.stats.detection_scores = self.language_manager.detect_with_confidence(&self.state.document_content);
             detected
         } else {
             self.state.selected_language
         };
         
-        if language_to_use != self.spell_checker.lock().unwrap().current_language() {
-            if let Ok(mut checker) = self.spell_checker.lock() {
-                if checker.set_language(language_to_use).is_ok() {
-                    self.state.selected_language = language_to_use;
-                }
+        if language_to_use != self.spell_checker.read().unwrap().current_language() {
+            if let Ok(mut checker) = self.spell_checker.write() {
+                checker.set_language_in_background(language_to_use);
+                self.state.selected_language = language_to_use;
             }
         }
         
+        // `handle_dictionary_loading` checks again once the dictionary is ready
+        if self.spell_checker.read().unwrap().dictionary_state() == LoadState::Loading {
+            self.loading_dictionary = true;
+            return;
+        }
+        
+        let filename = self.state.current_file
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .map(str::to_string);
+        
+        self.check_generation += 1;
+        let generation = self.check_generation;
+        // A clone, so the lock isn't held while the check runs
+        let checker = self.spell_checker.read().unwrap().clone();
+        let sender = self.check_sender.clone();
+        let content = self.state.document_content.clone();
+        std::thread::spawn(move || {
+            let start_time = Instant::now();
+            let analysis = checker.check_document_with_context(&content, filename.as_deref());
+            // The receiver is gone only if the app has closed
+            let _ = sender.send(CheckResult { generation, analysis, duration: start_time.elapsed() });
+        });
+        
+        self.checking = true;
+        self.stats.total_characters = self.state.document_content.chars().count();
+        self.stats.total_lines = self.state.document_content.lines().count();
+    }
+    
+    /// Apply the result of the latest background check, dropping any from
+    /// checks it superseded
+    fn poll_check_results(&mut self) {
+        let Some(result) = self.check_receiver
+            .try_iter()
+            .filter(|result| result.generation == self.check_generation)
+            .last()
+        else {
+            return;
+        };
+        
+        let analysis = result.analysis;
+        self.analysis = Some(analysis.clone());
+        self.stats.total_words = analysis.total_words;
+        self.stats.errors = analysis.misspelled_words;
+        self.stats.last_check_duration = result.duration;
+        self.stats.check_count += 1;
+        self.check_history.record(&analysis);
+        
+        self.text_editor.set_analysis(analysis.clone());
+        self.last_spell_check = Some(analysis);
+        self.checking = false;
+    }
+    
+    /// Re-check only the selected text and merge the result into the
+    /// current analysis, or use it as the analysis if there is none yet
+    fn check_selection(&mut self) {
+        let Some((start, end)) = self.text_editor.selection_range() else {
+            return;
+        };
+        
+        let start_time = Instant::now();
         let filename = self.state.current_file
             .as_ref()
             .and_then(|p| p.file_name())
             .and_then(|n| n.to_str());
         
-        let analysis = {
-            let checker = self.spell_checker.lock().unwrap();
-            checker.check_document(&self.state.document_content, filename)
+        let range = {
+            let checker = self.spell_checker.read().unwrap();
+            checker.check_range(&self.state.document_content, start, end, filename)
+        };
+        
+        let analysis = match self.analysis.take() {
+            Some(mut analysis) => {
+                analysis.merge_range(&self.state.document_content, start, end, range);
+                analysis
+            }
+            None => range,
         };
         
         self.analysis = Some(analysis.clone());
@@ -179,36 +539,78 @@ impl SpellCheckerApp {
         self.stats.errors = analysis.misspelled_words;
         self.stats.last_check_duration = start_time.elapsed();
         self.stats.check_count += 1;
-        self.stats.total_characters = self.state.document_content.chars().count();
-        self.stats.total_lines = self.state.document_content.lines().count();
         
         self.text_editor.set_analysis(analysis.clone());
         self.last_spell_check = Some(analysis);
-        self.last_check_time = Instant::now();
+    }
+    
+    /// Pick up the project dictionary for the current file's directory,
+    /// unless it is the one already loaded
+    fn load_project_dictionary(&mut self) {
+        let Some(dir) = self.state.current_file.as_ref().and_then(|p| p.parent()) else {
+            return;
+        };
+        if self.project_dictionary_dir.as_deref() == Some(dir) {
+            return;
+        }
+        self.project_dictionary_dir = Some(dir.to_path_buf());
+        
+        let result = {
+            let mut checker = self.spell_checker.write().unwrap();
+            checker.load_project_dictionary(dir)
+        };
+        
+        match result {
+            Ok(Some(path)) => self.notify_info(format!("Using project dictionary {}", path.display())),
+            Ok(None) => {}
+            Err(e) => self.notify_error(format!("Failed to load project dictionary: {}", e)),
+        }
+    }
+    
+    /// Store the open document's word goal under its path
+    fn remember_word_goal(&mut self) {
+        let Some(path) = self.state.current_file.clone() else {
+            return;
+        };
+        match self.state.word_goal {
+            Some(goal) => self.state.word_goals.insert(path, goal),
+            None => self.state.word_goals.remove(&path),
+        };
     }
     
     fn open_file(&mut self, path: PathBuf) -> anyhow::Result<()> {
-        let content = std::fs::read_to_string(&path)?;
+        let bytes = std::fs::read(&path)?;
+        // UTF-16 is full of NULs, so only other encodings get the binary check
+        let encoding = FileEncoding::detect(&bytes);
+        if !encoding.is_utf16() && is_probably_binary(&bytes) {
+            self.notify_warning(format!("'{}' doesn't look like a text file", path.display()));
+            return Ok(());
+        }
+        let content = encoding.decode(&bytes)?;
+        self.state.file_encoding = encoding;
+        self.state.word_goal = self.state.word_goals.get(&path).copied();
+        self.spell_checker.write().unwrap().reset_document_scope();
         self.state.current_file = Some(path.clone());
         self.state.document_content = content;
+        self.history.clear();
+        self.history.settle(&self.state.document_content);
         self.state.is_document_modified = false;
         
         if let Some(parent) = path.parent() {
             self.state.last_directory = Some(parent.to_path_buf());
         }
+        self.load_project_dictionary();
         
-        if !self.state.recent_files.contains(&path) {
-            self.state.recent_files.insert(0, path);
-            if self.state.recent_files.len() > 10 {
-                self.state.recent_files.pop();
-            }
-        }
+        self.state.push_recent_file(path);
         
+        if std::mem::take(&mut self.detection_overridden) {
+            self.state.auto_detect_language = true;
+        }
         if self.state.auto_detect_language {
             let detected = self.language_manager.detect_language(&self.state.document_content);
             self.state.selected_language = detected;
-            if let Ok(mut checker) = self.spell_checker.lock() {
-                let _ = checker.set_language(detected);
+            if let Ok(mut checker) = self.spell_checker.write() {
+                checker.set_language_in_background(detected);
             }
         }
         
@@ -219,9 +621,9 @@ impl SpellCheckerApp {
     
     fn save_file(&mut self) -> anyhow::Result<()> {
         if let Some(path) = &self.state.current_file {
-            std::fs::write(path, &self.state.document_content)?;
+            std::fs::write(path, self.state.file_encoding.encode(&self.state.document_content)?)?;
             self.state.is_document_modified = false;
-            self.show_notification("File saved successfully".to_string(), egui::Color32::GREEN);
+            self.notify_info("File saved successfully".to_string());
         } else {
             self.save_as()?;
         }
@@ -242,94 +644,237 @@ impl SpellCheckerApp {
             .set_directory(self.state.last_directory.clone().unwrap_or_else(|| PathBuf::from(".")))
             .save_file()
         {
-            std::fs::write(&path, &self.state.document_content)?;
+            std::fs::write(&path, self.state.file_encoding.encode(&self.state.document_content)?)?;
             self.state.current_file = Some(path);
+            self.remember_word_goal();
             self.state.is_document_modified = false;
-            self.show_notification("File saved successfully".to_string(), egui::Color32::GREEN);
+            self.load_project_dictionary();
+            self.check_spelling();
+            self.notify_info("File saved successfully".to_string());
         }
         Ok(())
     }
     
-    fn show_notification(&mut self, message: String, color: egui::Color32) {
-        self.show_notification = Some((message, color));
-        self.notification_timer = Instant::now();
+    fn notify(&mut self, level: NotificationLevel, message: String) {
+        self.notifications.push(level, message, Instant::now());
     }
     
-    fn handle_file_drop(&mut self, ctx: &egui::Context) {
-        if !ctx.input(|i| i.raw.hovered_files.is_empty()) {
-            self.is_dragging_file = true;
-        } else {
-            self.is_dragging_file = false;
+    fn notify_info(&mut self, message: String) {
+        self.notify(NotificationLevel::Info, message);
+    }
+    
+    fn notify_warning(&mut self, message: String) {
+        self.notify(NotificationLevel::Warning, message);
+    }
+    
+    fn notify_error(&mut self, message: String) {
+        self.notify(NotificationLevel::Error, message);
+    }
+    
+    fn undo(&mut self) {
+        if let Some(previous) = self.history.undo(&self.state.document_content) {
+            self.state.document_content = previous;
+            self.state.is_document_modified = true;
+            self.check_spelling();
         }
+    }
+    
+    fn redo(&mut self) {
+        if let Some(next) = self.history.redo(&self.state.document_content) {
+            self.state.document_content = next;
+            self.state.is_document_modified = true;
+            self.check_spelling();
+        }
+    }
+    
+    /// Ctrl+Z / Ctrl+Y step through our own history while it has entries;
+    /// otherwise the keys are left for the text widget's undo of typing
+    /// that hasn't been settled yet
+    fn handle_history_shortcuts(&mut self, ctx: &egui::Context) {
+        let undo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+        let redo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y);
+        let redo_shift = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z);
         
-        if ctx.input(|i| i.raw.dropped_files.len() > 0) {
-            if let Some(file) = ctx.input(|i| i.raw.dropped_files[0].path.clone()) {
-                if let Err(e) = self.open_file(file) {
-                    self.show_notification(format!("Failed to open file: {}", e), egui::Color32::RED);
-                }
+        // Check the shifted shortcut first: consuming Ctrl+Z also matches
+        // Ctrl+Shift+Z
+        if self.history.can_redo()
+            && ctx.input_mut(|i| i.consume_shortcut(&redo_shift) || i.consume_shortcut(&redo))
+        {
+            self.redo();
+        } else if self.history.can_undo() && ctx.input_mut(|i| !i.modifiers.shift && i.consume_shortcut(&undo)) {
+            self.undo();
+        }
+    }
+    
+    /// F8 / Shift+F8 move to the next / previous error in the sidebar list
+    /// and scroll the editor to it; Ctrl+. applies its first suggestion
+    fn handle_error_navigation(&mut self, ctx: &egui::Context) {
+        let Some(analysis) = &self.analysis else {
+            return;
+        };
+        
+        let previous = egui::KeyboardShortcut::new(egui::Modifiers::SHIFT, egui::Key::F8);
+        let next = egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F8);
+        let apply = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Period);
+        
+        let sidebar = &mut self.state.sidebar_state;
+        let error_count = sidebar.filtered_errors(analysis).len();
+        
+        // Shift+F8 first: consuming plain F8 would also match it
+        let step = ctx.input_mut(|i| {
+            if i.consume_shortcut(&previous) {
+                Some(false)
+            } else if i.consume_shortcut(&next) {
+                Some(true)
+            } else {
+                None
+            }
+        });
+        
+        if let Some(forward) = step {
+            sidebar.step_selected_error(error_count, forward);
+            if let Some(word) = sidebar.filtered_errors(analysis).get(sidebar.selected_error_index) {
+                self.text_editor.scroll_to_line(word.line);
             }
-            self.drop_highlight = false;
         }
         
-        if ctx.input(|i| i.pointer.any_down()) && self.is_dragging_file {
-            self.drop_highlight = true;
-        } else {
+        if ctx.input_mut(|i| i.consume_shortcut(&apply)) {
+            if let Some(edit) = sidebar.selected_error_fix(analysis) {
+                self.pending_fix_all = Some(vec![edit]);
+            }
+        }
+    }
+    
+    fn handle_file_drop(&mut self, ctx: &egui::Context) {
+        self.is_dragging_file = !ctx.input(|i| i.raw.hovered_files.is_empty());
+        
+        if ctx.input(|i| !i.raw.dropped_files.is_empty()) {
+            if let Some(file) = ctx.input(|i| i.raw.dropped_files[0].path.clone()) {
+                self.request_open(file);
+            }
             self.drop_highlight = false;
         }
+        
+        self.drop_highlight = ctx.input(|i| i.pointer.any_down()) && self.is_dragging_file;
     }
     
     fn handle_pending_actions(&mut self) {
         // Create a scope to drop the mutex guard before showing notifications
         if let Some(word) = self.pending_add_word.take() {
             let result = {
-                let mut checker = self.spell_checker.lock().unwrap();
+                let mut checker = self.spell_checker.write().unwrap();
                 checker.add_word_to_dictionary(&word)
             };
             
             if result.is_ok() {
-                self.show_notification(format!("Added '{}' to dictionary", word), egui::Color32::GREEN);
+                self.notify_info(format!("Added '{}' to dictionary", word));
+            }
+            self.check_spelling();
+        }
+        
+        if let Some(word) = self.pending_remove_word.take() {
+            let result = {
+                let mut checker = self.spell_checker.write().unwrap();
+                checker.remove_word_from_dictionary(&word)
+            };
+            
+            match result {
+                Ok(true) => self.notify_info(format!("Removed '{}' from dictionary", word)),
+                Ok(false) => {}
+                Err(e) => self.notify_error(format!("Failed to remove '{}': {}", word, e)),
+            }
+            self.check_spelling();
+        }
+        
+        if std::mem::take(&mut self.pending_learn_document) {
+            if let Some(analysis) = self.analysis.clone() {
+                let result = self.spell_checker.write().unwrap().learn_document(&analysis);
+                match result {
+                    Ok(count) => self.notify_info(format!("Learned {} words", count)),
+                    Err(e) => self.notify_error(format!("Failed to learn words: {}", e)),
+                }
+                self.check_spelling();
+            }
+        }
+        
+        if let Some(acronym) = self.pending_add_acronym.take() {
+            let result = self.spell_checker.write().unwrap().add_acronym(&acronym);
+            match result {
+                Ok(true) => self.notify_info(format!("Added acronym '{}'", acronym.trim().to_uppercase())),
+                Ok(false) => {}
+                Err(e) => self.notify_error(format!("Failed to save acronym: {}", e)),
+            }
+            self.check_spelling();
+        }
+        
+        if let Some(acronym) = self.pending_remove_acronym.take() {
+            let result = self.spell_checker.write().unwrap().remove_acronym(&acronym);
+            match result {
+                Ok(true) => self.notify_info(format!("Removed acronym '{}'", acronym)),
+                Ok(false) => {}
+                Err(e) => self.notify_error(format!("Failed to save acronym: {}", e)),
             }
             self.check_spelling();
         }
         
         if let Some(word) = self.pending_ignore_word.take() {
             let result = {
-                let mut checker = self.spell_checker.lock().unwrap();
+                let mut checker = self.spell_checker.write().unwrap();
                 checker.ignore_word(&word)
             };
             
             if result.is_ok() {
-                self.show_notification(format!("Ignored '{}' for this session", word), egui::Color32::YELLOW);
+                self.notify_warning(format!("Ignored '{}' for this session", word));
             }
             self.check_spelling();
         }
         
+        if let Some(word) = self.pending_ignore_in_document.take() {
+            self.spell_checker.write().unwrap().ignore_word_in_document(&word);
+            self.notify_info(format!("Ignored '{}' in this file", word));
+            self.check_spelling();
+        }
+        
         if let Some((find, replace)) = self.pending_replace.take() {
-            if !find.is_empty() {
+            if !find.is_empty() && self.state.document_content.contains(&find) {
+                self.history.push(self.state.document_content.clone());
                 self.state.document_content = self.state.document_content.replace(&find, &replace);
+                self.history.settle(&self.state.document_content);
                 self.state.is_document_modified = true;
                 self.check_spelling();
-                self.show_notification(format!("Replaced '{}' with '{}'", find, replace), egui::Color32::GREEN);
+                self.notify_info(format!("Replaced '{}' with '{}'", find, replace));
+            }
+        }
+        
+        if let Some(edits) = self.pending_fix_all.take() {
+            let before = self.state.document_content.clone();
+            let applied = apply_checked_edits(&mut self.state.document_content, &edits);
+            if applied > 0 {
+                self.history.push(before);
+                self.history.settle(&self.state.document_content);
+                self.state.is_document_modified = true;
+                self.check_spelling();
+                self.notify_info(format!("Fixed {} errors", applied));
             }
         }
         
         if self.pending_import_dict {
             self.pending_import_dict = false;
             if let Some(path) = FileDialog::new()
-                .add_filter("Dictionary files", &["txt", "dict"])
+                .add_filter("Dictionary files", &["txt", "dict", "dic"])
                 .set_directory(self.state.last_directory.clone().unwrap_or_else(|| PathBuf::from(".")))
                 .pick_file()
             {
                 let result = {
-                    let mut checker = self.spell_checker.lock().unwrap();
-                    checker.import_dictionary(&path)
+                    let mut checker = self.spell_checker.write().unwrap();
+                    checker.import_dictionary(&path, ImportMode::Merge)
                 };
                 
-                if let Err(e) = result {
-                    self.show_notification(format!("Failed to import: {}", e), egui::Color32::RED);
-                } else {
-                    self.show_notification("Dictionary imported successfully".to_string(), egui::Color32::GREEN);
+                match result {
+                    Ok(added) => self.notify_info(format!("Dictionary imported: {} new words", added)),
+                    Err(e) => self.notify_error(format!("Failed to import: {}", e)),
                 }
+                self.refresh_dictionary_availability();
                 self.check_spelling();
             }
         }
@@ -339,19 +884,61 @@ impl SpellCheckerApp {
             let default_name = format!("dictionary_{}.txt", self.state.selected_language.code());
             if let Some(path) = FileDialog::new()
                 .add_filter("Text files", &["txt"])
+                .add_filter("Hunspell dictionaries", &["dic"])
                 .set_file_name(&default_name)
                 .set_directory(self.state.last_directory.clone().unwrap_or_else(|| PathBuf::from(".")))
                 .save_file()
             {
                 let result = {
-                    let checker = self.spell_checker.lock().unwrap();
+                    let checker = self.spell_checker.read().unwrap();
                     checker.export_dictionary(&path)
                 };
                 
                 if let Err(e) = result {
-                    self.show_notification(format!("Failed to export: {}", e), egui::Color32::RED);
+                    self.notify_error(format!("Failed to export: {}", e));
+                } else {
+                    self.notify_info("Dictionary exported successfully".to_string());
+                }
+            }
+        }
+        
+        if self.pending_export_user_words {
+            self.pending_export_user_words = false;
+            let default_name = format!("user_words_{}.txt", self.state.selected_language.code());
+            if let Some(path) = FileDialog::new()
+                .add_filter("Text files", &["txt"])
+                .add_filter("CSV files", &["csv"])
+                .set_file_name(&default_name)
+                .set_directory(self.state.last_directory.clone().unwrap_or_else(|| PathBuf::from(".")))
+                .save_file()
+            {
+                let result = {
+                    let checker = self.spell_checker.read().unwrap();
+                    checker.export_user_words(&path)
+                };
+                
+                if let Err(e) = result {
+                    self.notify_error(format!("Failed to export: {}", e));
                 } else {
-                    self.show_notification("Dictionary exported successfully".to_string(), egui::Color32::GREEN);
+                    self.notify_info("User words exported successfully".to_string());
+                }
+            }
+        }
+        
+        if let Some(format) = self.pending_export_report.take() {
+            if let Some(analysis) = &self.analysis {
+                let report = analysis.to_report(format);
+                if let Some(path) = FileDialog::new()
+                    .add_filter(format.name(), &[format.extension()])
+                    .set_file_name(format!("spellcheck_report.{}", format.extension()))
+                    .set_directory(self.state.last_directory.clone().unwrap_or_else(|| PathBuf::from(".")))
+                    .save_file()
+                {
+                    if let Err(e) = std::fs::write(&path, report) {
+                        self.notify_error(format!("Failed to export report: {}", e));
+                    } else {
+                        self.notify_info("Report exported successfully".to_string());
+                    }
                 }
             }
         }
@@ -359,11 +946,43 @@ impl SpellCheckerApp {
         if self.pending_clear_ignored {
             self.pending_clear_ignored = false;
             {
-                let mut checker = self.spell_checker.lock().unwrap();
+                let mut checker = self.spell_checker.write().unwrap();
                 checker.clear_ignored_words();
             }
             self.check_spelling();
-            self.show_notification("Cleared ignored words".to_string(), egui::Color32::GREEN);
+            self.notify_info("Cleared ignored words".to_string());
+        }
+    }
+    
+    /// Ask whether to drop unsaved changes before opening another file
+    fn show_discard_dialog(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.pending_discard.clone() else {
+            return;
+        };
+        
+        let mut answered = false;
+        egui::Window::new("Unsaved Changes")
+            .resizable(false)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("The current document has unsaved changes.");
+                ui.label(format!("Discard them and open '{}'?", path.display()));
+                ui.horizontal(|ui| {
+                    if ui.button("Discard").clicked() {
+                        if let Err(e) = self.open_file(path.clone()) {
+                            self.notify_error(format!("Failed to open file: {}", e));
+                        }
+                        answered = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        answered = true;
+                    }
+                });
+            });
+        
+        if answered {
+            self.pending_discard = None;
         }
     }
     
@@ -412,56 +1031,175 @@ impl SpellCheckerApp {
         self.state.show_about = show_about;
     }
     
-    fn show_settings_dialog(&mut self, ctx: &egui::Context) {
-        let mut show_settings = self.state.show_settings;
+    /// Preferences apply as they are changed; checker settings are written
+    /// to `checker.toml` when the window closes
+    fn show_preferences_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.state.show_preferences;
+        let mut close_requested = false;
+        let mut recheck = false;
         
-        egui::Window::new("Settings")
-            .open(&mut show_settings)
+        let saved_settings = self.spell_checker.read().unwrap().current_settings();
+        let language_case_default = self.spell_checker.read().unwrap().current_language().default_case_sensitive();
+        let mut checker_settings = saved_settings.clone();
+        
+        egui::Window::new("Preferences")
+            .open(&mut open)
+            .collapsible(false)
             .resizable(true)
             .default_width(500.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ctx, |ui| {
-                ui.heading("Application Settings");
-                ui.separator();
+                ui.heading("Editor");
+                egui::Grid::new("preferences_editor")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Font size:");
+                        if ui.add(egui::Slider::new(&mut self.state.font_size, 8.0..=36.0).step_by(1.0)).changed() {
+                            self.text_editor.set_font_size(self.state.font_size);
+                        }
+                        ui.end_row();
+                        
+                        ui.label("Wrap text:");
+                        if ui.checkbox(&mut self.state.wrap_text, "").changed() {
+                            self.text_editor.set_wrap_lines(self.state.wrap_text);
+                        }
+                        ui.end_row();
+                        
+                        ui.label("Show line numbers:");
+                        ui.checkbox(&mut self.state.show_line_numbers, "");
+                        ui.end_row();
+                        
+                        ui.label("Show whitespace:");
+                        ui.checkbox(&mut self.state.show_whitespace, "");
+                        ui.end_row();
+                        
+                        ui.label("Tab width:");
+                        ui.add(egui::DragValue::new(&mut self.state.tab_width)
+                            .clamp_range(1..=8));
+                        ui.end_row();
+                        
+                        ui.label("Syntax highlighting:");
+                        ui.checkbox(&mut self.state.enable_syntax_highlighting, "");
+                        ui.end_row();
+                    });
                 
-                egui::Grid::new("settings_grid")
+                ui.add_space(8.0);
+                ui.heading("Checking");
+                egui::Grid::new("preferences_checking")
                     .num_columns(2)
-                    .spacing([20.0, 10.0])
+                    .spacing([20.0, 8.0])
                     .striped(true)
                     .show(ui, |ui| {
                         ui.label("Auto-check:");
                         ui.checkbox(&mut self.state.auto_check, "");
                         ui.end_row();
                         
-                        ui.label("Check interval (ms):");
-                        ui.add(egui::DragValue::new(&mut self.state.check_interval_ms)
+                        ui.label("Check after typing pauses for:");
+                        if ui.add(egui::DragValue::new(&mut self.state.config.auto_check_debounce_ms)
+                            .clamp_range(100..=5000)
+                            .suffix("ms"))
+                            .changed()
+                        {
+                            self.check_debounce.configure(&self.state);
+                        }
+                        ui.end_row();
+                        
+                        ui.label("Check while typing every:");
+                        if ui.add(egui::DragValue::new(&mut self.state.check_interval_ms)
                             .clamp_range(500..=10000)
-                            .suffix("ms"));
+                            .suffix("ms"))
+                            .on_hover_text("Longest wait for a check when typing doesn't pause")
+                            .changed()
+                        {
+                            self.check_debounce.configure(&self.state);
+                        }
                         ui.end_row();
                         
                         ui.label("Auto-detect language:");
                         ui.checkbox(&mut self.state.auto_detect_language, "");
                         ui.end_row();
                         
-                        ui.label("Show line numbers:");
-                        ui.checkbox(&mut self.state.show_line_numbers, "");
+                        ui.label("Confidence threshold:");
+                        let threshold = ui.add(egui::Slider::new(&mut checker_settings.confidence_threshold, 0.0..=1.0));
+                        recheck |= settled(&threshold);
                         ui.end_row();
                         
-                        ui.label("Wrap text:");
-                        ui.checkbox(&mut self.state.wrap_text, "");
+                        ui.label("Max suggestions:");
+                        let max_suggestions = ui.add(egui::Slider::new(&mut checker_settings.max_suggestions, 0..=20));
+                        recheck |= settled(&max_suggestions);
                         ui.end_row();
                         
-                        ui.label("Show whitespace:");
-                        ui.checkbox(&mut self.state.show_whitespace, "");
+                        ui.label("Show suggestions:");
+                        recheck |= ui.checkbox(&mut checker_settings.suggestions_enabled, "").changed();
                         ui.end_row();
                         
-                        ui.label("Syntax highlighting:");
-                        ui.checkbox(&mut self.state.enable_syntax_highlighting, "");
+                        ui.label("Case sensitive:");
+                        let mut case_sensitive = checker_settings.case_sensitive.unwrap_or(language_case_default);
+                        if ui.checkbox(&mut case_sensitive, "")
+                            .on_hover_text("Defaults to the usual setting for the language")
+                            .changed()
+                        {
+                            checker_settings.case_sensitive = Some(case_sensitive);
+                            recheck = true;
+                        }
                         ui.end_row();
                         
-                        ui.label("Font size:");
-                        ui.add(egui::DragValue::new(&mut self.state.font_size)
-                            .clamp_range(8.0..=36.0)
-                            .speed(0.5));
+                        ui.label("Flag mixed-script words:");
+                        recheck |= ui.checkbox(&mut checker_settings.detect_mixed_script, "").changed();
+                        ui.end_row();
+                        
+                        ui.label("Flag extra and trailing spaces:");
+                        recheck |= ui.checkbox(&mut checker_settings.check_whitespace, "").changed();
+                        ui.end_row();
+                        
+                        ui.label("Flag repeated words:");
+                        if ui.checkbox(&mut self.state.config.enable_advanced_typo_detection, "").changed() {
+                            self.spell_checker.write().unwrap()
+                                .set_advanced_typo_detection(self.state.config.enable_advanced_typo_detection);
+                            recheck = true;
+                        }
+                        ui.end_row();
+                    });
+                
+                ui.add_space(8.0);
+                ui.heading("Files");
+                egui::Grid::new("preferences_files")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Reload edited dictionaries:");
+                        if ui.checkbox(&mut self.state.watch_dictionaries, "").changed() {
+                            self.apply_dictionary_watch();
+                        }
+                        ui.end_row();
+                        
+                        ui.label("Keep accuracy history:");
+                        ui.checkbox(&mut self.state.persist_check_history, "");
+                        ui.end_row();
+                        
+                        ui.label("Auto-save app state:");
+                        ui.checkbox(&mut self.state.config.enable_auto_save, "");
+                        ui.end_row();
+                        
+                        ui.label("Auto-save interval (s):");
+                        ui.add_enabled(
+                            self.state.config.enable_auto_save,
+                            egui::DragValue::new(&mut self.state.config.auto_save_interval)
+                                .clamp_range(5..=600)
+                                .suffix("s"),
+                        );
+                        ui.end_row();
+                        
+                        ui.label("Recent files to keep:");
+                        if ui.add(egui::DragValue::new(&mut self.state.config.max_recent_files)
+                            .clamp_range(1..=50))
+                            .changed()
+                        {
+                            self.state.recent_files.truncate(self.state.config.max_recent_files);
+                        }
                         ui.end_row();
                     });
                 
@@ -469,27 +1207,45 @@ impl SpellCheckerApp {
                 
                 ui.horizontal(|ui| {
                     if ui.button("Reset to Defaults").clicked() {
-                        // Create a default state instead of trying to reconstruct the app
-                        self.state = AppState::default();
-                        self.text_editor.set_font_size(self.state.font_size);
-                        self.text_editor.set_wrap_lines(self.state.wrap_text);
-                        self.check_interval = std::time::Duration::from_millis(self.state.check_interval_ms);
+                        self.reset_preferences();
+                        checker_settings = CheckerSettings::default();
+                        recheck = true;
                     }
                     
-                    if ui.button("Save").clicked() {
-                        self.state.show_settings = false;
-                        self.text_editor.set_font_size(self.state.font_size);
-                        self.text_editor.set_wrap_lines(self.state.wrap_text);
-                        self.check_interval = std::time::Duration::from_millis(self.state.check_interval_ms);
-                    }
-                    
-                    if ui.button("Cancel").clicked() {
-                        self.state.show_settings = false;
+                    if ui.button("Close").clicked() {
+                        close_requested = true;
                     }
                 });
             });
-            
-        self.state.show_settings = show_settings;
+        
+        if checker_settings != saved_settings {
+            self.spell_checker.write().unwrap().apply_settings(&checker_settings);
+        }
+        if recheck {
+            self.check_spelling();
+        }
+        
+        let open = open && !close_requested;
+        if !open {
+            let data_dir = self.spell_checker.read().unwrap().data_dir().to_path_buf();
+            if let Err(e) = checker_settings.save(&data_dir) {
+                self.notify_error(format!("Failed to save preferences: {}", e));
+            }
+        }
+        self.state.show_preferences = open;
+    }
+    
+    /// Put every preference back to its default, leaving the open document
+    /// and file history alone
+    fn reset_preferences(&mut self) {
+        self.state.reset_preferences();
+        
+        self.text_editor.set_font_size(self.state.font_size);
+        self.text_editor.set_wrap_lines(self.state.wrap_text);
+        self.check_debounce.configure(&self.state);
+        self.spell_checker.write().unwrap()
+            .set_advanced_typo_detection(self.state.config.enable_advanced_typo_detection);
+        self.apply_dictionary_watch();
     }
     
     fn show_menu_bar(&mut self, ui: &mut egui::Ui) {
@@ -501,9 +1257,7 @@ impl SpellCheckerApp {
                         .set_directory(self.state.last_directory.clone().unwrap_or_else(|| PathBuf::from(".")))
                         .pick_file()
                     {
-                        if let Err(e) = self.open_file(path) {
-                            self.show_notification(format!("Failed to open file: {}", e), egui::Color32::RED);
-                        }
+                        self.request_open(path);
                     }
                     ui.close_menu();
                 }
@@ -512,14 +1266,14 @@ impl SpellCheckerApp {
                 
                 if ui.button("💾 Save").clicked() {
                     if let Err(e) = self.save_file() {
-                        self.show_notification(format!("Failed to save: {}", e), egui::Color32::RED);
+                        self.notify_error(format!("Failed to save: {}", e));
                     }
                     ui.close_menu();
                 }
                 
                 if ui.button("💾 Save As...").clicked() {
                     if let Err(e) = self.save_as() {
-                        self.show_notification(format!("Failed to save: {}", e), egui::Color32::RED);
+                        self.notify_error(format!("Failed to save: {}", e));
                     }
                     ui.close_menu();
                 }
@@ -528,18 +1282,23 @@ impl SpellCheckerApp {
                 
                 if !self.state.recent_files.is_empty() {
                     ui.menu_button("Recent Files", |ui| {
+                        // Files deleted or moved since they were opened
+                        self.state.recent_files.retain(|p| p.exists());
                         let recent_files = self.state.recent_files.clone();
                         for path in &recent_files {
                             if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
                                 if ui.button(format!("📄 {}", filename)).clicked() {
-                                    let path_clone = path.clone();
-                                    if let Err(e) = self.open_file(path_clone) {
-                                        self.show_notification(format!("Failed to open: {}", e), egui::Color32::RED);
-                                    }
+                                    self.request_open(path.clone());
                                     ui.close_menu();
                                 }
                             }
                         }
+                        
+                        ui.separator();
+                        if ui.button("🧹 Clear Recent").clicked() {
+                            self.state.recent_files.clear();
+                            ui.close_menu();
+                        }
                     });
                 }
                 
@@ -551,11 +1310,31 @@ impl SpellCheckerApp {
             });
             
             ui.menu_button("Edit", |ui| {
+                let undo = egui::Button::new("↩ Undo").shortcut_text("Ctrl+Z");
+                if ui.add_enabled(self.history.can_undo(), undo).clicked() {
+                    self.undo();
+                    ui.close_menu();
+                }
+                
+                let redo = egui::Button::new("↪ Redo").shortcut_text("Ctrl+Y");
+                if ui.add_enabled(self.history.can_redo(), redo).clicked() {
+                    self.redo();
+                    ui.close_menu();
+                }
+                
+                ui.separator();
+                
                 if ui.button("✏️ Check Spelling Now").clicked() {
                     self.check_spelling();
                     ui.close_menu();
                 }
                 
+                let has_selection = self.text_editor.selection_range().is_some();
+                if ui.add_enabled(has_selection, egui::Button::new("🔍 Check Selection")).clicked() {
+                    self.check_selection();
+                    ui.close_menu();
+                }
+                
                 ui.checkbox(&mut self.state.auto_check, "🔄 Auto-check");
                 ui.checkbox(&mut self.state.show_line_numbers, "🔢 Show Line Numbers");
                 
@@ -566,8 +1345,8 @@ impl SpellCheckerApp {
                     self.state.selected_language = detected;
                     self.state.auto_detect_language = false;
                     {
-                        let mut checker = self.spell_checker.lock().unwrap();
-                        let _ = checker.set_language(detected);
+                        let mut checker = self.spell_checker.write().unwrap();
+                        checker.set_language_in_background(detected);
                     }
                     self.check_spelling();
                     ui.close_menu();
@@ -576,7 +1355,8 @@ impl SpellCheckerApp {
                 ui.separator();
                 
                 if ui.button("🧹 Clear Document").clicked() {
-                    self.state.document_content.clear();
+                    self.history.push(std::mem::take(&mut self.state.document_content));
+                    self.history.settle("");
                     self.state.is_document_modified = true;
                     self.analysis = None;
                     ui.close_menu();
@@ -620,19 +1400,15 @@ impl SpellCheckerApp {
             
             ui.menu_button("Language", |ui| {
                 let available_languages = self.language_manager.available_languages().to_vec();
-                let mut selected_language = self.state.selected_language;
                 
                 for lang in &available_languages {
-                    if ui.selectable_value(
-                        &mut selected_language,
-                        *lang,
-                        format!("{} {}", lang.flag_emoji(), lang.name()),
-                    ).clicked() {
-                        self.state.selected_language = selected_language;
+                    let selected = self.state.selected_language == *lang;
+                    if language_option(ui, *lang, selected, self.has_dictionary(*lang)).clicked() {
+                        self.state.selected_language = *lang;
                         self.state.auto_detect_language = false;
                         {
-                            let mut checker = self.spell_checker.lock().unwrap();
-                            let _ = checker.set_language(*lang);
+                            let mut checker = self.spell_checker.write().unwrap();
+                            checker.set_language_in_background(*lang);
                         }
                         self.check_spelling();
                         ui.close_menu();
@@ -645,8 +1421,8 @@ impl SpellCheckerApp {
             });
             
             ui.menu_button("Tools", |ui| {
-                if ui.button("⚙️ Settings").clicked() {
-                    self.state.show_settings = true;
+                if ui.button("⚙️ Preferences...").clicked() {
+                    self.state.show_preferences = true;
                     ui.close_menu();
                 }
                 
@@ -704,6 +1480,18 @@ impl SpellCheckerApp {
         });
     }
     
+    fn window_title(&self) -> String {
+        window_title(self.state.current_file.as_deref(), self.state.is_document_modified)
+    }
+    
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let title = self.window_title();
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+    }
+    
     fn show_language_selection(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.label("🌍");
@@ -716,21 +1504,11 @@ impl SpellCheckerApp {
                 ))
                 .show_ui(ui, |ui| {
                     let available_languages = self.language_manager.available_languages().to_vec();
-                    let mut selected_language = self.state.selected_language;
                     
                     for lang in &available_languages {
-                        if ui.selectable_value(
-                            &mut selected_language,
-                            *lang,
-                            format!("{} {}", lang.flag_emoji(), lang.name()),
-                        ).clicked() {
-                            self.state.selected_language = selected_language;
-                            self.state.auto_detect_language = false;
-                            {
-                                let mut checker = self.spell_checker.lock().unwrap();
-                                let _ = checker.set_language(*lang);
-                            }
-                            self.check_spelling();
+                        let selected = self.state.selected_language == *lang;
+                        if language_option(ui, *lang, selected, self.has_dictionary(*lang)).clicked() {
+                            self.select_language(*lang);
                         }
                     }
                 });
@@ -739,7 +1517,18 @@ impl SpellCheckerApp {
         });
     }
     
-    fn show_status_bar(&self, ui: &mut egui::Ui) {
+    /// Switch to `language` chosen by the user, turning off auto-detection
+    fn select_language(&mut self, language: Language) {
+        self.state.selected_language = language;
+        self.state.auto_detect_language = false;
+        {
+            let mut checker = self.spell_checker.write().unwrap();
+            checker.set_language_in_background(language);
+        }
+        self.check_spelling();
+    }
+    
+    fn show_status_bar(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             if let Some(path) = &self.state.current_file {
                 let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("Untitled");
@@ -760,10 +1549,15 @@ impl SpellCheckerApp {
                 ui.label(format!("🔤 Chars: {}", self.stats.total_characters));
                 
                 if self.stats.errors > 0 {
-                    ui.colored_label(
-                        egui::Color32::RED,
-                        format!("❌ Errors: {}", self.stats.errors),
+                    let errors = ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(format!("❌ Errors: {}", self.stats.errors)).color(egui::Color32::RED),
+                        )
+                        .sense(egui::Sense::click()),
                     );
+                    if errors.on_hover_text("Show errors").clicked() {
+                        self.state.sidebar_state.open_errors_tab();
+                    }
                 } else if self.stats.total_words > 0 {
                     ui.colored_label(
                         egui::Color32::GREEN,
@@ -771,90 +1565,166 @@ impl SpellCheckerApp {
                     );
                 }
                 
-                if self.stats.last_check_duration.as_millis() > 0 {
+                if self.loading_dictionary {
+                    ui.spinner();
+                    ui.label("Loading dictionary…");
+                } else if self.checking {
+                    ui.spinner();
+                    ui.label("Checking...");
+                } else if self.stats.last_check_duration.as_millis() > 0 {
                     ui.label(format!("⚡ {}ms", self.stats.last_check_duration.as_millis()));
                 }
             });
         });
         
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if let Some((line, column)) = self.text_editor.cursor_position() {
+                ui.label(format!("Ln {}, Col {}", line, column));
+            }
+            
+            let encoding = &self.state.file_encoding;
+            ui.label(if encoding.bom { format!("{} with BOM", encoding.name) } else { encoding.name.clone() });
+            
+            let language = self.stats.detected_language
+                .filter(|_| self.state.auto_detect_language)
+                .unwrap_or(self.state.selected_language);
+            ui.menu_button(format!("{} {}", language.flag_emoji(), language.name()), |ui| {
+                for lang in self.language_manager.available_languages().to_vec() {
+                    let selected = lang == self.state.selected_language;
+                    if language_option(ui, lang, selected, self.has_dictionary(lang)).clicked() {
+                        self.select_language(lang);
+                        ui.close_menu();
+                    }
+                }
+            });
+            
             if self.state.auto_check {
                 ui.colored_label(egui::Color32::GREEN, "🔄 Auto");
             }
             
             let word_count = {
-                let checker = self.spell_checker.lock().unwrap();
+                let checker = self.spell_checker.read().unwrap();
                 checker.word_count()
             };
             ui.label(format!("📚 Dict: {}", word_count));
             
             if self.state.auto_detect_language {
-                if let Some(detected) = self.stats.detected_language {
-                    if detected != self.state.selected_language {
-                        ui.colored_label(
-                            egui::Color32::LIGHT_BLUE,
-                            format!("🌐 ({})", detected.name()),
-                        );
+                self.show_detection_status(ui);
+            }
+        });
+    }
+    
+    /// The detected language and how sure detection is. When it's unsure
+    /// about a document long enough to judge, the candidates are offered
+    /// to pick from instead.
+    fn show_detection_status(&mut self, ui: &mut egui::Ui) {
+        let long_enough = self.stats.total_words >= MIN_WORDS_FOR_LANGUAGE_PROMPT;
+        let scores = self.stats.detection_scores.clone();
+        let Some(&(detected, confidence)) = scores.first() else {
+            if long_enough {
+                ui.colored_label(egui::Color32::YELLOW, "🌐 Language unknown")
+                    .on_hover_text("Pick the language from the language menu");
+            }
+            return;
+        };
+        
+        ui.horizontal(|ui| {
+            if LanguageManager::is_low_confidence(&scores) && long_enough {
+                ui.colored_label(egui::Color32::YELLOW, "🌐 Which language?");
+                for (language, score) in scores {
+                    let label = format!("{} {} {:.0}%", language.flag_emoji(), language.name(), score);
+                    if ui.small_button(label).on_hover_text("Check this file in this language").clicked() {
+                        self.select_language(language);
+                        self.detection_overridden = true;
                     }
                 }
+            } else {
+                let candidates: Vec<String> = scores
+                    .iter()
+                    .map(|(language, score)| format!("{}: {:.0}%", language.name(), score))
+                    .collect();
+                ui.colored_label(egui::Color32::LIGHT_BLUE, format!("🌐 {} {:.0}%", detected.name(), confidence))
+                    .on_hover_text(format!("Detected language\n{}", candidates.join("\n")));
             }
         });
     }
     
-    fn show_notification_overlay(&self, ui: &mut egui::Ui) {
-        if let Some((message, color)) = &self.show_notification {
-            if self.notification_timer.elapsed() < std::time::Duration::from_secs(3) {
-                let rect = ui.max_rect();
-                let notification_rect = egui::Rect::from_center_size(
-                    egui::pos2(rect.center().x, rect.top() + 50.0),
-                    egui::vec2(300.0, 40.0),
-                );
-                
-                ui.painter().rect_filled(
-                    notification_rect,
-                    10.0,
-                    ui.visuals().panel_fill,
-                );
-                
-                ui.painter().rect_stroke(
-                    notification_rect,
-                    10.0,
-                    egui::Stroke::new(1.0, *color),
-                );
-                
-                ui.painter().text(
-                    notification_rect.center(),
-                    egui::Align2::CENTER_CENTER,
-                    message,
-                    egui::FontId::proportional(14.0),
-                    *color,
-                );
-            }
+    /// Stack the toasts in the top right corner, newest at the bottom.
+    /// Each fades out as it expires and can be closed early.
+    fn show_notifications(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        self.notifications.prune(now);
+        if self.notifications.is_empty() {
+            return;
+        }
+        
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("notifications"))
+            .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-12.0, 40.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (index, notification) in self.notifications.iter().enumerate() {
+                    let color = notification.level.color();
+                    ui.scope(|ui| {
+                        ui.set_opacity(notification.opacity(now));
+                        egui::Frame::popup(ui.style())
+                            .stroke(egui::Stroke::new(1.0, color))
+                            .show(ui, |ui| {
+                                ui.set_max_width(320.0);
+                                ui.horizontal(|ui| {
+                                    ui.colored_label(color, notification.level.icon());
+                                    ui.add(egui::Label::new(&notification.message).wrap(true));
+                                    if ui.small_button("✕").clicked() {
+                                        dismissed = Some(index);
+                                    }
+                                });
+                            });
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+        
+        if let Some(index) = dismissed {
+            self.notifications.dismiss(index);
         }
     }
     
     fn show_main_content(&mut self, ui: &mut egui::Ui) {
+        let word_goal = self.state.word_goal;
         if self.state.sidebar_state.visible {
             egui::SidePanel::left("sidebar")
                 .resizable(true)
                 .default_width(self.state.sidebar_width)
                 .width_range(200.0..=500.0)
                 .show_inside(ui, |ui| {
-                    let checker = self.spell_checker.lock().unwrap();
+                    let checker = self.spell_checker.read().unwrap();
                     self.state.sidebar_state.show(
                         ui,
                         &checker,
                         &self.analysis,
+                        self.check_generation,
                         &self.state.document_content,
+                        &self.check_history,
                         &mut self.pending_add_word,
                         &mut self.pending_ignore_word,
                         &mut self.pending_replace,
+                        &mut self.pending_fix_all,
                         &mut self.pending_import_dict,
                         &mut self.pending_export_dict,
+                        &mut self.pending_export_user_words,
                         &mut self.pending_clear_ignored,
+                        &mut self.pending_export_report,
+                        &mut self.pending_remove_word,
+                        &mut self.pending_add_acronym,
+                        &mut self.pending_remove_acronym,
+                        &mut self.pending_learn_document,
+                        &mut self.state.word_goal,
                     );
                 });
         }
+        if self.state.word_goal != word_goal {
+            self.remember_word_goal();
+        }
         
         egui::CentralPanel::default().show_inside(ui, |ui| {
             if self.drop_highlight {
@@ -879,29 +1749,55 @@ impl SpellCheckerApp {
                 &self.analysis,
             );
             
-            if editor_response.changed && self.state.auto_check {
-                self.check_spelling();
+            if editor_response.changed {
+                self.check_debounce.edited(Instant::now());
             }
             
-            self.show_notification_overlay(ui);
+            match self.text_editor.take_context_action() {
+                Some(ContextAction::Replace { start, end, original, replacement }) => {
+                    self.pending_fix_all = Some(vec![(start, end, original, replacement)]);
+                }
+                Some(ContextAction::AddWord(word)) => self.pending_add_word = Some(word),
+                Some(ContextAction::IgnoreWord(word)) => self.pending_ignore_word = Some(word),
+                Some(ContextAction::IgnoreInDocument(word)) => self.pending_ignore_in_document = Some(word),
+                Some(ContextAction::LookUp(word)) => {
+                    let language = self.spell_checker.read().unwrap().current_language();
+                    if let Some(definition) = self.definition_provider.define(&word, language) {
+                        self.notify_info(format!("{}: {}", word, definition));
+                    }
+                }
+                None => {}
+            }
         });
     }
 }
 
 impl eframe::App for SpellCheckerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_check_results();
         self.handle_pending_actions();
+        self.handle_dictionary_loading();
+        self.handle_dictionary_reloads();
         self.handle_file_drop(ctx);
+        self.handle_history_shortcuts(ctx);
+        self.handle_error_navigation(ctx);
         self.state.theme.apply(ctx);
+        self.text_editor.set_theme(self.state.theme);
+        self.text_editor.set_language(self.state.selected_language);
+        self.text_editor.set_syntax_highlighting(self.state.enable_syntax_highlighting);
+        self.text_editor.set_show_whitespace(self.state.show_whitespace);
+        self.text_editor.set_tab_width(self.state.tab_width);
         
         if self.state.show_about {
             self.show_about_dialog(ctx);
         }
         
-        if self.state.show_settings {
-            self.show_settings_dialog(ctx);
+        if self.state.show_preferences {
+            self.show_preferences_dialog(ctx);
         }
         
+        self.show_discard_dialog(ctx);
+        
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             self.show_menu_bar(ui);
         });
@@ -915,22 +1811,207 @@ impl eframe::App for SpellCheckerApp {
             self.show_main_content(ui);
         });
         
-        if self.state.auto_check && self.last_check_time.elapsed() > self.check_interval {
-            self.check_spelling();
+        self.show_notifications(ctx);
+        
+        // A pause in typing settles it into the edit history, so undoing
+        // past it doesn't skip over it. A check started while an earlier
+        // one is still running supersedes it.
+        if self.check_debounce.should_check(Instant::now()) {
+            self.history.settle(&self.state.document_content);
+            if self.state.auto_check {
+                self.check_spelling();
+            }
         }
         
+        self.update_window_title(ctx);
         ctx.request_repaint();
     }
     
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let data_dir = self.spell_checker.read().unwrap().data_dir().to_path_buf();
+        let session = SessionState::capture(
+            &data_dir,
+            self.state.current_file.as_deref(),
+            &self.state.document_content,
+            self.state.is_document_modified,
+            self.text_editor.cursor_position(),
+        );
+        match session {
+            Ok(session) => self.state.config.last_session = session,
+            Err(e) => self.notify_error(format!("Could not save session: {}", e)),
+        }
         eframe::set_value(storage, eframe::APP_KEY, &self.state);
+        if self.state.persist_check_history {
+            if let Err(e) = self.check_history.save(&data_dir) {
+                self.notify_error(format!("Could not save check history: {}", e));
+            }
+        }
     }
     
     fn auto_save_interval(&self) -> std::time::Duration {
-        std::time::Duration::from_secs(30)
+        if self.state.config.enable_auto_save {
+            std::time::Duration::from_secs(self.state.config.auto_save_interval)
+        } else {
+            std::time::Duration::MAX
+        }
     }
     
     fn persist_egui_memory(&self) -> bool {
         true
     }
-}
\ No newline at end of file
+}
+
+/// An entry in a language picker, greyed out with a hint when the
+/// language has no dictionary installed
+fn language_option(ui: &mut egui::Ui, language: Language, selected: bool, has_dictionary: bool) -> egui::Response {
+    let label = format!("{} {}", language.flag_emoji(), language.name());
+    if has_dictionary {
+        return ui.selectable_label(selected, label);
+    }
+    ui.selectable_label(selected, egui::RichText::new(label).weak())
+        .on_hover_text("No dictionary installed. Download a word list for this language, or add one with Import Dictionary.")
+}
+
+/// "• notes.md — AtomSpell", with the dot only while there are unsaved
+/// changes and "Untitled" before the document is saved
+fn window_title(file: Option<&Path>, modified: bool) -> String {
+    let filename = file
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled");
+    let modified = if modified { "• " } else { "" };
+    format!("{}{} — {}", modified, filename, crate::APP_NAME)
+}
+
+/// Whether a slider or drag value has finished changing: released after a
+/// drag, or changed by a click or the keyboard
+fn settled(response: &egui::Response) -> bool {
+    response.drag_stopped() || (response.changed() && !response.dragged())
+}
+
+/// Apply `(start, end, original, replacement)` edits, sorted by `start`,
+/// to `content`. Checks run in the background, so the text may have changed
+/// since the edits were made; any whose range no longer holds `original`
+/// is skipped. Returns how many were applied.
+fn apply_checked_edits(content: &mut String, edits: &[(usize, usize, String, String)]) -> usize {
+    // Apply from the end backwards so earlier offsets stay valid
+    let mut applied = 0;
+    for (start, end, original, replacement) in edits.iter().rev() {
+        if content.get(*start..*end) == Some(original.as_str()) {
+            content.replace_range(*start..*end, replacement);
+            applied += 1;
+        }
+    }
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    
+    #[test]
+    fn window_title_shows_the_file_and_unsaved_changes() {
+        let file = Path::new("/home/user/notes.md");
+        assert_eq!(window_title(Some(file), false), format!("notes.md — {}", crate::APP_NAME));
+        assert_eq!(window_title(Some(file), true), format!("• notes.md — {}", crate::APP_NAME));
+        assert_eq!(window_title(None, false), format!("Untitled — {}", crate::APP_NAME));
+        assert_eq!(window_title(None, true), format!("• Untitled — {}", crate::APP_NAME));
+    }
+    
+    #[test]
+    fn recent_files_keep_the_newest_up_to_the_limit() {
+        let mut state = AppState::default();
+        state.config.max_recent_files = 3;
+        for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+            state.push_recent_file(PathBuf::from(name));
+        }
+        assert_eq!(state.recent_files, ["d.txt", "c.txt", "b.txt"].map(PathBuf::from));
+        
+        // Reopening a file moves it to the front instead of listing it twice
+        state.push_recent_file(PathBuf::from("b.txt"));
+        assert_eq!(state.recent_files, ["b.txt", "d.txt", "c.txt"].map(PathBuf::from));
+    }
+    
+    #[test]
+    fn resetting_preferences_keeps_the_document() {
+        let mut state = AppState {
+            current_file: Some(PathBuf::from("essay.md")),
+            document_content: "Draft text".to_string(),
+            is_document_modified: true,
+            file_encoding: FileEncoding::detect(b"caf\xe9"),
+            word_goal: Some(500),
+            word_goals: HashMap::from([(PathBuf::from("essay.md"), 500)]),
+            font_size: 20.0,
+            tab_width: 8,
+            ..AppState::default()
+        };
+        
+        state.reset_preferences();
+        assert_eq!(state.current_file, Some(PathBuf::from("essay.md")));
+        assert_eq!(state.document_content, "Draft text");
+        assert!(state.is_document_modified);
+        assert_eq!(state.file_encoding.name, "windows-1252");
+        assert_eq!(state.word_goal, Some(500));
+        assert_eq!(state.word_goals.get(Path::new("essay.md")), Some(&500));
+        assert_eq!(state.font_size, AppState::default().font_size);
+        assert_eq!(state.tab_width, AppState::default().tab_width);
+    }
+    
+    #[test]
+    fn edits_are_skipped_once_the_text_has_moved() {
+        let edit = |start, end, original: &str, replacement: &str| (start, end, original.to_string(), replacement.to_string());
+        let edits = [edit(0, 3, "teh", "the"), edit(8, 11, "dgo", "dog")];
+        
+        let mut content = "teh big dgo".to_string();
+        assert_eq!(apply_checked_edits(&mut content, &edits), 2);
+        assert_eq!(content, "the big dog");
+        
+        // Typing at the start shifted the second word after the check ran
+        let mut content = "teh very big dgo".to_string();
+        assert_eq!(apply_checked_edits(&mut content, &edits), 1);
+        assert_eq!(content, "the very big dgo");
+    }
+    
+    #[test]
+    fn applying_the_selected_fix_checks_the_word_is_still_there() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.enable_suggestions(true);
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("The cat sta");
+        
+        let fix = Sidebar::new().selected_error_fix(&analysis).unwrap();
+        assert_eq!(fix, (8, 11, "sta".to_string(), "sat".to_string()));
+        
+        let mut content = "The cat sta".to_string();
+        assert_eq!(apply_checked_edits(&mut content, std::slice::from_ref(&fix)), 1);
+        assert_eq!(content, "The cat sat");
+        
+        // Text typed before the next check moves the word out from under the fix
+        let mut content = "The fat cat sta".to_string();
+        assert_eq!(apply_checked_edits(&mut content, &[fix]), 0);
+        assert_eq!(content, "The fat cat sta");
+    }
+    
+    #[test]
+    fn debounce_waits_for_a_pause_but_not_past_the_interval() {
+        let ms = std::time::Duration::from_millis;
+        let mut debounce = CheckDebounce { delay: ms(300), max_wait: ms(2000), pending: None };
+        let start = Instant::now();
+        assert!(!debounce.should_check(start));
+        
+        debounce.edited(start);
+        assert!(!debounce.should_check(start + ms(299)));
+        assert!(debounce.should_check(start + ms(300)));
+        
+        // Steady typing never pauses long enough, so the interval decides
+        for step in 1..=8 {
+            debounce.edited(start + ms(step * 250));
+        }
+        assert!(!debounce.should_check(start + ms(1999)));
+        assert!(debounce.should_check(start + ms(2000)));
+        
+        debounce.checked();
+        assert!(!debounce.should_check(start + ms(5000)));
+    }
+}
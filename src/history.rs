@@ -0,0 +1,315 @@
+use crate::checker::DocumentAnalysis;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default number of snapshots kept by `EditHistory`
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+/// Undo/redo stack of whole-document snapshots. Edits made by the app
+/// itself (replacements, fix-all, clearing) are pushed as they happen;
+/// typing is recorded at pauses by `settle`, so the two stay in one linear
+/// history.
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    undo: VecDeque<String>,
+    redo: Vec<String>,
+    capacity: usize,
+    /// The document as of the last snapshot point, if known
+    settled: Option<String>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl EditHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            capacity: capacity.max(1),
+            settled: None,
+        }
+    }
+    
+    /// Record the document as it was before a change. Typing since the
+    /// last snapshot point is recorded first, as a step of its own. Clears
+    /// the redo stack and drops the oldest snapshot once the capacity is
+    /// reached.
+    pub fn push(&mut self, snapshot: String) {
+        if let Some(settled) = self.settled.take() {
+            if settled != snapshot {
+                self.push_snapshot(settled);
+            }
+        }
+        self.push_snapshot(snapshot);
+    }
+    
+    /// Mark `current` as a snapshot point: a pause in typing, or the
+    /// document just after the app changed it. If it was typed over since
+    /// the last point, the text from then is pushed so the typing can be
+    /// undone in one step.
+    pub fn settle(&mut self, current: &str) {
+        if self.settled.as_deref() == Some(current) {
+            return;
+        }
+        if let Some(settled) = self.settled.replace(current.to_string()) {
+            self.push_snapshot(settled);
+        }
+    }
+    
+    fn push_snapshot(&mut self, snapshot: String) {
+        if self.undo.back() == Some(&snapshot) {
+            return;
+        }
+        
+        self.redo.clear();
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+    }
+    
+    /// Step back one change, counting any typing not yet settled as the
+    /// latest. `current` is kept so it can be redone.
+    pub fn undo(&mut self, current: &str) -> Option<String> {
+        self.settle(current);
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current.to_string());
+        self.settled = Some(previous.clone());
+        Some(previous)
+    }
+    
+    /// Reapply the last undone change, unless the document was typed over
+    /// since. `current` is kept so it can be undone again.
+    pub fn redo(&mut self, current: &str) -> Option<String> {
+        self.settle(current);
+        let next = self.redo.pop()?;
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(current.to_string());
+        self.settled = Some(next.clone());
+        Some(next)
+    }
+    
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+    
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.settled = None;
+    }
+}
+
+/// Default number of checks kept by `CheckHistory`
+pub const DEFAULT_CHECK_HISTORY_CAPACITY: usize = 500;
+
+/// Counts from one completed document check
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CheckRecord {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub total_words: usize,
+    pub misspelled_words: usize,
+    pub accuracy: f32,
+}
+
+/// Accuracy over a series of checks, oldest first, for showing a quality
+/// trend. Persisted as `history.json` in the data directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckHistory {
+    records: VecDeque<CheckRecord>,
+    capacity: usize,
+}
+
+impl Default for CheckHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHECK_HISTORY_CAPACITY)
+    }
+}
+
+impl CheckHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+    
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("history.json")
+    }
+    
+    /// Load the history from `history.json` in `data_dir`, starting empty
+    /// when the file is missing or malformed
+    pub fn load(data_dir: &Path) -> Self {
+        let content = match fs::read_to_string(Self::path(data_dir)) {
+            Ok(content) => content,
+            Err(_) => return Self::default(),
+        };
+        
+        match serde_json::from_str(&content) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("Warning: Could not parse {}: {}", Self::path(data_dir).display(), e);
+                Self::default()
+            }
+        }
+    }
+    
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let path = Self::path(data_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+    
+    /// Record a completed check. A check whose counts match the previous
+    /// record (the document hasn't changed) isn't recorded again, so
+    /// periodic re-checks don't flood the history. Returns whether a record
+    /// was added.
+    pub fn record(&mut self, analysis: &DocumentAnalysis) -> bool {
+        if let Some(last) = self.records.back() {
+            if last.total_words == analysis.total_words
+                && last.misspelled_words == analysis.misspelled_words
+            {
+                return false;
+            }
+        }
+        
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(CheckRecord {
+            timestamp,
+            total_words: analysis.total_words,
+            misspelled_words: analysis.misspelled_words,
+            accuracy: analysis.accuracy,
+        });
+        true
+    }
+    
+    pub fn records(&self) -> impl Iterator<Item = &CheckRecord> {
+        self.records.iter()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+    
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    
+    #[test]
+    fn edit_history_undoes_and_redoes_snapshots() {
+        let mut history = EditHistory::new(2);
+        assert!(!history.can_undo());
+        
+        history.push("one".to_string());
+        history.push("two".to_string());
+        assert_eq!(history.undo("three").as_deref(), Some("two"));
+        assert_eq!(history.redo("two").as_deref(), Some("three"));
+        assert!(!history.can_redo());
+        
+        // A new change clears the redo stack
+        assert_eq!(history.undo("three").as_deref(), Some("two"));
+        history.push("two".to_string());
+        assert!(!history.can_redo());
+    }
+    
+    #[test]
+    fn edit_history_drops_the_oldest_snapshot_at_capacity() {
+        let mut history = EditHistory::new(2);
+        for snapshot in ["one", "two", "three"] {
+            history.push(snapshot.to_string());
+        }
+        
+        assert_eq!(history.undo("four").as_deref(), Some("three"));
+        assert_eq!(history.undo("three").as_deref(), Some("two"));
+        assert_eq!(history.undo("two"), None);
+    }
+    
+    #[test]
+    fn typing_after_an_app_edit_is_undone_before_it() {
+        let mut history = EditHistory::default();
+        history.settle("teh cat");
+        
+        // Fix All, then typing that settles at a pause
+        history.push("teh cat".to_string());
+        history.settle("the cat");
+        history.settle("the cat sat");
+        
+        assert_eq!(history.undo("the cat sat").as_deref(), Some("the cat"));
+        assert_eq!(history.undo("the cat").as_deref(), Some("teh cat"));
+        assert_eq!(history.redo("teh cat").as_deref(), Some("the cat"));
+        assert_eq!(history.redo("the cat").as_deref(), Some("the cat sat"));
+    }
+    
+    #[test]
+    fn unsettled_typing_is_undone_first() {
+        let mut history = EditHistory::default();
+        history.settle("teh cat");
+        history.push("teh cat".to_string());
+        history.settle("the cat");
+        
+        // Ctrl+Z before the typing pause still only takes back the typing
+        assert_eq!(history.undo("the cat sat").as_deref(), Some("the cat"));
+        assert_eq!(history.redo("the cat").as_deref(), Some("the cat sat"));
+        
+        // Typing after an undo drops what could have been redone
+        assert_eq!(history.undo("the cat sat").as_deref(), Some("the cat"));
+        assert_eq!(history.redo("the cat dog"), None);
+        assert_eq!(history.undo("the cat dog").as_deref(), Some("the cat"));
+    }
+    
+    #[test]
+    fn check_history_records_changes_up_to_its_capacity() {
+        let (mut checker, dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        
+        let mut history = CheckHistory::new(2);
+        assert!(history.record(&checker.check_document("The cat")));
+        // A re-check of an unchanged document isn't recorded again
+        assert!(!history.record(&checker.check_document("The cat")));
+        assert!(history.record(&checker.check_document("The blorf")));
+        assert!(history.record(&checker.check_document("The cat blorf zzyzx")));
+        
+        assert_eq!(history.len(), 2);
+        let counts: Vec<(usize, usize)> = history.records().map(|r| (r.total_words, r.misspelled_words)).collect();
+        assert_eq!(counts, vec![(2, 1), (4, 2)]);
+        
+        history.save(dir.path()).unwrap();
+        assert_eq!(CheckHistory::load(dir.path()).len(), 2);
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use once_cell::sync::Lazy;
+use unicode_script::Script;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Language {
@@ -16,6 +17,12 @@ pub enum Language {
     Russian,
     Japanese,
     Korean,
+    Dutch,
+    Swedish,
+    Polish,
+    Turkish,
+    Arabic,
+    Hebrew,
     AutoDetect,
 }
 
@@ -33,6 +40,12 @@ impl Language {
             Language::Russian,
             Language::Japanese,
             Language::Korean,
+            Language::Dutch,
+            Language::Swedish,
+            Language::Polish,
+            Language::Turkish,
+            Language::Arabic,
+            Language::Hebrew,
             Language::AutoDetect,
         ]
     }
@@ -50,10 +63,40 @@ impl Language {
             Language::Russian => "rus",
             Language::Japanese => "jpn",
             Language::Korean => "kor",
+            Language::Dutch => "nld",
+            Language::Swedish => "swe",
+            Language::Polish => "pol",
+            Language::Turkish => "tur",
+            Language::Arabic => "ara",
+            Language::Hebrew => "heb",
             Language::AutoDetect => "auto",
         }
     }
     
+    /// Two-letter ISO 639-1 code, as used in web URLs. Auto-detect falls
+    /// back to English.
+    pub fn iso_639_1(&self) -> &str {
+        match self {
+            Language::English | Language::AutoDetect => "en",
+            Language::Afrikaans => "af",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::Chinese => "zh",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Russian => "ru",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+            Language::Dutch => "nl",
+            Language::Swedish => "sv",
+            Language::Polish => "pl",
+            Language::Turkish => "tr",
+            Language::Arabic => "ar",
+            Language::Hebrew => "he",
+        }
+    }
+    
     pub fn name(&self) -> &str {
         match self {
             Language::English => "English",
@@ -67,6 +110,12 @@ impl Language {
             Language::Russian => "Russian",
             Language::Japanese => "Japanese",
             Language::Korean => "Korean",
+            Language::Dutch => "Dutch",
+            Language::Swedish => "Swedish",
+            Language::Polish => "Polish",
+            Language::Turkish => "Turkish",
+            Language::Arabic => "Arabic",
+            Language::Hebrew => "Hebrew",
             Language::AutoDetect => "Auto-detect",
         }
     }
@@ -84,10 +133,40 @@ impl Language {
             Language::Russian => "🇷🇺",
             Language::Japanese => "🇯🇵",
             Language::Korean => "🇰🇷",
+            Language::Dutch => "🇳🇱",
+            Language::Swedish => "🇸🇪",
+            Language::Polish => "🇵🇱",
+            Language::Turkish => "🇹🇷",
+            Language::Arabic => "🇸🇦",
+            Language::Hebrew => "🇮🇱",
             Language::AutoDetect => "🌐",
         }
     }
     
+    /// The script the language is mainly written in. Japanese mixes kana
+    /// with Han characters and reports Hiragana; auto-detect is `Unknown`.
+    pub fn script(&self) -> Script {
+        match self {
+            Language::Chinese => Script::Han,
+            Language::Japanese => Script::Hiragana,
+            Language::Korean => Script::Hangul,
+            Language::Russian => Script::Cyrillic,
+            Language::Arabic => Script::Arabic,
+            Language::Hebrew => Script::Hebrew,
+            Language::AutoDetect => Script::Unknown,
+            _ => Script::Latin,
+        }
+    }
+    
+    pub fn is_cjk(&self) -> bool {
+        matches!(self, Language::Chinese | Language::Japanese | Language::Korean)
+    }
+    
+    /// Whether the language is written right to left
+    pub fn is_rtl(&self) -> bool {
+        matches!(self.script(), Script::Arabic | Script::Hebrew)
+    }
+    
     pub fn dictionary_filename(&self) -> Option<String> {
         match self {
             Language::AutoDetect => None,
@@ -108,12 +187,86 @@ impl Language {
             "rus" | "ru" | "russian" => Language::Russian,
             "jpn" | "ja" | "japanese" => Language::Japanese,
             "kor" | "ko" | "korean" => Language::Korean,
+            "nld" | "nl" | "dutch" => Language::Dutch,
+            "swe" | "sv" | "swedish" => Language::Swedish,
+            "pol" | "pl" | "polish" => Language::Polish,
+            "tur" | "tr" | "turkish" => Language::Turkish,
+            "ara" | "ar" | "arabic" => Language::Arabic,
+            "heb" | "he" | "hebrew" => Language::Hebrew,
             "auto" | "autodetect" => Language::AutoDetect,
             _ => Language::English,
         }
     }
     
+    /// Whether the language writes compound nouns as one word, so an
+    /// unknown word may still be valid as dictionary words run together
+    pub fn forms_compounds(&self) -> bool {
+        matches!(self, Language::German | Language::Dutch)
+    }
+    
+    /// Whether checking should match case unless the user says otherwise.
+    /// English word lists keep names and brands in their own case
+    /// ("Paris", "iPhone"), so a wrong case is worth flagging; German
+    /// capitalizes every noun, and other word lists rarely record case.
+    pub fn default_case_sensitive(&self) -> bool {
+        matches!(self, Language::English)
+    }
+    
+    /// Contraction and possessive endings that can follow a dictionary
+    /// word ("children's", "they're"). Empty where apostrophes don't mark
+    /// them, including CJK.
+    pub fn contraction_suffixes(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => &["n't", "'s", "'re", "'ve", "'ll", "'d", "'m", "'t"],
+            Language::German | Language::Dutch | Language::Afrikaans => &["'s"],
+            _ => &[],
+        }
+    }
+    
+    /// Two-letter stems that take a contraction suffix ("it's", "we'll").
+    /// The bundled word lists leave out two-letter words, so these can't
+    /// be looked up.
+    pub fn short_contraction_stems(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => &["he", "it", "we"],
+            _ => &[],
+        }
+    }
+    
+    /// Lowercase `word` using this language's casing rules. Turkish maps
+    /// dotted `İ` to `i` and plain `I` to dotless `ı`.
+    pub fn lowercase(&self, word: &str) -> String {
+        match self {
+            Language::Turkish => word
+                .chars()
+                .flat_map(|c| match c {
+                    'İ' => 'i'.to_lowercase(),
+                    'I' => 'ı'.to_lowercase(),
+                    c => c.to_lowercase(),
+                })
+                .collect(),
+            _ => word.to_lowercase(),
+        }
+    }
+    
+    /// Up to three likely languages for `text` with their scores, best
+    /// first. Too little text to go on comes back as English.
     pub fn detect_from_text(text: &str) -> Vec<(Language, f32)> {
+        let scores = Self::detection_scores(text);
+        if !scores.is_empty() {
+            return scores;
+        }
+        
+        if text.split_whitespace().count() < 3 {
+            vec![(Language::English, 100.0)]
+        } else {
+            vec![(Language::English, 80.0)]
+        }
+    }
+    
+    /// The scores behind `detect_from_text`, with no English fallback:
+    /// empty when there are under three words or nothing scores
+    fn detection_scores(text: &str) -> Vec<(Language, f32)> {
         static COMMON_WORDS: Lazy<HashMap<Language, Vec<&'static str>>> = Lazy::new(|| {
             let mut map = HashMap::new();
             
@@ -149,6 +302,20 @@ impl Language {
                 "él", "muy", "sin", "vez", "mucho", "saber", "qué", "sobre", "mi", "alguno"
             ]);
             
+            map.insert(Language::Italian, vec![
+                "il", "di", "che", "e", "la", "per", "un", "in", "non", "una",
+                "sono", "mi", "ho", "lo", "ma", "ti", "ha", "le", "si", "con",
+                "cosa", "se", "io", "come", "da", "questo", "qui", "hai", "bene", "tu",
+                "del", "della", "gli", "nel", "anche", "quando", "loro", "dei", "era", "più"
+            ]);
+            
+            map.insert(Language::Portuguese, vec![
+                "de", "a", "o", "que", "e", "do", "da", "em", "um", "para",
+                "é", "com", "não", "uma", "os", "no", "se", "na", "por", "mais",
+                "as", "dos", "como", "mas", "foi", "ao", "ele", "das", "tem", "à",
+                "seu", "sua", "ou", "ser", "quando", "muito", "há", "nos", "já", "também"
+            ]);
+            
             map.insert(Language::German, vec![
                 "der", "die", "und", "in", "den", "von", "zu", "das", "mit", "sich",
                 "des", "auf", "für", "ist", "im", "dem", "nicht", "ein", "Die", "eine",
@@ -156,6 +323,46 @@ impl Language {
                 "nach", "wird", "bei", "einer", "Der", "um", "am", "sind", "noch", "wie"
             ]);
             
+            map.insert(Language::Dutch, vec![
+                "de", "het", "een", "en", "van", "ik", "te", "dat", "die", "in",
+                "is", "niet", "op", "zijn", "aan", "met", "voor", "er", "maar", "om",
+                "ook", "als", "dan", "nog", "bij", "wat", "naar", "hij", "zij", "geen",
+                "worden", "werd", "wij", "ze", "heeft", "deze", "uit", "wordt", "kan", "zo"
+            ]);
+            
+            map.insert(Language::Swedish, vec![
+                "och", "det", "att", "i", "en", "jag", "hon", "som", "han", "på",
+                "den", "med", "var", "sig", "för", "så", "till", "är", "men", "ett",
+                "om", "hade", "de", "av", "mig", "du", "henne", "då", "sin", "nu",
+                "har", "inte", "hans", "honom", "skulle", "hennes", "där", "min", "vi", "kan"
+            ]);
+            
+            map.insert(Language::Polish, vec![
+                "i", "w", "nie", "na", "się", "z", "że", "do", "to", "jest",
+                "jak", "o", "ale", "co", "po", "tak", "za", "od", "czy", "tylko",
+                "jego", "jej", "ich", "był", "była", "już", "przez", "może", "który", "która",
+                "są", "gdy", "mnie", "ten", "ta", "dla", "bardzo", "tym", "jako", "pan"
+            ]);
+            
+            map.insert(Language::Turkish, vec![
+                "ve", "bir", "bu", "da", "de", "için", "ile", "çok", "ne", "o",
+                "ama", "gibi", "daha", "en", "var", "yok", "ben", "sen", "biz", "siz",
+                "onlar", "şey", "kadar", "sonra", "mi", "ki", "olarak", "olan", "değil", "her",
+                "göre", "diye", "ise", "bunu", "şu", "nasıl", "neden", "çünkü", "hem", "bile"
+            ]);
+            
+            map.insert(Language::Arabic, vec![
+                "في", "من", "على", "إلى", "أن", "عن", "مع", "هذا", "هذه", "التي",
+                "الذي", "كان", "لا", "ما", "هو", "هي", "كل", "قد", "بين", "أو",
+                "ذلك", "بعد", "عند", "لم", "إن", "كانت", "حتى", "لقد", "نحن", "هم"
+            ]);
+            
+            map.insert(Language::Hebrew, vec![
+                "של", "את", "על", "לא", "הוא", "היא", "זה", "עם", "כי", "אני",
+                "גם", "מה", "יש", "אבל", "או", "כל", "אם", "היה", "הם", "אל",
+                "רק", "עוד", "אנחנו", "אין", "כמו", "זאת", "שם", "היו", "לו", "בין"
+            ]);
+            
             map
         });
         
@@ -163,27 +370,38 @@ impl Language {
         let words: Vec<&str> = text_lower.split_whitespace().collect();
         
         if words.len() < 3 {
-            return vec![(Language::English, 100.0)];
+            return Vec::new();
         }
         
+        // Raw cosine similarities are small for short inputs, so each
+        // language's n-gram score is its share of the total similarity.
+        let input_profile = trigram_profile(words.iter().take(200).copied());
+        let similarities: Vec<(Language, f32)> = TRIGRAM_PROFILES
+            .iter()
+            .map(|(language, profile)| (*language, cosine_similarity(&input_profile, profile)))
+            .collect();
+        let total_similarity: f32 = similarities.iter().map(|(_, similarity)| similarity).sum();
+        
         let mut scores = HashMap::new();
         
-        for (language, common_words) in COMMON_WORDS.iter() {
-            let mut matches = 0;
-            let mut total_checked = 0;
-            
-            for word in words.iter().take(50) {
-                total_checked += 1;
-                if common_words.contains(word) {
-                    matches += 1;
-                }
-            }
+        for (language, similarity) in similarities {
+            let stopword_score = COMMON_WORDS
+                .get(&language)
+                .map(|common_words| {
+                    let checked: Vec<&&str> = words.iter().take(50).collect();
+                    let matches = checked.iter().filter(|w| common_words.contains(w)).count();
+                    (matches as f32 / checked.len() as f32) * 100.0
+                })
+                .unwrap_or(0.0);
+            let ngram_score = if total_similarity > 0.0 {
+                similarity / total_similarity * 100.0
+            } else {
+                0.0
+            };
             
-            if total_checked > 0 {
-                let score = (matches as f32 / total_checked as f32) * 100.0;
-                if score > 10.0 {
-                    scores.insert(*language, score);
-                }
+            let score = stopword_score * STOPWORD_WEIGHT + ngram_score * NGRAM_WEIGHT;
+            if score > 10.0 {
+                scores.insert(language, score);
             }
         }
         
@@ -208,24 +426,100 @@ impl Language {
             }
         }
         
-        if scores.is_empty() {
-            scores.insert(Language::English, 80.0);
-        }
-        
         let mut sorted_scores: Vec<(Language, f32)> = scores.into_iter().collect();
-        sorted_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        // Ties go by code so the order doesn't depend on hash iteration
+        sorted_scores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.code().cmp(b.0.code())));
         
         sorted_scores.truncate(3);
         sorted_scores
     }
 }
 
+/// Relative weight of the stopword hit rate in the blended detection score.
+const STOPWORD_WEIGHT: f32 = 0.4;
+/// Relative weight of the trigram profile similarity in the blended score.
+const NGRAM_WEIGHT: f32 = 0.6;
+/// `detect_language` falls back to English when no language scores above this
+const TRUSTED_DETECTION_SCORE: f32 = 25.0;
+/// Detection confidences below this are too unsure to act on without asking
+pub const LOW_DETECTION_CONFIDENCE: f32 = 40.0;
+
+/// Reference text for each language's trigram profile. Every sample says
+/// roughly the same thing so the profiles differ by language, not by topic.
+const LANGUAGE_SAMPLES: &[(Language, &str)] = &[
+    (Language::English, "The quick brown fox jumps over the lazy dog. It was the best of times and the worst of times, when people would think about what they have and what they want. There is nothing that could stop them from working together through the night, and they knew which way the wind would blow."),
+    (Language::Afrikaans, "Die vinnige bruin jakkals spring oor die lui hond. Dit was die beste van tye en die slegste van tye, toe die mense gedink het oor wat hulle gehad het en wat hulle wou hê. Daar is niks wat hulle kan keer om die hele nag saam te werk nie, en hulle het geweet waarheen die wind sou waai."),
+    (Language::French, "Le rapide renard brun saute par-dessus le chien paresseux. C'était le meilleur des temps et le pire des temps, quand les gens pensaient à ce qu'ils avaient et à ce qu'ils voulaient. Il n'y a rien qui puisse les empêcher de travailler ensemble pendant la nuit, et ils savaient de quel côté soufflerait le vent."),
+    (Language::Spanish, "El rápido zorro marrón salta sobre el perro perezoso. Era el mejor de los tiempos y el peor de los tiempos, cuando la gente pensaba en lo que tenía y en lo que quería. No hay nada que pueda impedirles trabajar juntos durante la noche, y sabían hacia dónde soplaría el viento."),
+    (Language::German, "Der schnelle braune Fuchs springt über den faulen Hund. Es war die beste aller Zeiten und die schlechteste aller Zeiten, als die Menschen darüber nachdachten, was sie hatten und was sie wollten. Es gibt nichts, was sie davon abhalten könnte, die ganze Nacht zusammen zu arbeiten, und sie wussten, woher der Wind wehen würde."),
+    (Language::Italian, "La rapida volpe marrone salta sopra il cane pigro. Era il migliore dei tempi e il peggiore dei tempi, quando la gente pensava a quello che aveva e a quello che voleva. Non c'è niente che possa impedire loro di lavorare insieme durante la notte, e sapevano da che parte avrebbe soffiato il vento."),
+    (Language::Portuguese, "A rápida raposa marrom pula sobre o cão preguiçoso. Era o melhor dos tempos e o pior dos tempos, quando as pessoas pensavam no que tinham e no que queriam. Não há nada que possa impedi-los de trabalhar juntos durante a noite, e eles sabiam para onde o vento iria soprar."),
+    (Language::Russian, "Быстрая коричневая лиса прыгает через ленивую собаку. Это было лучшее из времён и худшее из времён, когда люди думали о том, что у них есть и чего они хотят. Нет ничего, что могло бы помешать им работать вместе всю ночь, и они знали, куда подует ветер."),
+    (Language::Dutch, "De snelle bruine vos springt over de luie hond. Het was de beste der tijden en de slechtste der tijden, toen de mensen nadachten over wat ze hadden en wat ze wilden. Er is niets dat hen kan tegenhouden om de hele nacht samen te werken, en ze wisten welke kant de wind op zou waaien."),
+    (Language::Swedish, "Den snabba bruna räven hoppar över den lata hunden. Det var den bästa av tider och den värsta av tider, när människorna tänkte på vad de hade och vad de ville ha. Det finns ingenting som kan hindra dem från att arbeta tillsammans hela natten, och de visste åt vilket håll vinden skulle blåsa."),
+    (Language::Polish, "Szybki brązowy lis przeskakuje nad leniwym psem. To były najlepsze czasy i najgorsze czasy, kiedy ludzie myśleli o tym, co mają i czego chcą. Nie ma nic, co mogłoby ich powstrzymać przed wspólną pracą przez całą noc, a oni wiedzieli, skąd będzie wiał wiatr."),
+    (Language::Turkish, "Hızlı kahverengi tilki tembel köpeğin üzerinden atlar. Zamanların en iyisi ve zamanların en kötüsüydü, insanlar neye sahip olduklarını ve ne istediklerini düşünürken. Onları bütün gece birlikte çalışmaktan alıkoyabilecek hiçbir şey yok ve rüzgarın nereden eseceğini biliyorlardı."),
+    (Language::Arabic, "الثعلب البني السريع يقفز فوق الكلب الكسول. كان أفضل الأوقات وأسوأ الأوقات، عندما كان الناس يفكرون في ما لديهم وما يريدون. لا يوجد شيء يمكن أن يمنعهم من العمل معا طوال الليل، وكانوا يعرفون من أين ستهب الريح."),
+    (Language::Hebrew, "השועל החום המהיר קופץ מעל הכלב העצלן. זה היה הטוב שבזמנים והרע שבזמנים, כשאנשים חשבו על מה שיש להם ועל מה שהם רוצים. אין שום דבר שיכול למנוע מהם לעבוד יחד כל הלילה, והם ידעו מאיפה תנשב הרוח."),
+];
+
+static TRIGRAM_PROFILES: Lazy<HashMap<Language, HashMap<String, f32>>> = Lazy::new(|| {
+    LANGUAGE_SAMPLES
+        .iter()
+        .map(|(language, sample)| {
+            let lower = sample.to_lowercase();
+            (*language, trigram_profile(lower.split_whitespace()))
+        })
+        .collect()
+});
+
+/// Character trigram frequencies of `words`, each padded with a space on
+/// both sides so word boundaries contribute their own trigrams.
+fn trigram_profile<'a>(words: impl Iterator<Item = &'a str>) -> HashMap<String, f32> {
+    let mut profile = HashMap::new();
+    
+    for word in words {
+        let letters: String = word.chars().filter(|c| c.is_alphabetic()).collect();
+        if letters.is_empty() {
+            continue;
+        }
+        
+        let padded: Vec<char> = std::iter::once(' ')
+            .chain(letters.chars())
+            .chain(std::iter::once(' '))
+            .collect();
+        for window in padded.windows(3) {
+            *profile.entry(window.iter().collect()).or_insert(0.0) += 1.0;
+        }
+    }
+    
+    profile
+}
+
+fn cosine_similarity(a: &HashMap<String, f32>, b: &HashMap<String, f32>) -> f32 {
+    let dot: f32 = a
+        .iter()
+        .filter_map(|(gram, count)| b.get(gram).map(|other| count * other))
+        .sum();
+    let norm_a = a.values().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.values().map(|v| v * v).sum::<f32>().sqrt();
+    
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageManager {
     available_languages: Vec<Language>,
     current_language: Language,
     #[serde(skip)]
     dictionary_paths: HashMap<Language, PathBuf>,
+    /// Where dictionaries and user data are looked for besides the bundled ones
+    #[serde(skip, default = "LanguageManager::system_dict_dir")]
+    data_dir: PathBuf,
 }
 
 impl Default for LanguageManager {
@@ -236,10 +530,17 @@ impl Default for LanguageManager {
 
 impl LanguageManager {
     pub fn new() -> Self {
+        Self::with_data_dir(Self::system_dict_dir())
+    }
+    
+    /// Like `new`, but with user data kept under `data_dir` instead of
+    /// `system_dict_dir`
+    pub fn with_data_dir(data_dir: impl Into<PathBuf>) -> Self {
         let mut manager = Self {
             available_languages: Language::all(),
             current_language: Language::English,
             dictionary_paths: HashMap::new(),
+            data_dir: data_dir.into(),
         };
         
         manager.scan_dictionaries();
@@ -250,7 +551,7 @@ impl LanguageManager {
         let locations = vec![
             PathBuf::from("src/dictionary"),
             PathBuf::from("dictionary"),
-            Self::system_dict_dir(),
+            self.data_dir.clone(),
             PathBuf::from("."),
         ];
         
@@ -258,13 +559,18 @@ impl LanguageManager {
             if let Ok(entries) = std::fs::read_dir(&location) {
                 for entry in entries.flatten() {
                     let path = entry.path();
-                    if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("txt") {
+                    let extension = path.extension().and_then(|s| s.to_str());
+                    if path.is_file() && matches!(extension, Some("txt") | Some("dic")) {
                         if let Some(filename) = path.file_stem().and_then(|n| n.to_str()) {
                             if let Some(lang_code) = filename
                                 .strip_prefix("dictionary(")
                                 .and_then(|s| s.strip_suffix(")"))
                             {
                                 let language = Language::from_code(lang_code);
+                                // A Hunspell list is only used when there's no plain one
+                                if extension == Some("dic") && self.dictionary_paths.contains_key(&language) {
+                                    continue;
+                                }
                                 self.dictionary_paths.insert(language, path.clone());
                             }
                         }
@@ -294,8 +600,13 @@ impl LanguageManager {
             .unwrap_or_else(|| PathBuf::from("."))
     }
     
-    pub fn user_dict_dir() -> PathBuf {
-        let mut path = Self::system_dict_dir();
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+    
+    /// The user word lists under `data_dir`, created if it's missing
+    pub fn user_dict_dir(data_dir: &Path) -> PathBuf {
+        let mut path = data_dir.to_path_buf();
         path.push("user_dictionaries");
         std::fs::create_dir_all(&path).ok();
         path
@@ -316,10 +627,18 @@ impl LanguageManager {
                         Self::dictionary_dir().join(&filename),
                         PathBuf::from("src/dictionary").join(&filename),
                         PathBuf::from("dictionary").join(&filename),
-                        Self::user_dict_dir().join(&filename),
+                        Self::user_dict_dir(&self.data_dir).join(&filename),
                     ];
                     
+                    for path in &locations {
+                        if path.exists() {
+                            return Some(path.clone());
+                        }
+                    }
+                    
+                    // Fall back to a Hunspell word list of the same name
                     for path in locations {
+                        let path = path.with_extension("dic");
                         if path.exists() {
                             return Some(path);
                         }
@@ -355,11 +674,103 @@ impl LanguageManager {
         let scores = Language::detect_from_text(text);
         
         if let Some((detected_lang, score)) = scores.first() {
-            if *score > 25.0 {
+            if *score > TRUSTED_DETECTION_SCORE {
                 return *detected_lang;
             }
         }
         
         Language::English
     }
-}
\ No newline at end of file
+    
+    /// Up to three candidate languages for `text`, most likely first, each
+    /// with a confidence out of 100. Raw detection scores rarely get near
+    /// 100 even for clear text, so confidence is a candidate's share of the
+    /// candidates' total, scaled down when even the best score is too weak
+    /// for `detect_language` to trust. Empty when no language can be told,
+    /// as for blank or very short text.
+    pub fn detect_with_confidence(&self, text: &str) -> Vec<(Language, f32)> {
+        let scores = Language::detection_scores(text);
+        let total: f32 = scores.iter().map(|(_, score)| score).sum();
+        let Some(&(_, top)) = scores.first().filter(|_| total > 0.0) else {
+            return Vec::new();
+        };
+        let trust = (top / TRUSTED_DETECTION_SCORE).min(1.0);
+        
+        scores
+            .into_iter()
+            .map(|(language, score)| (language, score / total * 100.0 * trust))
+            .collect()
+    }
+    
+    /// Whether confidences from `detect_with_confidence` are too low to
+    /// trust, so the user should be asked to pick a language
+    pub fn is_low_confidence(scores: &[(Language, f32)]) -> bool {
+        scores.first().is_some_and(|&(_, confidence)| confidence < LOW_DETECTION_CONFIDENCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn score(scores: &[(Language, f32)], language: Language) -> f32 {
+        scores.iter().find(|(l, _)| *l == language).map_or(0.0, |(_, s)| *s)
+    }
+    
+    #[test]
+    fn spanish_sentence_outranks_portuguese() {
+        let scores = Language::detect_from_text("El perro come la comida porque tiene mucha hambre hoy");
+        assert_eq!(scores[0].0, Language::Spanish);
+        assert!(score(&scores, Language::Spanish) > score(&scores, Language::Portuguese));
+    }
+    
+    #[test]
+    fn script_and_direction_follow_the_language() {
+        assert_eq!(Language::English.script(), Script::Latin);
+        assert_eq!(Language::Russian.script(), Script::Cyrillic);
+        assert_eq!(Language::Chinese.script(), Script::Han);
+        assert_eq!(Language::Arabic.script(), Script::Arabic);
+        assert_eq!(Language::Hebrew.script(), Script::Hebrew);
+        
+        assert!(Language::Arabic.is_rtl());
+        assert!(Language::Hebrew.is_rtl());
+        assert!(!Language::English.is_rtl());
+        assert!(!Language::Japanese.is_rtl());
+        assert!(!Language::AutoDetect.is_rtl());
+    }
+    
+    #[test]
+    fn dutch_swedish_polish_and_turkish_are_detected() {
+        let samples = [
+            (Language::Dutch, "De kinderen spelen in de tuin en het is een mooie dag voor iedereen"),
+            (Language::Swedish, "Barnen leker i trädgården och det är en mycket fin dag för alla"),
+            (Language::Polish, "Dzieci bawią się w ogrodzie i to jest bardzo piękny dzień dla wszystkich"),
+            (Language::Turkish, "Çocuklar bahçede oynuyor ve bu gün herkes için çok güzel bir gün"),
+        ];
+        for (language, text) in samples {
+            assert_eq!(Language::detect_from_text(text)[0].0, language, "{}", text);
+        }
+    }
+    
+    #[test]
+    fn turkish_lowercases_dotted_and_dotless_i() {
+        assert_eq!(Language::Turkish.lowercase("İSTANBUL"), "istanbul");
+        assert_eq!(Language::Turkish.lowercase("IRMAK"), "ırmak");
+        assert_eq!(Language::English.lowercase("IRMAK"), "irmak");
+    }
+    
+    #[test]
+    fn only_weak_detections_are_low_confidence() {
+        assert!(LanguageManager::is_low_confidence(&[(Language::English, 30.0), (Language::French, 20.0)]));
+        assert!(!LanguageManager::is_low_confidence(&[(Language::English, LOW_DETECTION_CONFIDENCE)]));
+        // Nothing detected is left to the caller, not treated as a weak guess
+        assert!(!LanguageManager::is_low_confidence(&[]));
+        
+        let dir = TempDir::new().unwrap();
+        let manager = LanguageManager::with_data_dir(dir.path());
+        let scores = manager.detect_with_confidence("The children are playing in the garden and it is a very nice day for everyone");
+        assert_eq!(scores[0].0, Language::English);
+        assert!(!LanguageManager::is_low_confidence(&scores));
+    }
+}
@@ -1,19 +1,52 @@
 // Core modules
 pub mod checker;
 pub mod dictionary;
+pub mod diff;
+pub mod encoding;
+pub mod history;
+pub mod language;
+pub mod lookup;
+pub mod lsp;
+pub mod report;
+pub mod rules;
+pub mod sarif;
+pub mod session;
+pub mod settings;
+pub mod suggest;
+pub mod util;
+
+// HTTP JSON API behind `spellchecker_cli serve`
+#[cfg(feature = "server")]
+pub mod server;
+
+// GUI modules; build with `--no-default-features` for the checker alone
+#[cfg(feature = "gui")]
 pub mod editor;
+#[cfg(feature = "gui")]
 pub mod gui;
-pub mod language;
+#[cfg(feature = "gui")]
+pub mod notifications;
+#[cfg(feature = "gui")]
 pub mod sidebar;
+#[cfg(feature = "gui")]
 pub mod theme;
-pub mod util;
 
 // Re-export common types for easier access
-pub use checker::{DocumentAnalysis, SpellChecker, WordCheck, WordType};
-pub use dictionary::DictionaryManager;
+pub use checker::{AlphanumericPolicy, ConfidenceWeights, DocumentAnalysis, IssueKind, Severity, SpellChecker, WordCheck, WordContext, WordExplanation, WordType};
+pub use dictionary::{BytesSource, DictStats, Dictionary, DictionaryManager, DictionarySource, FileSource, ImportMode, LoadState};
+pub use encoding::FileEncoding;
+#[cfg(feature = "gui")]
 pub use gui::SpellCheckerApp;
+pub use history::{CheckHistory, CheckRecord};
 pub use language::{Language, LanguageManager};
+pub use lookup::{DefinitionProvider, NoDefinitions, UrlLookupProvider};
+pub use rules::IgnoreRule;
+pub use session::SessionState;
+pub use settings::CheckerSettings;
+pub use suggest::{EditDistanceProvider, KeyboardLayout, SuggestionProvider};
+#[cfg(feature = "gui")]
 pub use theme::AtomTheme;
+#[cfg(feature = "gui")]
 pub use sidebar::Sidebar;
 
 // Error handling
@@ -72,13 +105,18 @@ pub fn open_repository() -> Result<()> {
 }
 
 // Global configuration
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub enable_auto_save: bool,
     pub auto_save_interval: u64,
     pub max_recent_files: usize,
     pub enable_animations: bool,
     pub enable_advanced_typo_detection: bool,
+    /// How long typing has to pause before the document is auto-checked
+    pub auto_check_debounce_ms: u64,
+    /// The document open when the app last closed, reopened on the next launch
+    pub last_session: Option<SessionState>,
 }
 
 impl Default for Config {
@@ -89,6 +127,8 @@ impl Default for Config {
             max_recent_files: 10,
             enable_animations: true,
             enable_advanced_typo_detection: true,
+            auto_check_debounce_ms: 500,
+            last_session: None,
         }
     }
 }
\ No newline at end of file
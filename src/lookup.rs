@@ -0,0 +1,85 @@
+use crate::language::Language;
+
+/// Wiktionary, in the edition for the word's language
+pub const WIKTIONARY_URL_TEMPLATE: &str = "https://{lang}.wiktionary.org/wiki/{word}";
+
+/// Looks up the meaning of a word, e.g. for a "Look up" menu item. Network
+/// providers belong in the host application; the core ships only
+/// `NoDefinitions` and the browser-based `UrlLookupProvider`.
+pub trait DefinitionProvider: Send + Sync {
+    /// A definition to show inline, or `None` if there is none or the
+    /// provider displays it some other way
+    fn define(&self, word: &str, language: Language) -> Option<String>;
+}
+
+/// Never finds a definition
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoDefinitions;
+
+impl DefinitionProvider for NoDefinitions {
+    fn define(&self, _word: &str, _language: Language) -> Option<String> {
+        None
+    }
+}
+
+/// Opens an online dictionary page for the word in the default browser.
+/// `{word}` and `{lang}` (the ISO 639-1 code) in the template are filled in.
+#[derive(Debug, Clone)]
+pub struct UrlLookupProvider {
+    template: String,
+}
+
+impl Default for UrlLookupProvider {
+    fn default() -> Self {
+        Self::new(WIKTIONARY_URL_TEMPLATE)
+    }
+}
+
+impl UrlLookupProvider {
+    pub fn new(template: impl Into<String>) -> Self {
+        Self { template: template.into() }
+    }
+    
+    pub fn lookup_url(&self, word: &str, language: Language) -> String {
+        self.template
+            .replace("{lang}", language.iso_639_1())
+            .replace("{word}", &percent_encode(word.trim()))
+    }
+}
+
+impl DefinitionProvider for UrlLookupProvider {
+    fn define(&self, word: &str, language: Language) -> Option<String> {
+        if let Err(e) = open::that(self.lookup_url(word, language)) {
+            eprintln!("Warning: Could not open dictionary page: {}", e);
+        }
+        None
+    }
+}
+
+/// Percent-encode everything but unreserved URL characters, byte by byte
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn lookup_urls_fill_in_the_language_and_encoded_word() {
+        let wiktionary = UrlLookupProvider::default();
+        assert_eq!(wiktionary.lookup_url(" colour ", Language::English), "https://en.wiktionary.org/wiki/colour");
+        assert_eq!(wiktionary.lookup_url("café au lait", Language::French), "https://fr.wiktionary.org/wiki/caf%C3%A9%20au%20lait");
+        
+        let custom = UrlLookupProvider::new("https://example.com/define?q={word}&lang={lang}");
+        assert_eq!(custom.lookup_url("a&b", Language::German), "https://example.com/define?q=a%26b&lang=de");
+    }
+}
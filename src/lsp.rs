@@ -0,0 +1,110 @@
+use crate::checker::{DocumentAnalysis, IssueKind, Severity};
+use serde::Serialize;
+
+const SOURCE: &str = "atomspell";
+/// LSP `DiagnosticSeverity` values
+const SEVERITY_ERROR: u8 = 1;
+const SEVERITY_WARNING: u8 = 2;
+const SEVERITY_HINT: u8 = 4;
+
+/// A diagnostic in the shape of the Language Server Protocol `Diagnostic`
+/// type, so editor plugins can consume it without translation
+#[derive(Debug, Clone, Serialize)]
+pub struct LspDiagnostic {
+    pub range: LspRange,
+    pub severity: u8,
+    pub code: &'static str,
+    pub source: &'static str,
+    pub message: String,
+    pub data: LspDiagnosticData,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+/// Zero-based line and UTF-16 code unit offset, as LSP expects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LspPosition {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// Replacements an editor can offer as quick-fix code actions
+#[derive(Debug, Clone, Serialize)]
+pub struct LspDiagnosticData {
+    pub suggestions: Vec<String>,
+}
+
+impl DocumentAnalysis {
+    /// Convert misspelled words to LSP diagnostics. `text` must be the text
+    /// that was checked: our byte offsets are turned into UTF-16 offsets
+    /// against its lines. Words that no longer line up with `text`
+    /// are dropped.
+    pub fn to_lsp_diagnostics(&self, text: &str) -> Vec<LspDiagnostic> {
+        let lines: Vec<&str> = text.lines().collect();
+        let line_offsets = crate::util::line_start_offsets(text);
+        
+        self.words
+            .iter()
+            .filter(|w| !w.is_correct)
+            .filter_map(|w| {
+                let line_idx = w.line.checked_sub(1)?;
+                let line = lines.get(line_idx)?;
+                let line_start = *line_offsets.get(line_idx)?;
+                let start = utf16_offset(line, w.start.checked_sub(line_start)?)?;
+                let end = utf16_offset(line, w.end.checked_sub(line_start)?)?;
+                
+                Some(LspDiagnostic {
+                    range: LspRange {
+                        start: LspPosition { line: line_idx as u32, character: start },
+                        end: LspPosition { line: line_idx as u32, character: end },
+                    },
+                    severity: match w.severity {
+                        Severity::Error => SEVERITY_ERROR,
+                        Severity::Warning => SEVERITY_WARNING,
+                        Severity::Hint => SEVERITY_HINT,
+                    },
+                    code: if w.kind == IssueKind::Whitespace { "whitespace" } else { "spelling" },
+                    source: SOURCE,
+                    message: match w.kind {
+                        IssueKind::Whitespace => w.suggestion_label(w.suggestions.first().map_or("", String::as_str)),
+                        _ => match w.note() {
+                            Some(note) => format!("Possible spelling mistake: '{}' ({})", w.original, note),
+                            None => format!("Possible spelling mistake: '{}'", w.original),
+                        },
+                    },
+                    data: LspDiagnosticData { suggestions: w.suggestions.clone() },
+                })
+            })
+            .collect()
+    }
+}
+
+/// UTF-16 length of `line` up to `byte_offset`, or `None` if the offset is
+/// past the end or not on a char boundary
+fn utf16_offset(line: &str, byte_offset: usize) -> Option<u32> {
+    line.get(..byte_offset).map(|prefix| prefix.encode_utf16().count() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checker::checker_with_words;
+    
+    #[test]
+    fn offsets_count_utf16_units_after_an_emoji() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "hi"]);
+        checker.set_confidence_threshold(0.5);
+        
+        // The emoji is four bytes but two UTF-16 code units
+        let text = "The cat\nHi 😀 cta";
+        let diagnostics = checker.check_document(text).to_lsp_diagnostics(text);
+        assert_eq!(diagnostics.len(), 1);
+        
+        let range = diagnostics[0].range;
+        assert_eq!((range.start.line, range.start.character), (1, 6));
+        assert_eq!((range.end.line, range.end.character), (1, 9));
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+/// How long an info or warning toast stays up
+const INFO_LIFETIME: Duration = Duration::from_secs(4);
+/// Errors stay up longer so there's time to read them
+const ERROR_LIFETIME: Duration = Duration::from_secs(8);
+/// Toasts fade out over the last part of their lifetime
+const FADE_DURATION: Duration = Duration::from_millis(500);
+/// Older toasts are dropped once this many are showing
+const MAX_VISIBLE: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            NotificationLevel::Info => egui::Color32::GREEN,
+            NotificationLevel::Warning => egui::Color32::YELLOW,
+            NotificationLevel::Error => egui::Color32::RED,
+        }
+    }
+    
+    pub fn icon(&self) -> &str {
+        match self {
+            NotificationLevel::Info => "ℹ",
+            NotificationLevel::Warning => "⚠",
+            NotificationLevel::Error => "❌",
+        }
+    }
+    
+    fn lifetime(&self) -> Duration {
+        match self {
+            NotificationLevel::Error => ERROR_LIFETIME,
+            _ => INFO_LIFETIME,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub level: NotificationLevel,
+    pub shown_at: Instant,
+}
+
+impl Notification {
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.shown_at) >= self.level.lifetime()
+    }
+    
+    /// 1.0 until the toast starts fading, then down to 0.0 as it expires
+    pub fn opacity(&self, now: Instant) -> f32 {
+        let remaining = self.level.lifetime().saturating_sub(now.saturating_duration_since(self.shown_at));
+        (remaining.as_secs_f32() / FADE_DURATION.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Toasts waiting to be shown, oldest first. Times are passed in rather
+/// than read from the clock so expiry doesn't depend on when frames run.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    queue: Vec<Notification>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, level: NotificationLevel, message: String, now: Instant) {
+        self.queue.push(Notification { message, level, shown_at: now });
+        if self.queue.len() > MAX_VISIBLE {
+            self.queue.remove(0);
+        }
+    }
+    
+    /// Drop the toasts that have expired by `now`
+    pub fn prune(&mut self, now: Instant) {
+        self.queue.retain(|n| !n.is_expired(now));
+    }
+    
+    /// Close the toast at `index`, as returned by `iter`
+    pub fn dismiss(&mut self, index: usize) {
+        if index < self.queue.len() {
+            self.queue.remove(index);
+        }
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = &Notification> {
+        self.queue.iter()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn prune_drops_expired_toasts() {
+        let start = Instant::now();
+        let mut notifications = Notifications::default();
+        notifications.push(NotificationLevel::Info, "Saved".to_string(), start);
+        notifications.push(NotificationLevel::Error, "Could not open file".to_string(), start);
+        
+        notifications.prune(start + Duration::from_secs(1));
+        assert_eq!(notifications.iter().count(), 2);
+        
+        // Info toasts expire before errors
+        notifications.prune(start + INFO_LIFETIME);
+        let left: Vec<_> = notifications.iter().map(|n| n.level).collect();
+        assert_eq!(left, vec![NotificationLevel::Error]);
+        
+        notifications.prune(start + ERROR_LIFETIME);
+        assert!(notifications.is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::checker::{DocumentAnalysis, WordCheck};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportFormat {
+    Text,
+    Markdown,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn name(&self) -> &str {
+        match self {
+            ReportFormat::Text => "Text",
+            ReportFormat::Markdown => "Markdown",
+            ReportFormat::Csv => "CSV",
+        }
+    }
+    
+    pub fn extension(&self) -> &str {
+        match self {
+            ReportFormat::Text => "txt",
+            ReportFormat::Markdown => "md",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl DocumentAnalysis {
+    /// Render the misspelled words as a standalone report. Text and Markdown
+    /// reports start with a summary; CSV has one row per error under a
+    /// `line,column,word,type,confidence,suggestions` header.
+    pub fn to_report(&self, format: ReportFormat) -> String {
+        let errors: Vec<&WordCheck> = self.words.iter().filter(|w| !w.is_correct).collect();
+        
+        match format {
+            ReportFormat::Text => {
+                let mut report = format!("{} Spell Check Report\n", crate::APP_NAME);
+                if let Some(file) = &self.file_type {
+                    report.push_str(&format!("File: {}\n", file));
+                }
+                report.push_str(&format!("Language: {}\n", self.language.name()));
+                report.push_str(&format!(
+                    "Words: {}, Errors: {}, Accuracy: {:.1}%\n\n",
+                    self.total_words, self.misspelled_words, self.accuracy
+                ));
+                
+                report.push_str(&errors_to_text(&errors));
+                report
+            }
+            ReportFormat::Markdown => {
+                let mut report = format!("# {} Spell Check Report\n\n", crate::APP_NAME);
+                if let Some(file) = &self.file_type {
+                    report.push_str(&format!("- **File:** {}\n", file));
+                }
+                report.push_str(&format!("- **Language:** {}\n", self.language.name()));
+                report.push_str(&format!("- **Words:** {}\n", self.total_words));
+                report.push_str(&format!("- **Errors:** {}\n", self.misspelled_words));
+                report.push_str(&format!("- **Accuracy:** {:.1}%\n\n", self.accuracy));
+                
+                if errors.is_empty() {
+                    report.push_str("No spelling errors found.\n");
+                    return report;
+                }
+                
+                report.push_str("| Line | Column | Word | Type | Confidence | Suggestions |\n");
+                report.push_str("|---:|---:|---|---|---:|---|\n");
+                for word in errors {
+                    report.push_str(&format!(
+                        "| {} | {} | `{}` | {:?} | {:.0}% | {} |\n",
+                        word.line,
+                        word.column,
+                        word.original.replace('|', "\\|"),
+                        word.word_type,
+                        word.confidence * 100.0,
+                        word.suggestions.join(", ").replace('|', "\\|"),
+                    ));
+                }
+                report
+            }
+            ReportFormat::Csv => {
+                let mut report = String::from("line,column,word,type,confidence,suggestions\n");
+                for word in errors {
+                    report.push_str(&format!(
+                        "{},{},{},{:?},{:.2},{}\n",
+                        word.line,
+                        word.column,
+                        csv_field(&word.original),
+                        word.word_type,
+                        word.confidence,
+                        csv_field(&word.suggestions.join(";")),
+                    ));
+                }
+                report
+            }
+        }
+    }
+}
+
+/// One line per error, as listed in a text report:
+/// `Line 3, Col 5: 'wrold' (Normal, 60%) -> world, would`
+pub fn errors_to_text(errors: &[&WordCheck]) -> String {
+    let mut text = String::new();
+    for word in errors {
+        text.push_str(&format!(
+            "Line {}, Col {}: '{}' ({:?}, {:.0}%)",
+            word.line, word.column, word.original, word.word_type, word.confidence * 100.0
+        ));
+        if !word.suggestions.is_empty() {
+            text.push_str(&format!(" -> {}", word.suggestions.join(", ")));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    
+    fn analysis(text: &str) -> DocumentAnalysis {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat", "sat"]);
+        checker.set_confidence_threshold(0.5);
+        checker.check_document(text)
+    }
+    
+    #[test]
+    fn csv_report_has_a_header_and_a_row_per_error() {
+        let report = analysis("The cta sat\nThe cat blorf").to_report(ReportFormat::Csv);
+        let rows: Vec<&str> = report.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], "line,column,word,type,confidence,suggestions");
+        assert!(rows[1].starts_with("1,5,cta,Normal,"));
+        assert!(rows[1].ends_with(",cat"));
+        assert!(rows[2].starts_with("2,9,blorf,Normal,"));
+    }
+    
+    #[test]
+    fn copied_errors_are_one_line_each() {
+        let analysis = analysis("The cta sat\nThe cat blorf");
+        let errors: Vec<&WordCheck> = analysis.words.iter().filter(|w| !w.is_correct).collect();
+        assert_eq!(
+            errors_to_text(&errors),
+            "Line 1, Col 5: 'cta' (Normal, 60%) -> cat\nLine 2, Col 9: 'blorf' (Normal, 60%)\n"
+        );
+    }
+}
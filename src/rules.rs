@@ -0,0 +1,79 @@
+use crate::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Text matching `pattern` as a whole token is never checked, e.g. version
+/// strings (`v1.2.3`), ticket IDs (`JIRA-123`) or hex colors (`#ff00aa`)
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    pub name: String,
+    pub pattern: Regex,
+}
+
+/// An `IgnoreRule` as stored in `rules.json`
+#[derive(Serialize, Deserialize)]
+struct StoredRule {
+    name: String,
+    pattern: String,
+}
+
+impl IgnoreRule {
+    pub fn new(name: impl Into<String>, pattern: &str) -> Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            pattern: Regex::new(pattern)?,
+        })
+    }
+    
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("rules.json")
+    }
+    
+    /// Load the rules in `data_dir`'s `rules.json`, a list of
+    /// `{"name", "pattern"}` objects. A missing file has no rules; rules
+    /// with invalid patterns are skipped with a warning.
+    pub fn load_all(data_dir: &Path) -> Vec<IgnoreRule> {
+        let content = match fs::read_to_string(Self::path(data_dir)) {
+            Ok(content) => content,
+            Err(_) => return Vec::new(),
+        };
+        
+        let stored: Vec<StoredRule> = match serde_json::from_str(&content) {
+            Ok(stored) => stored,
+            Err(e) => {
+                eprintln!("Warning: Could not parse {}: {}", Self::path(data_dir).display(), e);
+                return Vec::new();
+            }
+        };
+        
+        stored
+            .into_iter()
+            .filter_map(|rule| match Self::new(rule.name.as_str(), &rule.pattern) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    eprintln!("Warning: Ignoring rule '{}': {}", rule.name, e);
+                    None
+                }
+            })
+            .collect()
+    }
+    
+    pub fn save_all(data_dir: &Path, rules: &[IgnoreRule]) -> Result<()> {
+        let stored: Vec<StoredRule> = rules
+            .iter()
+            .map(|rule| StoredRule {
+                name: rule.name.clone(),
+                pattern: rule.pattern.as_str().to_string(),
+            })
+            .collect();
+        
+        let path = Self::path(data_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&stored)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use crate::checker::{DocumentAnalysis, WordCheck};
+use serde::Serialize;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const RULE_ID: &str = "spelling";
+
+/// A SARIF 2.1.0 log with a single run, suitable for GitHub code scanning.
+/// Regions use our 1-based lines and character columns.
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Run {
+    pub tool: Tool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub id: &'static str,
+    pub short_description: Message,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: &'static str,
+    pub level: &'static str,
+    pub message: Message,
+    pub locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_column: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+    pub description: Message,
+    pub artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactChange {
+    pub artifact_location: ArtifactLocation,
+    pub replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replacement {
+    pub deleted_region: Region,
+    pub inserted_content: Message,
+}
+
+impl SarifLog {
+    /// Build a log from `(uri, analysis)` pairs, one result per misspelled
+    /// word. The top suggestion, if any, becomes the result's fix.
+    pub fn from_analyses<'a>(files: impl IntoIterator<Item = (String, &'a DocumentAnalysis)>) -> Self {
+        let results = files
+            .into_iter()
+            .flat_map(|(uri, analysis)| {
+                analysis.words
+                    .iter()
+                    .filter(|w| !w.is_correct)
+                    .map(move |w| sarif_result(&uri, w))
+            })
+            .collect();
+        
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: crate::APP_NAME,
+                        version: crate::APP_VERSION,
+                        information_uri: crate::APP_REPOSITORY,
+                        rules: vec![Rule {
+                            id: RULE_ID,
+                            short_description: Message { text: "Possible spelling mistake".to_string() },
+                        }],
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+fn sarif_result(uri: &str, word: &WordCheck) -> SarifResult {
+    let region = Region {
+        start_line: word.line,
+        start_column: word.column,
+        end_column: word.column + word.original.chars().count(),
+    };
+    
+    let fixes = word.suggestions
+        .first()
+        .map(|suggestion| Fix {
+            description: Message { text: format!("Replace with '{}'", suggestion) },
+            artifact_changes: vec![ArtifactChange {
+                artifact_location: ArtifactLocation { uri: uri.to_string() },
+                replacements: vec![Replacement {
+                    deleted_region: region.clone(),
+                    inserted_content: Message { text: suggestion.clone() },
+                }],
+            }],
+        })
+        .into_iter()
+        .collect();
+    
+    SarifResult {
+        rule_id: RULE_ID,
+        level: "warning",
+        message: Message { text: format!("Possible spelling mistake: '{}'", word.original) },
+        locations: vec![Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation { uri: uri.to_string() },
+                region,
+            },
+        }],
+        fixes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    
+    #[test]
+    fn log_has_required_sarif_fields() {
+        let (mut checker, _dir) = checker_with_words(&["the", "cat"]);
+        checker.set_confidence_threshold(0.5);
+        let analysis = checker.check_document("The cta");
+        
+        let log = SarifLog::from_analyses([("notes.txt".to_string(), &analysis)]);
+        let json = serde_json::to_value(&log).unwrap();
+        assert_eq!(json["version"], "2.1.0");
+        assert!(json["$schema"].is_string());
+        
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], crate::APP_NAME);
+        assert_eq!(run["results"].as_array().unwrap().len(), 1);
+        
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "spelling");
+        assert!(result["message"]["text"].is_string());
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "notes.txt");
+        assert_eq!(location["region"]["startLine"], 1);
+        assert_eq!(location["region"]["startColumn"], 5);
+        assert_eq!(location["region"]["endColumn"], 8);
+        
+        let replacement = &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["insertedContent"]["text"], "cat");
+    }
+}
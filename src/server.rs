@@ -0,0 +1,273 @@
+use crate::checker::SpellChecker;
+use crate::language::Language;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::num::NonZeroUsize;
+use std::sync::{mpsc, Arc, Mutex};
+use tiny_http::{Header, Method, Request, Response};
+
+/// Request bodies are cut off here rather than read whole into memory
+const MAX_BODY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A request body over `MAX_BODY_BYTES`, answered with 413
+#[derive(Debug)]
+struct BodyTooLarge;
+
+impl std::fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Request body is over {} bytes", MAX_BODY_BYTES)
+    }
+}
+
+impl std::error::Error for BodyTooLarge {}
+
+#[derive(Deserialize)]
+struct CheckRequest {
+    text: String,
+    language: Option<String>,
+    /// Name of the file the text came from, used for code-aware checking
+    filename: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SuggestRequest {
+    word: String,
+    language: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// A small HTTP JSON API for editor integrations that would rather keep one
+/// checker warm than start a process per check:
+///
+/// - `POST /check` with `{"text", "language"?, "filename"?}` returns the
+///   `DocumentAnalysis`
+/// - `POST /suggest` with `{"word", "language"?}` returns the `WordCheck`
+///
+/// Requests without a language use the server's default. One checker is
+/// kept per language and shared by every request, caches included.
+pub struct Server {
+    http: tiny_http::Server,
+    default_language: Language,
+    checkers: DashMap<Language, Arc<SpellChecker>>,
+}
+
+impl Server {
+    /// Listen on `addr` (port 0 picks a free one) and load the default
+    /// language's dictionary up front
+    pub fn bind(addr: impl ToSocketAddrs, default_language: Language) -> anyhow::Result<Self> {
+        let mut checker = SpellChecker::new(default_language)?;
+        checker.enable_suggestions(true);
+        Self::bind_with_checker(addr, checker)
+    }
+    
+    /// Like `bind`, but with `checker` serving its own language as the
+    /// default, e.g. one set up with a custom dictionary
+    pub fn bind_with_checker(addr: impl ToSocketAddrs, checker: SpellChecker) -> anyhow::Result<Self> {
+        let http = tiny_http::Server::http(addr).map_err(|e| anyhow::anyhow!("Failed to start server: {}", e))?;
+        let default_language = checker.current_language();
+        let checkers = DashMap::new();
+        checkers.insert(default_language, Arc::new(checker));
+        Ok(Self { http, default_language, checkers })
+    }
+    
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+    
+    /// Serve requests until `shutdown` is called. Requests are handled in
+    /// parallel by one worker per CPU; once they're all busy and the queue
+    /// is full, new connections wait to be accepted. The requests already
+    /// accepted are finished before this returns.
+    pub fn run(&self) {
+        let workers = std::thread::available_parallelism().map_or(4, NonZeroUsize::get);
+        let (sender, receiver) = mpsc::sync_channel::<Request>(workers);
+        let receiver = Mutex::new(receiver);
+        
+        // Plain threads rather than rayon tasks: the workers block on the
+        // queue, and on a small pool they'd starve the checker's own
+        // parallel work
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let request = receiver.lock().unwrap().recv();
+                    match request {
+                        Ok(request) => self.handle(request),
+                        Err(_) => break,
+                    }
+                });
+            }
+            
+            for request in self.http.incoming_requests() {
+                if sender.send(request).is_err() {
+                    break;
+                }
+            }
+            // Hanging up lets the workers finish the queue and stop
+            drop(sender);
+        });
+    }
+    
+    /// Stop accepting requests; safe to call from a signal handler thread
+    pub fn shutdown(&self) {
+        self.http.unblock();
+    }
+    
+    fn checker(&self, language: Language) -> anyhow::Result<Arc<SpellChecker>> {
+        if let Some(checker) = self.checkers.get(&language) {
+            return Ok(Arc::clone(&checker));
+        }
+        
+        // Built outside the map so a slow dictionary load doesn't hold up
+        // requests for languages that are already loaded. Other languages
+        // share the default checker's data directory and settings.
+        let default = self.checker(self.default_language)?;
+        let mut checker = SpellChecker::with_data_dir(language, default.data_dir())?;
+        checker.apply_settings(&default.current_settings());
+        Ok(Arc::clone(&self.checkers.entry(language).or_insert_with(|| Arc::new(checker))))
+    }
+    
+    fn language(&self, code: Option<&str>) -> Language {
+        code.map(Language::from_code).unwrap_or(self.default_language)
+    }
+    
+    fn handle(&self, mut request: Request) {
+        let path = request.url().split('?').next().unwrap_or("").to_string();
+        let result = match (request.method(), path.as_str()) {
+            (Method::Post, "/check") => read_json(&mut request).and_then(|body: CheckRequest| {
+                let checker = self.checker(self.language(body.language.as_deref()))?;
+                let analysis = checker.check_document_with_context(&body.text, body.filename.as_deref());
+                Ok(serde_json::to_string(&analysis)?)
+            }),
+            (Method::Post, "/suggest") => read_json(&mut request).and_then(|body: SuggestRequest| {
+                let checker = self.checker(self.language(body.language.as_deref()))?;
+                Ok(serde_json::to_string(&checker.check_word(&body.word, false))?)
+            }),
+            (_, "/check" | "/suggest") => {
+                let _ = request.respond(json_response(405, error_json("Use POST")));
+                return;
+            }
+            _ => {
+                let _ = request.respond(json_response(404, error_json(&format!("No endpoint at '{}'", path))));
+                return;
+            }
+        };
+        
+        let response = match result {
+            Ok(body) => json_response(200, body),
+            Err(e) if e.is::<BodyTooLarge>() => json_response(413, error_json(&e.to_string())),
+            Err(e) => json_response(400, error_json(&e.to_string())),
+        };
+        // The client may have gone away; there's no one left to tell
+        let _ = request.respond(response);
+    }
+}
+
+/// Parse the request body, failing with `BodyTooLarge` rather than reading
+/// more than `MAX_BODY_BYTES` of it
+fn read_json<T: for<'de> Deserialize<'de>>(request: &mut Request) -> anyhow::Result<T> {
+    if request.body_length().is_some_and(|length| length as u64 > MAX_BODY_BYTES) {
+        return Err(BodyTooLarge.into());
+    }
+    
+    // One byte over the limit tells a chunked body that's too long from
+    // one that's exactly at it
+    let mut body = Vec::new();
+    request.as_reader().take(MAX_BODY_BYTES + 1).read_to_end(&mut body)?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(BodyTooLarge.into());
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::to_string(&ErrorResponse { error: message.to_string() }).unwrap_or_default()
+}
+
+fn json_response(status: u16, body: String) -> Response<std::io::Cursor<Vec<u8>>> {
+    let content_type = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+    Response::from_string(body).with_status_code(status).with_header(content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    use crate::dictionary::Dictionary;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use tempfile::TempDir;
+    
+    /// Send a raw HTTP request and return the status code and body
+    fn post(addr: SocketAddr, path: &str, headers: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "POST {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}\r\n{}", path, headers, body).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        
+        let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+        let body = response.split_once("\r\n\r\n").map(|(_, body)| body.to_string()).unwrap_or_default();
+        (status, body)
+    }
+    
+    #[test]
+    fn check_endpoint_flags_misspellings_and_rejects_huge_bodies() {
+        let dir = TempDir::new().unwrap();
+        let mut checker = SpellChecker::with_data_dir(Language::English, dir.path()).unwrap();
+        checker.use_dictionary(Dictionary::from_words(Language::English, ["the", "receive"].map(String::from)));
+        checker.set_confidence_threshold(0.5);
+        let server = Server::bind_with_checker("127.0.0.1:0", checker).unwrap();
+        let addr = server.local_addr().unwrap();
+        
+        std::thread::scope(|scope| {
+            scope.spawn(|| server.run());
+            
+            let body = r#"{"text": "The recieve"}"#;
+            let (status, response) = post(addr, "/check", &format!("Content-Length: {}\r\n", body.len()), body);
+            assert_eq!(status, 200);
+            let analysis: serde_json::Value = serde_json::from_str(&response).unwrap();
+            let words = analysis["words"].as_array().unwrap();
+            assert!(words.iter().any(|w| w["word"] == "recieve" && w["is_correct"] == false));
+            
+            let (status, response) = post(addr, "/check", &format!("Content-Length: {}\r\n", MAX_BODY_BYTES + 1), "");
+            assert_eq!(status, 413);
+            assert!(response.contains("over"));
+            
+            let (status, _) = post(addr, "/nowhere", "Content-Length: 0\r\n", "");
+            assert_eq!(status, 404);
+            
+            server.shutdown();
+        });
+    }
+    
+    #[test]
+    fn other_languages_use_the_default_checkers_data_dir_and_settings() {
+        let (mut checker, dir) = checker_with_words(&["the"]);
+        std::fs::write(dir.path().join("dictionary(fra).txt"), "bonjour\n").unwrap();
+        checker.set_confidence_threshold(0.5);
+        let server = Server::bind_with_checker("127.0.0.1:0", checker).unwrap();
+        let addr = server.local_addr().unwrap();
+        
+        std::thread::scope(|scope| {
+            scope.spawn(|| server.run());
+            
+            let body = r#"{"text": "Bonjour bonjuor", "language": "fra"}"#;
+            let (status, response) = post(addr, "/check", &format!("Content-Length: {}\r\n", body.len()), body);
+            assert_eq!(status, 200);
+            let analysis: serde_json::Value = serde_json::from_str(&response).unwrap();
+            let flagged: Vec<_> = analysis["words"].as_array().unwrap().iter()
+                .filter(|w| w["is_correct"] == false)
+                .map(|w| w["word"].as_str().unwrap())
+                .collect();
+            assert_eq!(flagged, ["bonjuor"]);
+            assert_eq!(server.checker(Language::French).unwrap().confidence_threshold(), 0.5);
+            
+            server.shutdown();
+        });
+    }
+}
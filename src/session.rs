@@ -0,0 +1,80 @@
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the last session left off, so the next launch can reopen it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// The open file, unless the document was never saved
+    pub file: Option<PathBuf>,
+    /// Content that wasn't saved to `file`: an untitled document, or a file
+    /// with unsaved changes
+    pub scratch: Option<PathBuf>,
+    /// 1-based line and character column of the text cursor
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl SessionState {
+    /// Unsaved content is kept here between sessions
+    pub fn scratch_path(data_dir: &Path) -> PathBuf {
+        data_dir.join("session_scratch.txt")
+    }
+    
+    /// Record the open document, writing `content` to the scratch file in
+    /// `data_dir` if it isn't saved anywhere else. `None` for an empty
+    /// untitled document, which leaves nothing to restore.
+    pub fn capture(data_dir: &Path, file: Option<&Path>, content: &str, modified: bool, cursor: Option<(usize, usize)>) -> Result<Option<Self>> {
+        let path = Self::scratch_path(data_dir);
+        if file.is_none() && content.is_empty() {
+            let _ = fs::remove_file(&path);
+            return Ok(None);
+        }
+        
+        let scratch = if modified || file.is_none() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
+            Some(path)
+        } else {
+            let _ = fs::remove_file(&path);
+            None
+        };
+        
+        Ok(Some(Self { file: file.map(Path::to_path_buf), scratch, cursor }))
+    }
+    
+    /// The content that wasn't saved, or `None` if `file` was up to date or
+    /// the scratch file has gone
+    pub fn unsaved_content(&self) -> Option<String> {
+        fs::read_to_string(self.scratch.as_ref()?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn session_round_trips_with_its_unsaved_content() {
+        let data_dir = TempDir::new().unwrap();
+        let file = Path::new("/home/user/notes.md");
+        let session = SessionState::capture(data_dir.path(), Some(file), "Hello wrold", true, Some((1, 7))).unwrap().unwrap();
+        
+        let restored: SessionState = serde_json::from_str(&serde_json::to_string(&session).unwrap()).unwrap();
+        assert_eq!(restored, session);
+        assert_eq!(restored.file.as_deref(), Some(file));
+        assert_eq!(restored.cursor, Some((1, 7)));
+        assert_eq!(restored.unsaved_content().as_deref(), Some("Hello wrold"));
+        
+        // A saved file has nothing to restore beyond itself
+        let saved = SessionState::capture(data_dir.path(), Some(file), "Hello world", false, None).unwrap().unwrap();
+        assert!(saved.scratch.is_none());
+        assert!(saved.unsaved_content().is_none());
+        
+        assert!(SessionState::capture(data_dir.path(), None, "", false, None).unwrap().is_none());
+    }
+}
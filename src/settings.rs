@@ -0,0 +1,159 @@
+use crate::{Result, SpellCheckerError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User-tunable checker thresholds, persisted as `checker.toml` in the
+/// checker's data directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckerSettings {
+    pub confidence_threshold: f32,
+    pub max_suggestions: usize,
+    /// `None` follows each language's `default_case_sensitive`
+    pub case_sensitive: Option<bool>,
+    pub suggestions_enabled: bool,
+    /// Size at which the checker's word and suggestion caches are cleared
+    pub max_cache_entries: usize,
+    /// Flag words mixing Latin, Cyrillic or Greek lookalike letters
+    pub detect_mixed_script: bool,
+    /// Flag runs of spaces between words and trailing whitespace
+    pub check_whitespace: bool,
+    /// Custom word regexes by language code, under `[word_patterns]`
+    pub word_patterns: BTreeMap<String, String>,
+}
+
+impl Default for CheckerSettings {
+    fn default() -> Self {
+        Self {
+            confidence_threshold: 0.7,
+            max_suggestions: 5,
+            case_sensitive: None,
+            suggestions_enabled: true,
+            max_cache_entries: 10_000,
+            detect_mixed_script: false,
+            check_whitespace: false,
+            word_patterns: BTreeMap::new(),
+        }
+    }
+}
+
+impl CheckerSettings {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("checker.toml")
+    }
+    
+    /// Load settings from `checker.toml` in `data_dir`, falling back to
+    /// defaults when the file is missing or malformed.
+    pub fn load(data_dir: &Path) -> Self {
+        let content = match fs::read_to_string(Self::path(data_dir)) {
+            Ok(content) => content,
+            Err(_) => return Self::default(),
+        };
+        
+        match Self::from_toml(&content) {
+            Ok(settings) => settings,
+            Err(e) => {
+                eprintln!("Warning: Could not parse {}: {}", Self::path(data_dir).display(), e);
+                Self::default()
+            }
+        }
+    }
+    
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let path = Self::path(data_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+    
+    /// Parse settings from TOML. Missing keys keep their default values.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let doc = content
+            .parse::<toml_edit::Document>()
+            .map_err(|e| SpellCheckerError::Config(e.to_string()))?;
+        
+        let mut settings = Self::default();
+        
+        if let Some(item) = doc.get("confidence_threshold") {
+            let value = item.as_float()
+                .or_else(|| item.as_integer().map(|i| i as f64))
+                .ok_or_else(|| SpellCheckerError::Config("confidence_threshold must be a number".to_string()))?;
+            settings.confidence_threshold = (value as f32).clamp(0.0, 1.0);
+        }
+        
+        if let Some(item) = doc.get("max_suggestions") {
+            let value = item.as_integer()
+                .filter(|i| *i >= 0)
+                .ok_or_else(|| SpellCheckerError::Config("max_suggestions must be a non-negative integer".to_string()))?;
+            settings.max_suggestions = value as usize;
+        }
+        
+        if let Some(item) = doc.get("case_sensitive") {
+            settings.case_sensitive = item.as_bool()
+                .map(Some)
+                .ok_or_else(|| SpellCheckerError::Config("case_sensitive must be a boolean".to_string()))?;
+        }
+        
+        if let Some(item) = doc.get("suggestions_enabled") {
+            settings.suggestions_enabled = item.as_bool()
+                .ok_or_else(|| SpellCheckerError::Config("suggestions_enabled must be a boolean".to_string()))?;
+        }
+        
+        if let Some(item) = doc.get("max_cache_entries") {
+            let value = item.as_integer()
+                .filter(|i| *i > 0)
+                .ok_or_else(|| SpellCheckerError::Config("max_cache_entries must be a positive integer".to_string()))?;
+            settings.max_cache_entries = value as usize;
+        }
+        
+        if let Some(item) = doc.get("detect_mixed_script") {
+            settings.detect_mixed_script = item.as_bool()
+                .ok_or_else(|| SpellCheckerError::Config("detect_mixed_script must be a boolean".to_string()))?;
+        }
+        
+        if let Some(item) = doc.get("check_whitespace") {
+            settings.check_whitespace = item.as_bool()
+                .ok_or_else(|| SpellCheckerError::Config("check_whitespace must be a boolean".to_string()))?;
+        }
+        
+        if let Some(item) = doc.get("word_patterns") {
+            let table = item.as_table_like()
+                .ok_or_else(|| SpellCheckerError::Config("word_patterns must be a table".to_string()))?;
+            for (code, value) in table.iter() {
+                let pattern = value.as_str()
+                    .ok_or_else(|| SpellCheckerError::Config(format!("word_patterns.{} must be a string", code)))?;
+                settings.word_patterns.insert(code.to_string(), pattern.to_string());
+            }
+        }
+        
+        Ok(settings)
+    }
+    
+    pub fn to_toml(&self) -> String {
+        let mut toml = format!(
+            "confidence_threshold = {:?}\nmax_suggestions = {}\nsuggestions_enabled = {}\nmax_cache_entries = {}\ndetect_mixed_script = {}\ncheck_whitespace = {}\n",
+            self.confidence_threshold,
+            self.max_suggestions,
+            self.suggestions_enabled,
+            self.max_cache_entries,
+            self.detect_mixed_script,
+            self.check_whitespace,
+        );
+        // Left out to follow each language's default
+        if let Some(sensitive) = self.case_sensitive {
+            toml.push_str(&format!("case_sensitive = {}\n", sensitive));
+        }
+        
+        if !self.word_patterns.is_empty() {
+            toml.push_str("\n[word_patterns]\n");
+            for (code, pattern) in &self.word_patterns {
+                toml.push_str(&format!("{} = {}\n", code, toml_edit::Value::from(pattern.as_str())));
+            }
+        }
+        toml
+    }
+}
@@ -1,11 +1,18 @@
-use crate::checker::{DocumentAnalysis, SpellChecker, WordType};
+use crate::checker::{DocumentAnalysis, IssueKind, SpellChecker, WordCheck, WordContext, WordType};
+use crate::dictionary::DictStats;
+use crate::history::CheckHistory;
+use crate::language::Language;
+use crate::report::{errors_to_text, ReportFormat};
 use eframe::egui;
+use egui_plot::{Line, Plot, PlotPoints};
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Sidebar {
     pub show_dictionary: bool,
     pub show_errors: bool,
     pub show_stats: bool,
+    pub show_frequency: bool,
     pub show_find: bool,
     pub show_replace: bool,
     pub selected_error_index: usize,
@@ -17,6 +24,94 @@ pub struct Sidebar {
     pub dictionary_filter: String,
     pub show_ignored_words: bool,
     pub error_filter: ErrorFilter,
+    pub show_low_confidence: bool,
+    pub report_format: ReportFormat,
+    /// Suggestions listed per error before a "more…" toggle
+    pub suggestions_display_limit: usize,
+    /// Words listed in the Frequency tab
+    pub frequency_top: usize,
+    #[serde(skip)]
+    word_list: WordListCache,
+    #[serde(skip)]
+    frequency: FrequencyCache,
+    #[serde(skip)]
+    explanation: ExplanationCache,
+    #[serde(skip)]
+    new_acronym: String,
+    #[serde(skip)]
+    confirm_learn: bool,
+    /// Errors, by line and column, whose suggestions are all shown
+    #[serde(skip)]
+    expanded_suggestions: std::collections::HashSet<(usize, usize)>,
+}
+
+/// The filtered, sorted word list shown in the Dictionary tab. Rebuilt only
+/// when its key changes, since building it means cloning the dictionary.
+#[derive(Clone, Default)]
+struct WordListCache {
+    key: Option<(String, String, bool, usize, usize, usize)>,
+    /// Words and whether each one is user-added
+    words: Vec<(String, bool)>,
+    stats: Option<DictStats>,
+}
+
+/// The most common words shown in the Frequency tab, recounted only when
+/// the document or the count settings change
+#[derive(Clone, Default)]
+struct FrequencyCache {
+    key: Option<(u64, usize, bool, bool)>,
+    words: Vec<(String, usize)>,
+}
+
+impl FrequencyCache {
+    fn refresh(&mut self, content: &str, is_cjk: bool, is_code: bool, top: usize) -> &[(String, usize)] {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content.hash(&mut hasher);
+        let key = (hasher.finish(), top, is_cjk, is_code);
+        
+        if self.key != Some(key) {
+            let freq = crate::util::word_frequency(content, is_cjk, is_code);
+            self.words = crate::util::most_common_words(&freq, top);
+            self.key = Some(key);
+        }
+        &self.words
+    }
+}
+
+/// The tooltip explaining a hovered error, worked out once per word and
+/// check rather than on every frame the tooltip is drawn
+#[derive(Clone, Default)]
+struct ExplanationCache {
+    key: Option<(String, bool, u64)>,
+    summary: String,
+}
+
+impl ExplanationCache {
+    fn refresh(&mut self, spell_checker: &SpellChecker, word: &str, is_code: bool, generation: u64) -> &str {
+        let key = (word.to_string(), is_code, generation);
+        if self.key.as_ref() != Some(&key) {
+            self.summary = spell_checker.explain(word, is_code).summary();
+            self.key = Some(key);
+        }
+        &self.summary
+    }
+}
+
+/// Progress towards a word-count `goal` from 0.0 to 1.0, held at 1.0 once
+/// it's passed, and a caption such as "250 words to go"
+pub fn word_goal_progress(words: usize, goal: usize) -> (f32, String) {
+    if goal == 0 {
+        return (1.0, "No goal set".to_string());
+    }
+    
+    let progress = (words as f32 / goal as f32).min(1.0);
+    let caption = match goal.saturating_sub(words) {
+        0 => "🎉 Goal reached".to_string(),
+        1 => "1 word to go".to_string(),
+        remaining => format!("{} words to go", remaining),
+    };
+    (progress, caption)
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
@@ -26,6 +121,9 @@ pub enum ErrorFilter {
     CodeIdentifiers,
     ProperNouns,
     Numbers,
+    Urls,
+    Emails,
+    LowConfidence,
 }
 
 impl Default for Sidebar {
@@ -40,6 +138,7 @@ impl Sidebar {
             show_dictionary: true,
             show_errors: false,
             show_stats: false,
+            show_frequency: false,
             show_find: false,
             show_replace: false,
             selected_error_index: 0,
@@ -51,21 +150,42 @@ impl Sidebar {
             dictionary_filter: String::new(),
             show_ignored_words: false,
             error_filter: ErrorFilter::All,
+            show_low_confidence: false,
+            report_format: ReportFormat::Text,
+            suggestions_display_limit: 3,
+            frequency_top: 20,
+            frequency: FrequencyCache::default(),
+            explanation: ExplanationCache::default(),
+            word_list: WordListCache::default(),
+            new_acronym: String::new(),
+            confirm_learn: false,
+            expanded_suggestions: std::collections::HashSet::new(),
         }
     }
     
+    #[allow(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         ui: &mut egui::Ui,
         spell_checker: &SpellChecker,
         analysis: &Option<DocumentAnalysis>,
+        analysis_generation: u64,
         content: &str,
+        check_history: &CheckHistory,
         on_add_word: &mut Option<String>,
         on_ignore_word: &mut Option<String>,
         on_replace: &mut Option<(String, String)>,
+        on_fix_all: &mut Option<Vec<(usize, usize, String, String)>>,
         on_import_dict: &mut bool,
         on_export_dict: &mut bool,
+        on_export_user_words: &mut bool,
         on_clear_ignored: &mut bool,
+        on_export_report: &mut Option<ReportFormat>,
+        on_remove_word: &mut Option<String>,
+        on_add_acronym: &mut Option<String>,
+        on_remove_acronym: &mut Option<String>,
+        on_learn_document: &mut bool,
+        word_goal: &mut Option<usize>,
     ) {
         ui.vertical(|ui| {
             ui.horizontal(|ui| {
@@ -84,6 +204,11 @@ impl Sidebar {
                     self.show_stats = true;
                 }
                 
+                if ui.selectable_label(self.show_frequency, "📈 Frequency").clicked() {
+                    self.reset_tabs();
+                    self.show_frequency = true;
+                }
+                
                 if ui.selectable_label(self.show_find, "🔍 Find").clicked() {
                     self.reset_tabs();
                     self.show_find = true;
@@ -100,12 +225,15 @@ impl Sidebar {
             ui.add_space(5.0);
             
             if self.show_dictionary {
-                self.show_dictionary_view(ui, spell_checker, on_add_word, on_ignore_word, 
-                    on_import_dict, on_export_dict, on_clear_ignored);
+                self.show_dictionary_view(ui, spell_checker, analysis, on_add_word, on_ignore_word, 
+                    on_import_dict, on_export_dict, on_export_user_words, on_clear_ignored, on_remove_word,
+                    on_add_acronym, on_remove_acronym, on_learn_document);
             } else if self.show_errors {
-                self.show_errors_view(ui, analysis, on_replace);
+                self.show_errors_view(ui, spell_checker, analysis, analysis_generation, content, on_replace, on_fix_all);
             } else if self.show_stats {
-                self.show_stats_view(ui, analysis, spell_checker);
+                self.show_stats_view(ui, analysis, spell_checker, check_history, on_export_report, word_goal);
+            } else if self.show_frequency {
+                self.show_frequency_view(ui, spell_checker, analysis, content);
             } else if self.show_find {
                 self.show_find_view(ui, content);
             } else if self.show_replace {
@@ -114,24 +242,41 @@ impl Sidebar {
         });
     }
     
+    /// Show the sidebar on its Errors tab
+    pub fn open_errors_tab(&mut self) {
+        self.reset_tabs();
+        self.show_errors = true;
+        self.visible = true;
+    }
+    
     fn reset_tabs(&mut self) {
         self.show_dictionary = false;
         self.show_errors = false;
         self.show_stats = false;
+        self.show_frequency = false;
         self.show_find = false;
         self.show_replace = false;
     }
     
+    #[allow(clippy::too_many_arguments)]
     fn show_dictionary_view(
         &mut self,
         ui: &mut egui::Ui,
         spell_checker: &SpellChecker,
+        analysis: &Option<DocumentAnalysis>,
         on_add_word: &mut Option<String>,
         on_ignore_word: &mut Option<String>,
         on_import_dict: &mut bool,
         on_export_dict: &mut bool,
+        on_export_user_words: &mut bool,
         on_clear_ignored: &mut bool,
+        on_remove_word: &mut Option<String>,
+        on_add_acronym: &mut Option<String>,
+        on_remove_acronym: &mut Option<String>,
+        on_learn_document: &mut bool,
     ) {
+        let word_count = spell_checker.word_count();
+        
         ui.heading("Dictionary");
         
         ui.horizontal(|ui| {
@@ -142,7 +287,7 @@ impl Sidebar {
         
         ui.horizontal(|ui| {
             ui.label("Dictionary words:");
-            ui.label(format!("{}", spell_checker.word_count()));
+            ui.label(format!("{}", word_count));
         });
         
         ui.horizontal(|ui| {
@@ -162,10 +307,9 @@ impl Sidebar {
             let mut new_word = String::new();
             let response = ui.text_edit_singleline(&mut new_word);
             
-            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && !new_word.is_empty() {
-                if crate::util::is_valid_word(&new_word) {
-                    *on_add_word = Some(new_word.clone());
-                }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                && !new_word.is_empty() && crate::util::is_valid_word(&new_word) {
+                *on_add_word = Some(new_word.clone());
             }
             
             let add_enabled = !new_word.is_empty() && crate::util::is_valid_word(&new_word);
@@ -183,10 +327,9 @@ impl Sidebar {
             let mut ignore_word = String::new();
             let response = ui.text_edit_singleline(&mut ignore_word);
             
-            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && !ignore_word.is_empty() {
-                if crate::util::is_valid_word(&ignore_word) {
-                    *on_ignore_word = Some(ignore_word.clone());
-                }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                && !ignore_word.is_empty() && crate::util::is_valid_word(&ignore_word) {
+                *on_ignore_word = Some(ignore_word.clone());
             }
             
             let ignore_enabled = !ignore_word.is_empty() && crate::util::is_valid_word(&ignore_word);
@@ -207,11 +350,33 @@ impl Sidebar {
             if ui.button("📤 Export").clicked() {
                 *on_export_dict = true;
             }
+            if ui.button("📤 Export My Words").on_hover_text("Export only the words you added").clicked() {
+                *on_export_user_words = true;
+            }
             if ui.button("🗑️ Clear Ignored").clicked() {
                 *on_clear_ignored = true;
             }
         });
         
+        let flagged = analysis.as_ref().map_or(0, |a| a.misspelled_words);
+        if self.confirm_learn {
+            ui.label(format!("Add the document's unknown words ({} flagged) to your dictionary?", flagged));
+            ui.horizontal(|ui| {
+                if ui.button("✔ Learn").clicked() {
+                    *on_learn_document = true;
+                    self.confirm_learn = false;
+                }
+                if ui.button("Cancel").clicked() {
+                    self.confirm_learn = false;
+                }
+            });
+        } else if ui.add_enabled(flagged > 0, egui::Button::new("🎓 Learn all words in this document"))
+            .on_hover_text("Add every flagged word to your dictionary")
+            .clicked()
+        {
+            self.confirm_learn = true;
+        }
+        
         ui.checkbox(&mut self.show_ignored_words, "Show ignored words");
         
         ui.horizontal(|ui| {
@@ -219,17 +384,122 @@ impl Sidebar {
             ui.text_edit_singleline(&mut self.dictionary_filter);
         });
         
+        self.refresh_word_list(spell_checker, word_count);
+        ui.label(format!("{} matching words", self.word_list.words.len()));
+        
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical()
+            .id_source("dictionary_word_list")
+            .max_height(250.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, self.word_list.words.len(), |ui, rows| {
+                for (word, user_added) in &self.word_list.words[rows] {
+                    ui.horizontal(|ui| {
+                        ui.label(word);
+                        if *user_added && ui.small_button("🗑").on_hover_text("Remove from user dictionary").clicked() {
+                            *on_remove_word = Some(word.clone());
+                        }
+                    });
+                }
+            });
+        
+        if let Some(stats) = &self.word_list.stats {
+            egui::CollapsingHeader::new("📏 Statistics")
+                .id_source("dictionary_stats")
+                .show(ui, |ui| {
+                    ui.label(format!("Average length: {:.1} chars", stats.average_length));
+                    ui.label(format!(
+                        "With digits: {}, hyphens: {}, apostrophes: {}",
+                        stats.with_digits, stats.with_hyphens, stats.with_apostrophes
+                    ));
+                    
+                    let largest = stats.length_histogram.iter().copied().max().unwrap_or(0).max(1);
+                    for (length, &count) in stats.length_histogram.iter().enumerate().filter(|(_, &c)| c > 0) {
+                        ui.horizontal(|ui| {
+                            ui.monospace(format!("{:>3}", length));
+                            ui.add(egui::ProgressBar::new(count as f32 / largest as f32)
+                                .desired_width(120.0)
+                                .text(format!("{}", count)));
+                        });
+                    }
+                });
+        }
+        
+        egui::CollapsingHeader::new("🔠 Acronyms")
+            .id_source("dictionary_acronyms")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.text_edit_singleline(&mut self.new_acronym);
+                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    let add_enabled = !self.new_acronym.trim().is_empty();
+                    if add_enabled && (submitted || ui.button("Add").clicked()) {
+                        *on_add_acronym = Some(std::mem::take(&mut self.new_acronym));
+                    }
+                });
+                
+                egui::ScrollArea::vertical()
+                    .id_source("dictionary_acronym_list")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for acronym in spell_checker.acronyms() {
+                            ui.horizontal(|ui| {
+                                ui.label(&acronym);
+                                if ui.small_button("🗑").on_hover_text("Remove acronym").clicked() {
+                                    *on_remove_acronym = Some(acronym.clone());
+                                }
+                            });
+                        }
+                    });
+            });
+        
         ui.separator();
         
         ui.label("ℹ️ Added words are saved permanently");
         ui.label("Ignored words are session-only");
     }
     
+    fn refresh_word_list(&mut self, spell_checker: &SpellChecker, word_count: usize) {
+        let key = (
+            spell_checker.current_language().code().to_string(),
+            self.dictionary_filter.clone(),
+            self.show_ignored_words,
+            word_count,
+            spell_checker.user_word_count(),
+            spell_checker.ignored_word_count(),
+        );
+        if self.word_list.key.as_ref() == Some(&key) {
+            return;
+        }
+        
+        self.word_list.stats = None;
+        self.word_list.words = match spell_checker.get_current_dictionary() {
+            Ok(dict) => {
+                self.word_list.stats = Some(dict.stats());
+                let words = if self.show_ignored_words {
+                    dict.filtered_ignored_words(&self.dictionary_filter, usize::MAX)
+                } else {
+                    dict.filtered_words(&self.dictionary_filter, usize::MAX)
+                };
+                words
+                    .into_iter()
+                    .map(|w| (w.clone(), dict.get_user_words().contains(w)))
+                    .collect()
+            }
+            Err(_) => Vec::new(),
+        };
+        self.word_list.key = Some(key);
+    }
+    
+    #[allow(clippy::too_many_arguments)]
     fn show_errors_view(
         &mut self,
         ui: &mut egui::Ui,
+        spell_checker: &SpellChecker,
         analysis: &Option<DocumentAnalysis>,
+        analysis_generation: u64,
+        content: &str,
         on_replace: &mut Option<(String, String)>,
+        on_fix_all: &mut Option<Vec<(usize, usize, String, String)>>,
     ) {
         ui.heading("Spelling Errors");
         
@@ -239,27 +509,41 @@ impl Sidebar {
             ui.radio_value(&mut self.error_filter, ErrorFilter::HighConfidence, "High Confidence");
             ui.radio_value(&mut self.error_filter, ErrorFilter::CodeIdentifiers, "Code");
             ui.radio_value(&mut self.error_filter, ErrorFilter::ProperNouns, "Proper Nouns");
+            ui.radio_value(&mut self.error_filter, ErrorFilter::Urls, "URLs");
+            ui.radio_value(&mut self.error_filter, ErrorFilter::Emails, "Emails");
+            if self.show_low_confidence {
+                ui.radio_value(&mut self.error_filter, ErrorFilter::LowConfidence, "Low Confidence");
+            }
+        });
+        
+        ui.horizontal(|ui| {
+            if ui.checkbox(&mut self.show_low_confidence, "Show low-confidence").changed()
+                && !self.show_low_confidence && self.error_filter == ErrorFilter::LowConfidence {
+                self.error_filter = ErrorFilter::All;
+            }
+            ui.label("Suggestions shown:");
+            ui.add(egui::DragValue::new(&mut self.suggestions_display_limit).clamp_range(1..=20));
         });
         
         if let Some(analysis) = analysis {
-            if analysis.misspelled_words == 0 {
+            let low_confidence = self.low_confidence_words(analysis);
+            
+            if analysis.misspelled_words == 0 && low_confidence.is_empty() {
                 ui.colored_label(egui::Color32::GREEN, "✅ No spelling errors found!");
                 return;
             }
             
-            let filtered_errors: Vec<&crate::checker::WordCheck> = analysis.words
-                .iter()
-                .filter(|w| !w.is_correct)
-                .filter(|w| match self.error_filter {
-                    ErrorFilter::All => true,
-                    ErrorFilter::HighConfidence => w.confidence >= 0.8,
-                    ErrorFilter::CodeIdentifiers => matches!(w.word_type, WordType::CodeIdentifier),
-                    ErrorFilter::ProperNouns => matches!(w.word_type, WordType::ProperNoun),
-                    ErrorFilter::Numbers => matches!(w.word_type, WordType::Number),
-                })
-                .collect();
+            if analysis.truncated {
+                let shown = analysis.words.iter().filter(|w| !w.is_correct).count();
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("⚠ Showing the first {} of {} errors", shown, analysis.misspelled_words),
+                );
+            }
             
-            if filtered_errors.is_empty() {
+            let filtered_errors = self.filtered_errors(analysis);
+            
+            if filtered_errors.is_empty() && low_confidence.is_empty() {
                 ui.label("No errors match the current filter");
                 return;
             }
@@ -270,15 +554,33 @@ impl Sidebar {
                     
                     ui.horizontal(|ui| {
                         let color = match word.word_type {
+                            _ if word.kind == IssueKind::Capitalization => egui::Color32::LIGHT_GREEN,
+                            _ if word.kind == IssueKind::MixedScript => egui::Color32::from_rgb(255, 140, 0),
+                            _ if word.kind == IssueKind::Whitespace => egui::Color32::GRAY,
                             WordType::CodeIdentifier => egui::Color32::BLUE,
                             WordType::ProperNoun => egui::Color32::YELLOW,
                             WordType::Acronym => egui::Color32::LIGHT_BLUE,
+                            WordType::RepeatedWord => egui::Color32::GOLD,
                             _ => egui::Color32::RED,
                         };
                         
-                        ui.colored_label(color, "✗");
+                        let icon = match (word.kind, &word.word_type) {
+                            (IssueKind::Capitalization, _) => "🔠",
+                            (IssueKind::MixedScript, _) => "⚠",
+                            (IssueKind::Whitespace, _) => "␣",
+                            (_, WordType::RepeatedWord) => "🔁",
+                            _ => "✗",
+                        };
+                        ui.colored_label(color, icon);
                         
-                        if ui.selectable_label(is_selected, &word.word).clicked() {
+                        let mut label = ui.selectable_label(is_selected, word.display_text());
+                        if word.kind != IssueKind::Whitespace {
+                            let explanation = &mut self.explanation;
+                            label = label.on_hover_ui(|ui| {
+                                ui.label(explanation.refresh(spell_checker, &word.original, analysis.likely_code, analysis_generation));
+                            });
+                        }
+                        if label.clicked() {
                             self.selected_error_index = idx;
                         }
                         
@@ -288,32 +590,82 @@ impl Sidebar {
                             egui::Color32::GRAY,
                             format!("{:.0}%", word.confidence * 100.0)
                         );
+                        
+                        if let Some(note) = word.note() {
+                            ui.colored_label(egui::Color32::LIGHT_BLUE, note);
+                        }
                     });
                     
+                    if let Some(context) = &word.context {
+                        ui.indent("context", |ui| {
+                            ui.label(context_layout(ui, context, word.original.len()));
+                        });
+                    }
+                    
                     if !word.suggestions.is_empty() {
                         ui.indent("suggestions", |ui| {
                             ui.label("Suggestions:");
-                            for suggestion in &word.suggestions {
+                            // The order is kept, so the first one listed is
+                            // still the one the shortcuts apply
+                            let key = (word.line, word.column);
+                            let expanded = self.expanded_suggestions.contains(&key);
+                            let shown = if expanded { word.suggestions.len() } else { self.suggestions_display_limit.max(1) };
+                            for suggestion in word.suggestions.iter().take(shown) {
                                 ui.horizontal(|ui| {
                                     if ui.button("Use").clicked() {
-                                        *on_replace = Some((word.word.clone(), suggestion.clone()));
+                                        // Replacing every run of spaces in
+                                        // the document would be too much
+                                        if word.kind == IssueKind::Whitespace {
+                                            *on_fix_all = Some(vec![(word.start, word.end, word.original.clone(), suggestion.clone())]);
+                                        } else {
+                                            *on_replace = Some((word.original.clone(), word.replacement(suggestion)));
+                                        }
                                     }
-                                    ui.label(suggestion);
+                                    ui.label(word.suggestion_label(suggestion));
                                 });
                             }
+                            
+                            let hidden = word.suggestions.len().saturating_sub(self.suggestions_display_limit.max(1));
+                            if hidden > 0 {
+                                let toggle = if expanded { "less".to_string() } else { format!("{} more…", hidden) };
+                                if ui.small_button(toggle).clicked() && !self.expanded_suggestions.remove(&key) {
+                                    self.expanded_suggestions.insert(key);
+                                }
+                            }
                         });
                     }
                     
                     ui.separator();
                 }
+                
+                if !low_confidence.is_empty() {
+                    ui.colored_label(egui::Color32::GRAY, format!("Low confidence ({})", low_confidence.len()));
+                    for word in &low_confidence {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(egui::Color32::GRAY, "?");
+                            ui.colored_label(egui::Color32::GRAY, word.display_word());
+                            ui.colored_label(egui::Color32::GRAY, format!("(L{}:C{})", word.line, word.column));
+                            ui.colored_label(egui::Color32::GRAY, format!("{:.0}%", word.confidence * 100.0));
+                        });
+                    }
+                }
             });
             
             ui.separator();
             ui.horizontal(|ui| {
                 ui.label(format!("Errors: {}/{}", filtered_errors.len(), analysis.misspelled_words));
-                if analysis.misspelled_words > 0 {
-                    if ui.button("▶️ Fix All").clicked() {
-                        ui.label("Feature coming soon...");
+                if !filtered_errors.is_empty() && ui.button("📋 Copy Errors").on_hover_text("Copy the listed errors as text").clicked() {
+                    ui.ctx().copy_text(errors_to_text(&filtered_errors));
+                }
+                if analysis.misspelled_words > 0 && ui.button("▶️ Fix All").clicked() {
+                    // fix_all_edits only returns edits whose range holds
+                    // the original word, so it can be read back from `content`
+                    let edits: Vec<_> = analysis.fix_all_edits(content, spell_checker.confidence_threshold())
+                        .into_iter()
+                        .map(|(start, end, replacement)| (start, end, content[start..end].to_string(), replacement))
+                        .collect();
+                    if !edits.is_empty() {
+                        *on_fix_all = Some(edits);
                     }
                 }
             });
@@ -322,11 +674,60 @@ impl Sidebar {
         }
     }
     
+    /// Errors listed in the Errors tab under the current filter
+    pub fn filtered_errors<'a>(&self, analysis: &'a DocumentAnalysis) -> Vec<&'a WordCheck> {
+        analysis.words
+            .iter()
+            .filter(|w| !w.is_correct)
+            .filter(|w| match self.error_filter {
+                ErrorFilter::All => true,
+                ErrorFilter::HighConfidence => w.confidence >= 0.8,
+                ErrorFilter::CodeIdentifiers => matches!(w.word_type, WordType::CodeIdentifier),
+                ErrorFilter::ProperNouns => matches!(w.word_type, WordType::ProperNoun),
+                ErrorFilter::Numbers => matches!(w.word_type, WordType::Number),
+                ErrorFilter::Urls => matches!(w.word_type, WordType::Url),
+                ErrorFilter::Emails => matches!(w.word_type, WordType::Email),
+                ErrorFilter::LowConfidence => false,
+            })
+            .collect()
+    }
+    
+    /// Move `selected_error_index` to the next or previous of `count`
+    /// errors, wrapping around at either end
+    pub fn step_selected_error(&mut self, count: usize, forward: bool) {
+        self.selected_error_index = wrapping_step(self.selected_error_index, count, forward);
+    }
+    
+    /// The selected error's first suggestion as a `(start, end, original,
+    /// replacement)` edit, for Ctrl+.
+    pub fn selected_error_fix(&self, analysis: &DocumentAnalysis) -> Option<(usize, usize, String, String)> {
+        let word = *self.filtered_errors(analysis).get(self.selected_error_index)?;
+        let suggestion = word.suggestions.first()?;
+        Some((word.start, word.end, word.original.clone(), word.replacement(suggestion)))
+    }
+    
+    /// Words the dictionary rejected but whose confidence fell below the
+    /// checker's threshold, so they aren't counted as errors. Empty unless
+    /// `show_low_confidence` is on.
+    pub fn low_confidence_words<'a>(&self, analysis: &'a DocumentAnalysis) -> Vec<&'a WordCheck> {
+        if !self.show_low_confidence {
+            return Vec::new();
+        }
+        
+        analysis.words
+            .iter()
+            .filter(|w| w.raw_misspelled && w.is_correct)
+            .collect()
+    }
+    
     fn show_stats_view(
         &mut self,
         ui: &mut egui::Ui,
         analysis: &Option<DocumentAnalysis>,
         spell_checker: &SpellChecker,
+        check_history: &CheckHistory,
+        on_export_report: &mut Option<ReportFormat>,
+        word_goal: &mut Option<usize>,
     ) {
         ui.heading("Document Statistics");
         
@@ -339,6 +740,22 @@ impl Sidebar {
                 ui.add(gauge);
             });
             
+            ui.horizontal(|ui| {
+                ui.label("🎯 Word goal:");
+                let mut goal = word_goal.unwrap_or(0);
+                let input = ui.add(egui::DragValue::new(&mut goal).speed(10.0).clamp_range(0..=1_000_000));
+                if input.on_hover_text("0 for no goal").changed() {
+                    *word_goal = (goal > 0).then_some(goal);
+                }
+            });
+            if let Some(goal) = *word_goal {
+                let (progress, caption) = word_goal_progress(analysis.total_words, goal);
+                ui.horizontal(|ui| {
+                    ui.add(egui::widgets::ProgressBar::new(progress).show_percentage().desired_width(150.0));
+                    ui.label(caption);
+                });
+            }
+            
             ui.separator();
             
             egui::Grid::new("stats_grid")
@@ -406,11 +823,87 @@ impl Sidebar {
                 ui.label(format!("🔤 Average word length: {:.1} chars", 
                     characters as f32 / analysis.total_words as f32));
             }
+            
+            if check_history.len() > 1 {
+                ui.separator();
+                ui.label("📈 Accuracy trend:");
+                let points: PlotPoints = check_history
+                    .records()
+                    .enumerate()
+                    .map(|(i, record)| [i as f64, record.accuracy as f64])
+                    .collect();
+                Plot::new("accuracy_trend")
+                    .height(120.0)
+                    .include_y(0.0)
+                    .include_y(100.0)
+                    .allow_drag(false)
+                    .allow_zoom(false)
+                    .allow_scroll(false)
+                    .show_x(false)
+                    .show(ui, |plot_ui| plot_ui.line(Line::new(points).name("Accuracy %")));
+            }
+            
+            ui.separator();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("report_format")
+                    .selected_text(self.report_format.name())
+                    .show_ui(ui, |ui| {
+                        for format in [ReportFormat::Text, ReportFormat::Markdown, ReportFormat::Csv] {
+                            ui.selectable_value(&mut self.report_format, format, format.name());
+                        }
+                    });
+                
+                if ui.button("📄 Export Report").clicked() {
+                    *on_export_report = Some(self.report_format);
+                }
+            });
         } else {
             ui.label("No statistics available. Load a document first.");
         }
     }
     
+    fn show_frequency_view(
+        &mut self,
+        ui: &mut egui::Ui,
+        spell_checker: &SpellChecker,
+        analysis: &Option<DocumentAnalysis>,
+        content: &str,
+    ) {
+        ui.heading("Word Frequency");
+        
+        ui.horizontal(|ui| {
+            ui.label("Show top:");
+            ui.add(egui::DragValue::new(&mut self.frequency_top).clamp_range(5..=200));
+        });
+        
+        let is_cjk = matches!(spell_checker.current_language(), Language::Chinese | Language::Japanese | Language::Korean);
+        let is_code = analysis.as_ref().map_or_else(|| crate::util::is_likely_code(content), |a| a.likely_code);
+        let words = self.frequency.refresh(content, is_cjk, is_code, self.frequency_top);
+        
+        if words.is_empty() {
+            ui.label("No words yet. Load or type a document first.");
+            return;
+        }
+        
+        let largest = words.first().map_or(1, |(_, count)| (*count).max(1));
+        egui::ScrollArea::vertical()
+            .id_source("frequency_list")
+            .show(ui, |ui| {
+                egui::Grid::new("frequency_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        for (word, count) in words {
+                            ui.label(word);
+                            ui.add(egui::ProgressBar::new(*count as f32 / largest as f32)
+                                .desired_width(120.0)
+                                .text(format!("{}", count)));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+    
     fn show_find_view(&mut self, ui: &mut egui::Ui, content: &str) {
         ui.heading("Find in Document");
         
@@ -431,11 +924,7 @@ impl Sidebar {
         }
         
         if !self.find_text.is_empty() {
-            let count = if self.case_sensitive_find {
-                content.matches(&self.find_text).count()
-            } else {
-                content.to_lowercase().matches(&self.find_text.to_lowercase()).count()
-            };
+            let count = self.count_matches(content);
             
             if count > 0 {
                 ui.colored_label(egui::Color32::GREEN, format!("Found {} occurrences", count));
@@ -473,15 +962,32 @@ impl Sidebar {
         });
         
         if !self.find_text.is_empty() {
-            let count = if self.case_sensitive_find {
-                content.matches(&self.find_text).count()
-            } else {
-                content.to_lowercase().matches(&self.find_text.to_lowercase()).count()
-            };
+            let count = self.count_matches(content);
             ui.label(format!("Found {} occurrences", count));
         }
     }
     
+    /// Count occurrences of `find_text` in `content`, honouring the case
+    /// sensitivity and whole-word options
+    pub fn count_matches(&self, content: &str) -> usize {
+        if self.find_text.is_empty() {
+            return 0;
+        }
+        
+        let escaped = regex::escape(&self.find_text);
+        let pattern = if self.whole_word_find {
+            format!(r"\b{}\b", escaped)
+        } else {
+            escaped
+        };
+        
+        regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive_find)
+            .build()
+            .map(|re| re.find_iter(content).count())
+            .unwrap_or(0)
+    }
+    
     pub fn visible(&self) -> bool {
         self.visible
     }
@@ -493,4 +999,114 @@ impl Sidebar {
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
-}
\ No newline at end of file
+}
+
+/// Index after stepping once from `index` through `len` items. An index
+/// that is already out of range restarts from the first (or last) item.
+fn wrapping_step(index: usize, len: usize, forward: bool) -> usize {
+    match (len, forward) {
+        (0, _) => 0,
+        (_, true) if index >= len => 0,
+        (_, false) if index >= len => len - 1,
+        (_, true) => (index + 1) % len,
+        (_, false) => (index + len - 1) % len,
+    }
+}
+
+/// `context` as weak text with the word underlined in red
+fn context_layout(ui: &egui::Ui, context: &WordContext, word_len: usize) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Small.resolve(ui.style());
+    let plain = egui::TextFormat::simple(font_id.clone(), ui.visuals().weak_text_color());
+    let highlighted = egui::TextFormat {
+        underline: egui::Stroke::new(1.0, egui::Color32::RED),
+        ..egui::TextFormat::simple(font_id, ui.visuals().text_color())
+    };
+    
+    let word_end = (context.offset + word_len).min(context.text.len());
+    let mut job = egui::text::LayoutJob::default();
+    job.append(&context.text[..context.offset], 0.0, plain.clone());
+    job.append(&context.text[context.offset..word_end], 0.0, highlighted);
+    job.append(&context.text[word_end..], 0.0, plain);
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker_with_words;
+    
+    #[test]
+    fn whole_word_find_skips_matches_inside_words() {
+        let mut sidebar = Sidebar::new();
+        sidebar.find_text = "is".to_string();
+        
+        sidebar.whole_word_find = true;
+        assert_eq!(sidebar.count_matches("this is it"), 1);
+        
+        sidebar.whole_word_find = false;
+        assert_eq!(sidebar.count_matches("this is it"), 2);
+    }
+    
+    #[test]
+    fn low_confidence_words_are_listed_only_when_shown() {
+        let (checker, _dir) = checker_with_words(&["the", "cat"]);
+        // Plain misspellings fall just below the default threshold
+        let analysis = checker.check_document("The cat blorf");
+        assert_eq!(analysis.misspelled_words, 0);
+        
+        let mut sidebar = Sidebar::new();
+        assert!(sidebar.low_confidence_words(&analysis).is_empty());
+        
+        sidebar.show_low_confidence = true;
+        let words: Vec<&str> = sidebar.low_confidence_words(&analysis).iter().map(|w| w.original.as_str()).collect();
+        assert_eq!(words, vec!["blorf"]);
+    }
+    
+    #[test]
+    fn error_navigation_wraps_around() {
+        assert_eq!(wrapping_step(0, 3, true), 1);
+        assert_eq!(wrapping_step(2, 3, true), 0);
+        assert_eq!(wrapping_step(0, 3, false), 2);
+        assert_eq!(wrapping_step(7, 3, true), 0);
+        assert_eq!(wrapping_step(7, 3, false), 2);
+        assert_eq!(wrapping_step(0, 0, true), 0);
+    }
+    
+    #[test]
+    fn word_frequencies_are_recounted_only_when_the_text_changes() {
+        let mut cache = FrequencyCache::default();
+        let top = cache.refresh("the cat and the dog and the bird", false, false, 2).to_vec();
+        assert_eq!(top, vec![("the".to_string(), 3), ("and".to_string(), 2)]);
+        
+        // Same key: the cached list is returned as is
+        cache.words.clear();
+        assert!(cache.refresh("the cat and the dog and the bird", false, false, 2).is_empty());
+        
+        assert_eq!(cache.refresh("the cat and the dog and the bird", false, false, 1), [("the".to_string(), 3)]);
+        assert_eq!(cache.refresh("cat cat dog", false, false, 1), [("cat".to_string(), 2)]);
+    }
+    
+    #[test]
+    fn word_goal_progress_stops_at_the_goal() {
+        assert_eq!(word_goal_progress(250, 1000), (0.25, "750 words to go".to_string()));
+        assert_eq!(word_goal_progress(999, 1000).1, "1 word to go");
+        assert_eq!(word_goal_progress(1200, 1000), (1.0, "🎉 Goal reached".to_string()));
+        assert_eq!(word_goal_progress(10, 0), (1.0, "No goal set".to_string()));
+    }
+    
+    #[test]
+    fn explanations_are_worked_out_once_per_word_and_check() {
+        let (checker, _dir) = checker_with_words(&["the", "cat"]);
+        
+        let mut cache = ExplanationCache::default();
+        let summary = cache.refresh(&checker, "blorf", false, 1).to_string();
+        assert_eq!(summary, checker.explain("blorf", false).summary());
+        
+        // Same word and check: the cached text is returned as is
+        cache.summary.clear();
+        assert!(cache.refresh(&checker, "blorf", false, 1).is_empty());
+        
+        assert_eq!(cache.refresh(&checker, "blorf", false, 2), summary);
+        assert_eq!(cache.refresh(&checker, "cat", false, 2), checker.explain("cat", false).summary());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::dictionary::Dictionary;
+use crate::language::Language;
+use crate::util::bounded_levenshtein;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Dictionary words further than this many edits away are never suggested
+const MAX_EDIT_DISTANCE: usize = 2;
+/// Cost of substituting a neighboring key when ranking keyboard-aware
+const ADJACENT_KEY_COST: f32 = 0.5;
+
+/// Keyboard layouts for keyboard-aware suggestion ranking
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Qwertz,
+}
+
+impl KeyboardLayout {
+    /// The letter rows from top to bottom, each staggered half a key right
+    /// of the one above
+    fn rows(&self) -> [&'static str; 3] {
+        match self {
+            KeyboardLayout::Qwerty => ["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            KeyboardLayout::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn"],
+            KeyboardLayout::Qwertz => ["qwertzuiopü", "asdfghjklöä", "yxcvbnm"],
+        }
+    }
+    
+    fn position(&self, c: char) -> Option<(usize, usize)> {
+        let c = c.to_lowercase().next()?;
+        self.rows()
+            .iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.chars().position(|key| key == c).map(|col| (row, col)))
+    }
+    
+    /// Whether `a` and `b` are different letter keys next to each other,
+    /// in the same row or diagonally
+    pub fn are_adjacent(&self, a: char, b: char) -> bool {
+        let (Some((row_a, col_a)), Some((row_b, col_b))) = (self.position(a), self.position(b)) else {
+            return false;
+        };
+        
+        match row_b as isize - row_a as isize {
+            0 => col_a.abs_diff(col_b) == 1,
+            // The row below is shifted right, so its key at `col` sits
+            // under keys `col` and `col + 1` of the row above
+            1 => col_b == col_a || col_b + 1 == col_a,
+            -1 => col_a == col_b || col_a + 1 == col_b,
+            _ => false,
+        }
+    }
+}
+
+/// Produces replacement candidates for a misspelled word. Implement this to
+/// plug in another engine (a model, an external service) and install it with
+/// `SpellChecker::set_suggestion_provider`. The checker caches results and
+/// keeps only the first `max_suggestions`, so return the best first.
+pub trait SuggestionProvider: Send + Sync {
+    fn suggest(&self, word: &str, language: Language, dictionary: &Dictionary) -> Vec<String>;
+}
+
+/// The built-in provider: dictionary words within two edits, ranked by
+/// distance with a bonus for common words
+#[derive(Debug, Clone)]
+pub struct EditDistanceProvider {
+    frequency_weight: f32,
+    keyboard_layout: Option<KeyboardLayout>,
+}
+
+impl Default for EditDistanceProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditDistanceProvider {
+    pub fn new() -> Self {
+        Self { frequency_weight: 1.5, keyboard_layout: None }
+    }
+    
+    pub fn frequency_weight(&self) -> f32 {
+        self.frequency_weight
+    }
+    
+    /// How many edits of distance the most common dictionary word is worth
+    /// when ranking; 0.0 ranks by edit distance alone
+    pub fn set_frequency_weight(&mut self, weight: f32) {
+        self.frequency_weight = weight.max(0.0);
+    }
+    
+    pub fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        self.keyboard_layout
+    }
+    
+    /// Rank neighboring-key slips on `layout` ("thr" for "the") as closer
+    /// than other substitutions; `None` counts every edit the same
+    pub fn set_keyboard_layout(&mut self, layout: Option<KeyboardLayout>) {
+        self.keyboard_layout = layout;
+    }
+}
+
+impl SuggestionProvider for EditDistanceProvider {
+    fn suggest(&self, word: &str, _language: Language, dictionary: &Dictionary) -> Vec<String> {
+        // Every word within reach is scored: sampling the set would make
+        // the result depend on hash order and change from run to run
+        let word_chars: Vec<char> = word.chars().collect();
+        let candidates: Vec<&String> = dictionary.get_words().iter()
+            .filter(|w| w.len().abs_diff(word.len()) <= MAX_EDIT_DISTANCE * 4)
+            .collect();
+        
+        let suggestions: Vec<(String, usize)> = candidates
+            .par_iter()
+            .filter_map(|&dict_word| {
+                bounded_levenshtein(&word_chars, dict_word, MAX_EDIT_DISTANCE).map(|distance| (dict_word.clone(), distance))
+            })
+            .collect();
+        
+        // Common words earn up to `frequency_weight` edits of credit, so a
+        // very common word one edit further away can still rank first. Ties
+        // (and dictionaries without frequencies) fall back to alphabetical.
+        let mut ranked: Vec<(f32, String)> = suggestions
+            .into_iter()
+            .map(|(candidate, distance)| {
+                let distance = match self.keyboard_layout {
+                    Some(layout) => keyboard_distance(word, &candidate, layout),
+                    None => distance as f32,
+                };
+                let score = distance - self.frequency_weight * dictionary.relative_frequency(&candidate);
+                (score, candidate)
+            })
+            .collect();
+        ranked.sort_by(|(a_score, a), (b_score, b)| a_score.total_cmp(b_score).then_with(|| a.cmp(b)));
+        
+        ranked.into_iter().map(|(_, word)| word).collect()
+    }
+}
+
+/// Damerau-Levenshtein distance (optimal string alignment) where
+/// substituting a neighboring key on `layout` costs `ADJACENT_KEY_COST`
+fn keyboard_distance(a: &str, b: &str, layout: KeyboardLayout) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    
+    let mut rows = vec![vec![0.0f32; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i as f32;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j as f32;
+    }
+    
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = if a[i - 1] == b[j - 1] {
+                0.0
+            } else if layout.are_adjacent(a[i - 1], b[j - 1]) {
+                ADJACENT_KEY_COST
+            } else {
+                1.0
+            };
+            
+            let mut cost = (rows[i - 1][j] + 1.0)
+                .min(rows[i][j - 1] + 1.0)
+                .min(rows[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost = cost.min(rows[i - 2][j - 2] + 1.0);
+            }
+            rows[i][j] = cost;
+        }
+    }
+    
+    rows[a.len()][b.len()]
+}
@@ -11,6 +11,7 @@ pub enum AtomTheme {
     Dracula,
     GruvboxDark,
     Nord,
+    HighContrast,
 }
 
 impl AtomTheme {
@@ -24,6 +25,7 @@ impl AtomTheme {
             AtomTheme::Dracula,
             AtomTheme::GruvboxDark,
             AtomTheme::Nord,
+            AtomTheme::HighContrast,
         ]
     }
     
@@ -37,10 +39,15 @@ impl AtomTheme {
             AtomTheme::Dracula => "Dracula",
             AtomTheme::GruvboxDark => "Gruvbox Dark",
             AtomTheme::Nord => "Nord",
+            AtomTheme::HighContrast => "High Contrast",
         }
     }
     
     pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_visuals(self.visuals());
+    }
+    
+    pub fn visuals(&self) -> egui::Visuals {
         let mut visuals = match self {
             AtomTheme::OneDark => egui::Visuals::dark(),
             AtomTheme::OneLight => egui::Visuals::light(),
@@ -50,6 +57,7 @@ impl AtomTheme {
             AtomTheme::Dracula => dracula(),
             AtomTheme::GruvboxDark => gruvbox_dark(),
             AtomTheme::Nord => nord(),
+            AtomTheme::HighContrast => high_contrast(),
         };
         
         match self {
@@ -89,17 +97,56 @@ impl AtomTheme {
                 visuals.widgets.hovered.fg_stroke.color = egui::Color32::from_rgb(236, 239, 244);
                 visuals.widgets.active.fg_stroke.color = egui::Color32::from_rgb(216, 222, 233);
             }
+            AtomTheme::HighContrast => {
+                visuals.widgets.noninteractive.fg_stroke.color = egui::Color32::WHITE;
+                visuals.widgets.inactive.fg_stroke.color = egui::Color32::WHITE;
+                visuals.widgets.hovered.fg_stroke.color = egui::Color32::from_rgb(255, 255, 0);
+                visuals.widgets.active.fg_stroke.color = egui::Color32::from_rgb(255, 255, 0);
+            }
             _ => {}
         }
         
-        ctx.set_visuals(visuals);
+        visuals
+    }
+    
+    /// Underline for spelling errors, chosen to stand out against the
+    /// theme's editor background
+    pub fn error_underline_color(&self) -> egui::Color32 {
+        match self {
+            AtomTheme::OneDark => egui::Color32::from_rgb(255, 92, 92),
+            AtomTheme::OneLight => egui::Color32::from_rgb(202, 18, 67),
+            AtomTheme::SolarizedDark => egui::Color32::from_rgb(240, 80, 70),
+            AtomTheme::SolarizedLight => egui::Color32::from_rgb(220, 50, 47),
+            AtomTheme::Monokai => egui::Color32::from_rgb(249, 38, 114),
+            AtomTheme::Dracula => egui::Color32::from_rgb(255, 85, 85),
+            AtomTheme::GruvboxDark => egui::Color32::from_rgb(251, 73, 52),
+            AtomTheme::Nord => egui::Color32::from_rgb(229, 112, 122),
+            AtomTheme::HighContrast => egui::Color32::from_rgb(255, 70, 70),
+        }
+    }
+    
+    /// Underline for softer issues such as proper nouns, acronyms and
+    /// repeated words
+    pub fn suggestion_underline_color(&self) -> egui::Color32 {
+        match self {
+            AtomTheme::OneDark => egui::Color32::from_rgb(229, 192, 123),
+            AtomTheme::OneLight => egui::Color32::from_rgb(152, 104, 1),
+            AtomTheme::SolarizedDark => egui::Color32::from_rgb(203, 150, 0),
+            AtomTheme::SolarizedLight => egui::Color32::from_rgb(203, 75, 22),
+            AtomTheme::Monokai => egui::Color32::from_rgb(230, 219, 116),
+            AtomTheme::Dracula => egui::Color32::from_rgb(241, 250, 140),
+            AtomTheme::GruvboxDark => egui::Color32::from_rgb(250, 189, 47),
+            AtomTheme::Nord => egui::Color32::from_rgb(235, 203, 139),
+            AtomTheme::HighContrast => egui::Color32::from_rgb(255, 255, 0),
+        }
     }
     
     pub fn is_dark(&self) -> bool {
         match self {
             AtomTheme::OneDark | AtomTheme::SolarizedDark | 
             AtomTheme::Monokai | AtomTheme::Dracula |
-            AtomTheme::GruvboxDark | AtomTheme::Nord => true,
+            AtomTheme::GruvboxDark | AtomTheme::Nord |
+            AtomTheme::HighContrast => true,
             AtomTheme::OneLight | AtomTheme::SolarizedLight => false,
         }
     }
@@ -187,4 +234,47 @@ fn nord() -> egui::Visuals {
     visuals.error_fg_color = egui::Color32::from_rgb(191, 97, 106);
     visuals.hyperlink_color = egui::Color32::from_rgb(136, 192, 208);
     visuals
-}
\ No newline at end of file
+}
+
+fn high_contrast() -> egui::Visuals {
+    let mut visuals = egui::Visuals::dark();
+    visuals.override_text_color = Some(egui::Color32::WHITE);
+    visuals.window_fill = egui::Color32::BLACK;
+    visuals.panel_fill = egui::Color32::BLACK;
+    visuals.window_stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+    visuals.faint_bg_color = egui::Color32::from_gray(40);
+    visuals.extreme_bg_color = egui::Color32::BLACK;
+    visuals.code_bg_color = egui::Color32::from_gray(25);
+    visuals.warn_fg_color = egui::Color32::from_rgb(255, 255, 0);
+    visuals.error_fg_color = egui::Color32::from_rgb(255, 70, 70);
+    visuals.hyperlink_color = egui::Color32::from_rgb(0, 255, 255);
+    visuals.selection.bg_fill = egui::Color32::from_rgb(0, 90, 200);
+    visuals.selection.stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+    visuals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// WCAG contrast ratio between two colours, from 1.0 to 21.0
+    fn contrast(a: egui::Color32, b: egui::Color32) -> f32 {
+        let luminance = |c: egui::Color32| {
+            let channel = |v: u8| {
+                let v = v as f32 / 255.0;
+                if v <= 0.03928 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+            };
+            0.2126 * channel(c.r()) + 0.7152 * channel(c.g()) + 0.0722 * channel(c.b())
+        };
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+    
+    #[test]
+    fn error_underlines_stand_out_from_the_background() {
+        for theme in AtomTheme::all() {
+            let ratio = contrast(theme.error_underline_color(), theme.visuals().window_fill);
+            assert!(ratio >= 3.0, "{} underline contrast is {:.2}", theme.name(), ratio);
+        }
+    }
+}
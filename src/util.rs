@@ -1,21 +1,61 @@
 use regex::Regex;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
+use unicode_script::{Script, UnicodeScript};
 
 // Compile regex only once for better performance
 pub static WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\b[\p{L}][\p{L}'-]*\b").unwrap()
+    Regex::new(r"\b[\p{L}][\p{L}'’ʼ‐‑–-]*\b").unwrap()
 });
 
 // New regex for CJK languages (Chinese, Japanese, Korean)
 pub static CJK_WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"[\p{Han}\p{Hiragana}\p{Katakana}\p{Hangul}]+|[\p{L}][\p{L}'-]*").unwrap()
+    Regex::new(r"[\p{Han}\p{Hiragana}\p{Katakana}\p{Hangul}]+|[\p{L}][\p{L}'’ʼ‐‑–-]*").unwrap()
+});
+
+// CJK runs (with the long-vowel mark "ー", which isn't in the kana scripts)
+// split from runs of Latin letters and digits, so "新しいiPhone12を" is
+// "新しい", "iPhone12", "を"
+pub static CJK_MIXED_WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\p{Han}\p{Hiragana}\p{Katakana}\p{Hangul}ー]+|[\p{Latin}\p{N}][\p{Latin}\p{N}'’ʼ‐‑–-]*").unwrap()
+});
+
+// Regex for Arabic and Hebrew, whose vowel points are combining marks
+pub static RTL_WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\p{L}][\p{L}\p{M}'’ʼ‐‑–-]*").unwrap()
 });
 
 // Regex for programming languages (ignores common code patterns)
 pub static CODE_WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
     // Match words but ignore common programming patterns
-    Regex::new(r"\b([a-zA-Z][a-zA-Z'-]{2,})\b").unwrap()
+    Regex::new(r"\b([a-zA-Z][a-zA-Z'’ʼ-]{2,})\b").unwrap()
+});
+
+// Regex for whole code identifiers, including snake_case underscores
+pub static IDENTIFIER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[a-zA-Z][a-zA-Z0-9_]*\b").unwrap()
+});
+
+// Regex for URLs and email addresses, which are checked as a whole rather
+// than split into words
+pub static LINK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?P<url>\b(?:https?|ftp)://[^\s<>"']*[^\s<>"'.,;:!?)\]]|\bwww\.[^\s<>"']*[^\s<>"'.,;:!?)\]])|(?P<email>\b[\w.%+-]+@[\w-]+(?:\.[\w-]+)*\.\p{L}{2,}\b)"#).unwrap()
+});
+
+// Regex for two or more spaces in a row
+pub static MULTIPLE_SPACES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r" {2,}").unwrap()
+});
+
+// Regex for the HTML attributes whose values are shown to readers
+static HTML_TEXT_ATTRIBUTE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\s(?:alt|title)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+
+// Regex for character references such as `&amp;` and `&#233;`
+static HTML_ENTITY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"&#?[A-Za-z0-9]+;").unwrap()
 });
 
 // Regex to identify code-specific patterns to ignore
@@ -44,12 +84,28 @@ static CODE_KEYWORDS: Lazy<Vec<&'static str>> = Lazy::new(|| {
     ]
 });
 
+/// Map typographic apostrophes (’, ʼ) to `'` and hyphen variants (‐, ‑, –)
+/// to `-`, so "don’t" matches "don't" in the dictionary
+pub fn normalize_punctuation(word: &str) -> Cow<'_, str> {
+    if !word.contains(['’', 'ʼ', '‐', '‑', '–']) {
+        return Cow::Borrowed(word);
+    }
+    
+    Cow::Owned(word.chars()
+        .map(|c| match c {
+            '’' | 'ʼ' => '\'',
+            '‐' | '‑' | '–' => '-',
+            c => c,
+        })
+        .collect())
+}
+
 /// Extract words from text based on language and context
 pub fn extract_words(text: &str, is_cjk: bool, is_code: bool) -> Vec<String> {
     if is_cjk {
         CJK_WORD_REGEX
             .find_iter(text)
-            .map(|mat| mat.as_str().to_string())
+            .map(|mat| normalize_punctuation(mat.as_str()).into_owned())
             .collect()
     } else if is_code {
         // For code, we want to be more selective
@@ -64,12 +120,12 @@ pub fn extract_words(text: &str, is_cjk: bool, is_code: bool) -> Vec<String> {
                 !is_likely_code_symbol(word) &&
                 !is_common_code_pattern(word)
             })
-            .map(|word| word.to_lowercase())
+            .map(|word| normalize_punctuation(word).to_lowercase())
             .collect()
     } else {
         WORD_REGEX
             .find_iter(text)
-            .map(|mat| mat.as_str().to_lowercase())
+            .map(|mat| normalize_punctuation(mat.as_str()).to_lowercase())
             .collect()
     }
 }
@@ -141,7 +197,8 @@ pub fn calculate_accuracy(correct: usize, total: usize) -> f32 {
 
 /// Sanitize word by removing invalid characters
 pub fn sanitize_word(word: &str) -> String {
-    let trimmed = word.trim();
+    let normalized = normalize_punctuation(word);
+    let trimmed = normalized.trim();
     if trimmed.is_empty() {
         return String::new();
     }
@@ -172,13 +229,56 @@ pub fn is_valid_word(word: &str) -> bool {
     trimmed.len() >= 2
 }
 
+/// `suggestion` cased like `original`: all caps if `original` is
+/// ("RECIEVE" -> "RECEIVE"), capitalized if `original` starts with a
+/// capital ("Recieve" -> "Receive"), otherwise unchanged
+pub fn match_case(suggestion: &str, original: &str) -> String {
+    let mut letters = original.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return suggestion.to_string();
+    };
+    
+    let rest: Vec<char> = letters.collect();
+    if first.is_uppercase() && !rest.is_empty() && rest.iter().all(|c| c.is_uppercase()) {
+        return suggestion.to_uppercase();
+    }
+    if first.is_uppercase() {
+        let mut chars = suggestion.chars();
+        if let Some(initial) = chars.next() {
+            return initial.to_uppercase().chain(chars).collect();
+        }
+    }
+    
+    suggestion.to_string()
+}
+
+/// Bytes `is_probably_binary` looks at
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Whether `bytes` look like binary data rather than text: the first 8KB
+/// hold a NUL byte, or more than one control character (other than
+/// whitespace and escape) in ten. Bytes above ASCII count as text, since
+/// they may be UTF-8 or a legacy encoding.
+pub fn is_probably_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+    if sample.contains(&0) {
+        return true;
+    }
+    
+    let control = sample
+        .iter()
+        .filter(|&&b| (b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b)) || b == 0x7f)
+        .count();
+    control * 10 > sample.len()
+}
+
 /// Check if text contains CJK characters
 pub fn is_cjk_text(text: &str) -> bool {
     text.chars().any(|c| {
-        ('\u{4E00}' <= c && c <= '\u{9FFF}') || // Chinese
-        ('\u{3040}' <= c && c <= '\u{309F}') || // Hiragana
-        ('\u{30A0}' <= c && c <= '\u{30FF}') || // Katakana
-        ('\u{AC00}' <= c && c <= '\u{D7AF}')    // Hangul
+        ('\u{4E00}'..='\u{9FFF}').contains(&c) || // Chinese
+        ('\u{3040}'..='\u{309F}').contains(&c) || // Hiragana
+        ('\u{30A0}'..='\u{30FF}').contains(&c) || // Katakana
+        ('\u{AC00}'..='\u{D7AF}').contains(&c)    // Hangul
     })
 }
 
@@ -233,6 +333,355 @@ pub fn is_code_file(filename: &str) -> bool {
     }
 }
 
+/// Match a path against a shell-style glob: `*` and `?` stay within one
+/// path component, `**` spans directories. A pattern without `/` also
+/// matches any single component, so `target` or `*.draft.md` work at any depth.
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    fn matches(pattern: &[char], path: &[char]) -> bool {
+        match pattern {
+            [] => path.is_empty(),
+            ['*', '*', rest @ ..] => {
+                let rest = rest.strip_prefix(&['/']).unwrap_or(rest);
+                (0..=path.len()).any(|idx| matches(rest, &path[idx..]))
+            }
+            ['*', rest @ ..] => {
+                let component_end = path.iter().position(|&c| c == '/').unwrap_or(path.len());
+                (0..=component_end).any(|idx| matches(rest, &path[idx..]))
+            }
+            ['?', rest @ ..] => path.first().is_some_and(|&c| c != '/') && matches(rest, &path[1..]),
+            [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+        }
+    }
+    
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path = path.replace('\\', "/");
+    
+    if matches(&pattern, &path.chars().collect::<Vec<_>>()) {
+        return true;
+    }
+    
+    !pattern.contains(&'/')
+        && path.split('/').any(|component| matches(&pattern, &component.chars().collect::<Vec<_>>()))
+}
+
+/// Map a filename's extension to the programming language name used for
+/// syntax-aware features
+pub fn programming_language_from_filename(filename: &str) -> Option<&'static str> {
+    match filename.rsplit('.').next() {
+        Some("rs") => Some("rust"),
+        Some("py") => Some("python"),
+        Some("js") | Some("jsx") => Some("javascript"),
+        Some("ts") | Some("tsx") => Some("typescript"),
+        Some("java") => Some("java"),
+        Some("cpp") | Some("cc") | Some("cxx") | Some("c") => Some("cpp"),
+        Some("go") => Some("go"),
+        Some("cs") => Some("csharp"),
+        Some("rb") => Some("ruby"),
+        Some("php") => Some("php"),
+        Some("html") | Some("htm") => Some("html"),
+        Some("css") => Some("css"),
+        Some("md") => Some("markdown"),
+        Some("json") => Some("json"),
+        Some("toml") => Some("toml"),
+        Some("yaml") | Some("yml") => Some("yaml"),
+        Some("xml") => Some("xml"),
+        Some("sh") | Some("bash") => Some("shell"),
+        _ => None,
+    }
+}
+
+/// Split a code identifier into snake_case and camelCase parts with their
+/// byte offsets, keeping acronym runs together ("HTTPServer" -> "HTTP", "Server")
+pub fn split_identifier(identifier: &str) -> Vec<(usize, &str)> {
+    let chars: Vec<(usize, char)> = identifier.char_indices().collect();
+    let byte_at = |idx: usize| chars.get(idx).map(|(b, _)| *b).unwrap_or(identifier.len());
+    
+    let mut parts = Vec::new();
+    let mut part_start: Option<usize> = None;
+    
+    for (idx, &(_, c)) in chars.iter().enumerate() {
+        if !c.is_alphabetic() {
+            if let Some(start) = part_start.take() {
+                parts.push((byte_at(start), &identifier[byte_at(start)..byte_at(idx)]));
+            }
+            continue;
+        }
+        
+        match part_start {
+            Some(start) => {
+                let prev = chars[idx - 1].1;
+                let next_is_lower = chars.get(idx + 1).map(|(_, n)| n.is_lowercase()).unwrap_or(false);
+                if (prev.is_lowercase() && c.is_uppercase())
+                    || (prev.is_uppercase() && c.is_uppercase() && next_is_lower) {
+                    parts.push((byte_at(start), &identifier[byte_at(start)..byte_at(idx)]));
+                    part_start = Some(idx);
+                }
+            }
+            None => part_start = Some(idx),
+        }
+    }
+    
+    if let Some(start) = part_start {
+        parts.push((byte_at(start), &identifier[byte_at(start)..]));
+    }
+    
+    parts
+}
+
+/// Widen the byte range `start..end` so it doesn't cut a word in half. Ends
+/// past the text are clamped and ends inside a char move outwards.
+pub fn expand_to_word_boundaries(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+    
+    let mut start = start.min(text.len());
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = end.clamp(start, text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    
+    while let Some(c) = text[..start].chars().next_back().filter(|&c| is_word_char(c)) {
+        start -= c.len_utf8();
+    }
+    while let Some(c) = text[end..].chars().next().filter(|&c| is_word_char(c)) {
+        end += c.len_utf8();
+    }
+    
+    (start, end)
+}
+
+/// Byte offset at which each line of `text` starts, matching the lines
+/// produced by `str::lines`
+pub fn line_start_offsets(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.split_inclusive('\n').scan(0, |offset, line| {
+            *offset += line.len();
+            Some(*offset)
+        }))
+        .collect()
+}
+
+/// The 1-based character column of the byte `byte_offset` of `line`, so
+/// "é" before a word moves it one column, not two. Offsets past the end or
+/// inside a character count the characters that start before them.
+pub fn byte_to_char_column(line: &str, byte_offset: usize) -> usize {
+    line.char_indices().take_while(|&(i, _)| i < byte_offset).count() + 1
+}
+
+/// An ATX heading (`## Title`) in a Markdown document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownHeading {
+    /// 1 for `#` through 6 for `######`
+    pub level: usize,
+    pub title: String,
+    /// Byte offset of the start of the heading's line
+    pub start: usize,
+}
+
+/// The ATX headings of a Markdown document, in order. Lines inside fenced
+/// code blocks are skipped, so a `# comment` in a shell snippet isn't one.
+pub fn markdown_headings(text: &str) -> Vec<MarkdownHeading> {
+    let mut headings = Vec::new();
+    // The fence character and length of the code block we're in, if any
+    let mut fence: Option<(char, usize)> = None;
+    
+    for (line, start) in text.lines().zip(line_start_offsets(text)) {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if indent > 3 {
+            continue;
+        }
+        let line = &line[indent..];
+        
+        if let Some(marker) = line.chars().next().filter(|&c| c == '`' || c == '~') {
+            let length = line.chars().take_while(|&c| c == marker).count();
+            if length >= 3 {
+                fence = match fence {
+                    None => Some((marker, length)),
+                    Some((open, open_length)) if open == marker && length >= open_length
+                        && line[length..].trim().is_empty() => None,
+                    open => open,
+                };
+                continue;
+            }
+        }
+        if fence.is_some() {
+            continue;
+        }
+        
+        let level = line.chars().take_while(|&c| c == '#').count();
+        let rest = &line[level..];
+        if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+            continue;
+        }
+        
+        // An optional closing run of `#`s only counts after whitespace
+        let mut title = rest.trim();
+        let without_closing = title.trim_end_matches('#');
+        if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+            title = without_closing.trim_end();
+        }
+        
+        headings.push(MarkdownHeading { level, title: title.to_string(), start });
+    }
+    
+    headings
+}
+
+/// Byte range of the section under the first heading titled `title`,
+/// compared case-insensitively: from the heading line up to the next
+/// heading of the same or a higher level, or the end of the text
+pub fn markdown_section(text: &str, title: &str) -> Option<(usize, usize)> {
+    let title = title.trim().to_lowercase();
+    let headings = markdown_headings(text);
+    let index = headings.iter().position(|h| h.title.to_lowercase() == title)?;
+    let heading = &headings[index];
+    
+    let end = headings[index + 1..]
+        .iter()
+        .find(|h| h.level <= heading.level)
+        .map_or(text.len(), |h| h.start);
+    Some((heading.start, end))
+}
+
+/// Byte ranges of the text a reader of an HTML or XML document sees: the
+/// content between tags and the values of `alt` and `title` attributes, in
+/// order. Tags, comments, character references and the contents of
+/// `<script>` and `<style>` elements are left out.
+pub fn html_text_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut text_start = 0;
+    
+    while let Some(offset) = text[text_start..].find('<') {
+        let tag_start = text_start + offset;
+        push_html_text(&mut spans, text, text_start, tag_start);
+        
+        let rest = &text[tag_start..];
+        if rest.starts_with("<!--") {
+            text_start = rest.find("-->").map_or(text.len(), |p| tag_start + p + 3);
+            continue;
+        }
+        
+        let tag_end = html_tag_len(rest).map_or(text.len(), |len| tag_start + len);
+        let tag = &text[tag_start..tag_end];
+        for caps in HTML_TEXT_ATTRIBUTE_REGEX.captures_iter(tag) {
+            if let Some(value) = caps.get(1).or_else(|| caps.get(2)) {
+                push_html_text(&mut spans, text, tag_start + value.start(), tag_start + value.end());
+            }
+        }
+        
+        // Scripts and styles run to their closing tag whatever they contain
+        let name: String = tag[1..].chars().take_while(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_lowercase();
+        text_start = if name == "script" || name == "style" {
+            let closing = format!("</{}", name);
+            text[tag_end..].to_ascii_lowercase().find(&closing).map_or(text.len(), |p| tag_end + p)
+        } else {
+            tag_end
+        };
+    }
+    
+    push_html_text(&mut spans, text, text_start, text.len());
+    spans
+}
+
+/// Length of the tag opening `text` up to and including its `>`, skipping
+/// any `>` inside quoted attribute values; `None` if it's never closed
+fn html_tag_len(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (idx, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(idx + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Add `start..end` of `text` to `spans`, split around character references
+fn push_html_text(spans: &mut Vec<(usize, usize)>, text: &str, start: usize, end: usize) {
+    let mut from = start;
+    for entity in HTML_ENTITY_REGEX.find_iter(&text[start..end]) {
+        if start + entity.start() > from {
+            spans.push((from, start + entity.start()));
+        }
+        from = start + entity.end();
+    }
+    if end > from {
+        spans.push((from, end));
+    }
+}
+
+/// Apply `(start, end, replacement)` byte-range edits to `text`. Edits must
+/// be sorted by start and not overlap; they are applied from the end
+/// backwards so earlier offsets stay valid. Edits outside `text` or off a
+/// char boundary are skipped.
+pub fn apply_edits(text: &str, edits: &[(usize, usize, String)]) -> String {
+    let mut result = text.to_string();
+    for (start, end, replacement) in edits.iter().rev() {
+        if result.get(*start..*end).is_some() {
+            result.replace_range(*start..*end, replacement);
+        }
+    }
+    result
+}
+
+/// Render a unified diff between `old` and `new` for `path`, with three lines
+/// of context. Lines are compared by position, which suits in-place word
+/// corrections that never add or remove lines.
+pub fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    const CONTEXT: usize = 3;
+    
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let line_count = old_lines.len().max(new_lines.len());
+    
+    let changed: Vec<usize> = (0..line_count)
+        .filter(|&idx| old_lines.get(idx) != new_lines.get(idx))
+        .collect();
+    
+    if changed.is_empty() {
+        return String::new();
+    }
+    
+    // Group changed lines whose context windows touch into one hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changed {
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + CONTEXT + 1).min(line_count);
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+    for (start, end) in hunks {
+        let old_len = end.min(old_lines.len()).saturating_sub(start);
+        let new_len = end.min(new_lines.len()).saturating_sub(start);
+        diff.push_str(&format!("@@ -{},{} +{},{} @@\n", start + 1, old_len, start + 1, new_len));
+        
+        for idx in start..end {
+            match (old_lines.get(idx), new_lines.get(idx)) {
+                (Some(a), Some(b)) if a == b => diff.push_str(&format!(" {}\n", a)),
+                (a, b) => {
+                    if let Some(a) = a {
+                        diff.push_str(&format!("-{}\n", a));
+                    }
+                    if let Some(b) = b {
+                        diff.push_str(&format!("+{}\n", b));
+                    }
+                }
+            }
+        }
+    }
+    
+    diff
+}
+
 /// Calculate word similarity using Levenshtein distance
 pub fn levenshtein_distance(a: &str, b: &str) -> usize {
     if a == b { return 0; }
@@ -266,6 +715,51 @@ pub fn levenshtein_distance(a: &str, b: &str) -> usize {
     prev_row[b_len]
 }
 
+/// Levenshtein distance from the chars `a` to `b`, or `None` if it's more
+/// than `max`. Only cells within `max` of the diagonal are computed, and
+/// the search stops as soon as a whole row exceeds `max`, so distant words
+/// are rejected after a few characters.
+pub fn bounded_levenshtein(a: &[char], b: &str, max: usize) -> Option<usize> {
+    let b_len = b.chars().count();
+    if a.len().abs_diff(b_len) > max {
+        return None;
+    }
+    
+    // Cells outside the band hold `max + 1`, meaning "too far"
+    let too_far = max + 1;
+    let mut prev_row: Vec<usize> = (0..=a.len()).map(|j| j.min(too_far)).collect();
+    let mut curr_row = vec![too_far; a.len() + 1];
+    
+    for (i, b_char) in b.chars().enumerate() {
+        let i = i + 1;
+        let from = i.saturating_sub(max).max(1);
+        let to = (i + max).min(a.len());
+        
+        curr_row.fill(too_far);
+        if i <= max {
+            curr_row[0] = i;
+        }
+        
+        let mut row_min = curr_row[0];
+        for j in from..=to {
+            let cost = usize::from(a[j - 1] != b_char);
+            let distance = (prev_row[j] + 1)
+                .min(curr_row[j - 1] + 1)
+                .min(prev_row[j - 1] + cost)
+                .min(too_far);
+            curr_row[j] = distance;
+            row_min = row_min.min(distance);
+        }
+        
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev_row, &mut curr_row);
+    }
+    
+    Some(prev_row[a.len()]).filter(|&distance| distance <= max)
+}
+
 /// Get suggestions for a misspelled word
 pub fn get_suggestions(word: &str, dictionary_words: &std::collections::HashSet<String>) -> Vec<String> {
     if word.len() <= 1 {
@@ -291,4 +785,215 @@ pub fn get_suggestions(word: &str, dictionary_words: &std::collections::HashSet<
         .take(5)
         .map(|(word, _)| word)
         .collect()
-}
\ No newline at end of file
+}
+
+/// Metaphone key for `word`: a rough English pronunciation code, so words
+/// that sound alike ("fonetik", "phonetic") share a key. Non-ASCII letters
+/// are ignored; returns an empty key if nothing is left.
+pub fn metaphone(word: &str) -> String {
+    let mut letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    
+    // Silent or special first letters
+    match letters.as_slice() {
+        ['A', 'E', ..] | ['G', 'N', ..] | ['K', 'N', ..] | ['P', 'N', ..] | ['W', 'R', ..] => {
+            letters.remove(0);
+        }
+        ['X', ..] => letters[0] = 'S',
+        ['W', 'H', ..] => {
+            letters.remove(1);
+        }
+        _ => {}
+    }
+    
+    let is_vowel = |c: Option<&char>| matches!(c, Some('A' | 'E' | 'I' | 'O' | 'U'));
+    let is_front_vowel = |c: Option<&char>| matches!(c, Some('E' | 'I' | 'Y'));
+    let mut code = String::new();
+    
+    for (i, &c) in letters.iter().enumerate() {
+        let prev = i.checked_sub(1).and_then(|p| letters.get(p));
+        let next = letters.get(i + 1);
+        let after_next = letters.get(i + 2);
+        
+        if prev == Some(&c) && c != 'C' {
+            continue;
+        }
+        
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    code.push(c);
+                }
+            }
+            'B' => {
+                if !(prev == Some(&'M') && next.is_none()) {
+                    code.push('B');
+                }
+            }
+            'C' => {
+                if next == Some(&'I') && after_next == Some(&'A') {
+                    code.push('X');
+                } else if next == Some(&'H') {
+                    code.push(if prev == Some(&'S') { 'K' } else { 'X' });
+                } else if is_front_vowel(next) {
+                    if prev != Some(&'S') {
+                        code.push('S');
+                    }
+                } else {
+                    code.push('K');
+                }
+            }
+            'D' => {
+                if next == Some(&'G') && is_front_vowel(after_next) {
+                    code.push('J');
+                } else {
+                    code.push('T');
+                }
+            }
+            'G' => {
+                if next == Some(&'H') && !is_vowel(after_next) {
+                    continue;
+                }
+                if next == Some(&'N') && (after_next.is_none() || letters[i + 2..] == ['E', 'D']) {
+                    continue;
+                }
+                if is_front_vowel(next) {
+                    code.push('J');
+                } else {
+                    code.push('K');
+                }
+            }
+            'H' => {
+                if is_vowel(next) && !matches!(prev, Some('C' | 'S' | 'P' | 'T' | 'G')) {
+                    code.push('H');
+                }
+            }
+            'K' => {
+                if prev != Some(&'C') {
+                    code.push('K');
+                }
+            }
+            'P' => code.push(if next == Some(&'H') { 'F' } else { 'P' }),
+            'Q' => code.push('K'),
+            'S' => {
+                if next == Some(&'H') || (next == Some(&'I') && matches!(after_next, Some('O' | 'A'))) {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            'T' => {
+                if next == Some(&'I') && matches!(after_next, Some('O' | 'A')) {
+                    code.push('X');
+                } else if next == Some(&'H') {
+                    code.push('0');
+                } else if !(next == Some(&'C') && after_next == Some(&'H')) {
+                    code.push('T');
+                }
+            }
+            'V' => code.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(next) {
+                    code.push(c);
+                }
+            }
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => code.push(c),
+        }
+    }
+    
+    code
+}
+
+// Regex for letter runs that may hide mixed-script words. Unlike the code
+// word regexes it isn't limited to ASCII, so a Cyrillic letter inside a
+// Latin identifier doesn't split it.
+pub static SCRIPT_TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\p{L}\p{M}][\p{L}\p{M}\p{N}_]*").unwrap()
+});
+
+/// Scripts whose letters are easily confused with each other
+const CONFUSABLE_SCRIPTS: [Script; 3] = [Script::Latin, Script::Cyrillic, Script::Greek];
+
+/// Latin letters and the Cyrillic or Greek letters that look like them
+const CONFUSABLES: &[(char, char)] = &[
+    ('a', 'а'), ('c', 'с'), ('d', 'ԁ'), ('e', 'е'), ('h', 'һ'), ('i', 'і'),
+    ('j', 'ј'), ('l', 'ӏ'), ('o', 'о'), ('p', 'р'), ('q', 'ԛ'), ('s', 'ѕ'),
+    ('w', 'ԝ'), ('x', 'х'), ('y', 'у'),
+    ('A', 'А'), ('B', 'В'), ('C', 'С'), ('E', 'Е'), ('H', 'Н'), ('I', 'І'),
+    ('J', 'Ј'), ('K', 'К'), ('M', 'М'), ('O', 'О'), ('P', 'Р'), ('S', 'Ѕ'),
+    ('T', 'Т'), ('X', 'Х'), ('Y', 'У'),
+    ('i', 'ι'), ('o', 'ο'), ('v', 'ν'),
+    ('A', 'Α'), ('B', 'Β'), ('E', 'Ε'), ('H', 'Η'), ('I', 'Ι'), ('K', 'Κ'),
+    ('M', 'Μ'), ('N', 'Ν'), ('O', 'Ο'), ('P', 'Ρ'), ('T', 'Τ'), ('X', 'Χ'),
+    ('Y', 'Υ'), ('Z', 'Ζ'),
+];
+
+/// The script most of `word`'s letters are written in, if more than one
+/// of Latin, Cyrillic and Greek appear in it. Other mixes (Japanese kana
+/// with kanji, say) are normal and return `None`.
+pub fn mixed_script_dominant(word: &str) -> Option<Script> {
+    let mut counts = [0usize; CONFUSABLE_SCRIPTS.len()];
+    for c in word.chars() {
+        if let Some(i) = CONFUSABLE_SCRIPTS.iter().position(|&s| s == c.script()) {
+            counts[i] += 1;
+        }
+    }
+    
+    if counts.iter().filter(|&&count| count > 0).count() < 2 {
+        return None;
+    }
+    // Ties go to the earlier script, so Latin wins over a lookalike
+    let dominant = counts.iter().enumerate().rev().max_by_key(|&(_, count)| count).map(|(i, _)| i)?;
+    Some(CONFUSABLE_SCRIPTS[dominant])
+}
+
+/// Replace lookalike letters in `word` with their `script` equivalents.
+/// Letters without a lookalike are left as they are.
+pub fn normalize_confusables(word: &str, script: Script) -> String {
+    word.chars()
+        .map(|c| {
+            if c.script() == script {
+                return c;
+            }
+            CONFUSABLES.iter()
+                .find_map(|&(latin, other)| {
+                    if c == other && latin.script() == script {
+                        Some(latin)
+                    } else if c == latin && other.script() == script {
+                        Some(other)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(c)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn nul_bytes_are_binary_but_utf8_text_is_not() {
+        assert!(is_probably_binary(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        assert!(is_probably_binary(b"plain text until\0here"));
+        assert!(!is_probably_binary("Größe, café and 日本語\n\ttabbed line\r\n".as_bytes()));
+        assert!(!is_probably_binary(b""));
+    }
+    
+    #[test]
+    fn match_case_follows_the_original_casing() {
+        assert_eq!(match_case("receive", "Recieve"), "Receive");
+        assert_eq!(match_case("receive", "RECIEVE"), "RECEIVE");
+        assert_eq!(match_case("receive", "recieve"), "receive");
+        // A lone capital is a capitalized word, not an all-caps one
+        assert_eq!(match_case("an", "A"), "An");
+        assert_eq!(match_case("éclair", "Eclaire"), "Éclair");
+    }
+}